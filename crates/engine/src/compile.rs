@@ -0,0 +1,31 @@
+use std::io::{stdin, stdout, Read, Write};
+
+use anyhow::{anyhow, Result};
+use quickjs_wasm_rs::Context;
+
+use crate::modules::{self, Source};
+
+/// Set by the host to run the engine as a compiler, with `modules` or `script` as value.
+pub const COMPILE_ENV: &str = "JS_WASM_WORKERS_COMPILE";
+
+/// Compiles the source read from stdin to QuickJS bytecode, written to stdout.
+///
+/// The `modules` mode reads the same source as an invocation, and writes its modules as JSON. The
+/// `script` mode reads a global script, like the web platform APIs, and writes its raw bytecode.
+pub fn compile(mode: &str) -> Result<()> {
+    let context = Context::default();
+    let mut input = String::new();
+
+    stdin().read_to_string(&mut input)?;
+
+    let output = match mode {
+        "modules" => serde_json::to_vec(&modules::compile(&context, &Source::parse(&input))?)?,
+        "script" => context.compile_global("web-platform-apis.js", &input)?,
+        _ => return Err(anyhow!("Unknown compile mode: {mode}")),
+    };
+
+    stdout().write_all(&output)?;
+    stdout().flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+
+use anyhow::Result;
+use quickjs_wasm_rs::Value;
+use serde::Serialize;
+
+use crate::fetch::mem::ToMem;
+
+extern "C" {
+    fn import_error(ptr: *const u8);
+}
+
+// Set by the web platform APIs on the errors they throw for the handler
+const KIND_PROPERTY: &str = "___kind";
+
+/// What failed, which the host maps to the status of its error response.
+#[derive(Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// An uncaught exception, or a rejection, of the handler or its evaluation
+    #[default]
+    Exception,
+    /// The handler doesn't export nor register a request handler
+    MissingHandler,
+    /// The handler returned something else than a `Response`
+    InvalidResponse,
+}
+
+/// An error of the handler, sent to the host as `{name, message, stack, kind}`.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct HandlerError {
+    pub name: String,
+    pub message: String,
+    pub stack: Option<String>,
+    pub kind: ErrorKind,
+}
+
+impl HandlerError {
+    /// Reads a thrown value, which may not be an `Error`, e.g. `throw "message"`.
+    pub fn from_value(value: &Value) -> Result<Self> {
+        if !value.is_object() {
+            return Ok(Self {
+                name: "Error".to_string(),
+                message: value.as_str()?.to_string(),
+                stack: None,
+                kind: ErrorKind::Exception,
+            });
+        }
+
+        let property = |name: &str| -> Result<Option<String>> {
+            let property = value.get_property(name)?;
+
+            if property.is_undefined() || property.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(property.as_str()?.to_string()))
+            }
+        };
+
+        Ok(Self {
+            name: property("name")?.unwrap_or_else(|| "Error".to_string()),
+            message: property("message")?.unwrap_or_default(),
+            stack: property("stack")?.filter(|stack| !stack.is_empty()),
+            kind: match property(KIND_PROPERTY)?.as_deref() {
+                Some("invalid_response") => ErrorKind::InvalidResponse,
+                Some("missing_handler") => ErrorKind::MissingHandler,
+                _ => ErrorKind::Exception,
+            },
+        })
+    }
+
+    pub fn missing_handler(message: &str) -> Self {
+        Self {
+            name: "Error".to_string(),
+            message: message.to_string(),
+            stack: None,
+            kind: ErrorKind::MissingHandler,
+        }
+    }
+
+    /// Reads an error without its JS value, like the ones of the module evaluation, formatted as
+    /// `Uncaught SyntaxError: message` followed by the stack.
+    pub fn from_error(error: &anyhow::Error) -> Self {
+        let error = error.to_string();
+        let (first_line, stack) = error.split_once('\n').unwrap_or((&error, ""));
+        let first_line = first_line.trim_start_matches("Uncaught ");
+        let (name, message) = match first_line.split_once(": ") {
+            Some((name, message)) if is_error_name(name) => (name, message),
+            _ => ("Error", first_line),
+        };
+
+        Self {
+            name: name.to_string(),
+            message: message.to_string(),
+            stack: Some(stack.to_string()).filter(|stack| !stack.trim().is_empty()),
+            kind: ErrorKind::Exception,
+        }
+    }
+
+    pub fn report(&self) {
+        let json = serde_json::to_string(self).unwrap();
+
+        unsafe { import_error(json.as_str().to_mem()) };
+    }
+}
+
+impl fmt::Display for HandlerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.message)
+    }
+}
+
+impl std::error::Error for HandlerError {}
+
+fn is_error_name(name: &str) -> bool {
+    name.ends_with("Error") && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
@@ -1,4 +1,4 @@
 #![allow(clippy::module_inception)]
 pub mod fetch;
-mod http;
-mod mem;
+pub(crate) mod http;
+pub(crate) mod mem;
@@ -1,9 +1,19 @@
+use std::collections::HashMap;
+
 use anyhow::{anyhow, Result};
+use http::header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH};
 use quickjs_wasm_rs::{Context, Value};
 use serde_bytes::ByteBuf;
 
 use super::http::*;
 use super::mem::{FromMem, ToMem};
+use crate::globals::compression::{decompress, Format};
+
+// The content codings decoded by `fetch`, announced unless the request has its own
+const SUPPORTED_ENCODINGS: &str = "gzip, deflate, br";
+
+// The maximum size of a decoded response body, so a small compressed body can't exhaust the memory
+const MAX_DECODED_BODY_SIZE: usize = 128 * 1024 * 1024;
 
 extern "C" {
     fn import_send_request(ptr: *mut u8) -> *mut u8;
@@ -38,21 +48,59 @@ fn fetcher(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
                 Some(ByteBuf::from(bytes))
             };
             let headers = request.get_property("headers")?.as_str()?.to_string();
+            let mut headers: HashMap<String, String> = serde_json::from_str(&headers)?;
+
+            if !headers
+                .keys()
+                .any(|name| name.eq_ignore_ascii_case(ACCEPT_ENCODING.as_str()))
+            {
+                headers.insert(ACCEPT_ENCODING.to_string(), SUPPORTED_ENCODINGS.to_string());
+            }
 
             let response = send_request(Request {
                 method,
                 url,
-                headers: Some(serde_json::from_str(&headers)?),
+                headers: Some(headers),
                 body,
                 // body: Some(ByteBuf::from(body)),
             })?;
 
-            context.value_from_str(&response)
+            context.value_from_str(&decode_response(response, MAX_DECODED_BODY_SIZE)?)
         }
         _ => Err(anyhow!("expected 1 argument, got {}", args.len())),
     }
 }
 
+// Decompresses the body of a response with a supported `Content-Encoding`, like the browsers do.
+// The `Content-Encoding` and `Content-Length` headers are removed, as they don't describe the
+// decoded body, so the response can be returned by the handler as is. A body which decodes to more
+// than `limit` bytes is a `TypeError`.
+pub(crate) fn decode_response(response: String, limit: usize) -> Result<String> {
+    let mut decoded: Response = serde_json::from_str(&response)?;
+    let mut headers: HashMap<String, String> =
+        serde_json::from_str(decoded.headers.as_deref().unwrap_or("{}"))?;
+    let format = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(CONTENT_ENCODING.as_str()))
+        .and_then(|(_, coding)| Format::from_content_encoding(coding));
+
+    match (format, decoded.body.as_mut()) {
+        // The responses without a body, e.g. to a `HEAD` request, keep their headers
+        (Some(format), Some(body)) if !body.is_empty() => {
+            *body = ByteBuf::from(decompress(format, body, limit)?);
+        }
+        _ => return Ok(response),
+    }
+
+    headers.retain(|name, _| {
+        !name.eq_ignore_ascii_case(CONTENT_ENCODING.as_str())
+            && !name.eq_ignore_ascii_case(CONTENT_LENGTH.as_str())
+    });
+    decoded.headers = Some(serde_json::to_string(&headers)?);
+
+    Ok(serde_json::to_string(&decoded)?)
+}
+
 fn send_request(request: Request) -> Result<String> {
     let req = serde_json::to_string(&request)?;
 
@@ -15,7 +15,8 @@ pub struct Request {
 pub struct Response {
     pub status: usize,
     pub body: Option<ByteBuf>,
-    pub headers: Option<HashMap<String, String>>,
+    /// The headers serialized as a JSON object, as sent by the host
+    pub headers: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -84,6 +84,23 @@ impl ToMem for &str {
     }
 }
 
+impl ToMem for &[u8] {
+    type Type = *const u8;
+    fn to_mem(self) -> Self::Type {
+        stack_push(self.len() as i32);
+        self.as_ptr()
+    }
+}
+
+impl FromMem for Vec<u8> {
+    type Type = *mut u8;
+    fn from_mem(value: Self::Type) -> Self {
+        let len = stack_pop() as usize;
+
+        unsafe { Vec::from_raw_parts(value, len, len) }
+    }
+}
+
 impl<T, E> ToMem for Result<T, E>
 where
     Self: Serialize,
@@ -1,2 +1,12 @@
+pub mod blob;
+pub mod compression;
 pub mod console;
+pub mod headers;
+pub mod kv;
+pub mod multipart;
+pub mod registry;
+pub mod text_encoding;
+pub mod url;
+pub mod url_pattern;
 pub mod utils;
+pub mod websocket;
@@ -0,0 +1,92 @@
+use anyhow::{anyhow, Result};
+use quickjs_wasm_rs::{Context, Value};
+
+use super::registry::{index, Registry};
+
+// The bytes of the blobs. They are immutable, so the slices of a blob share its bytes, and its
+// handle, instead of copying them.
+static BLOBS: Registry<Box<[u8]>> = Registry::new("blob");
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Copies the bytes from `start` to `end` of a blob, which are clamped to its size.
+pub fn read(id: usize, start: usize, end: usize) -> Result<Vec<u8>> {
+    BLOBS.get(id, |bytes| {
+        let end = end.min(bytes.len());
+
+        Ok(bytes[start.min(end)..end].to_vec())
+    })
+}
+
+pub(crate) fn set_global_blob(context: &Context) -> Result<()> {
+    let global = context.global_object()?;
+    let blob = context.object_value()?;
+
+    blob.set_property("create", context.wrap_callback(create_blob)?)?;
+    blob.set_property("read", context.wrap_callback(read_blob)?)?;
+    blob.set_property("text", context.wrap_callback(read_blob_text)?)?;
+
+    global.set_property("___blob", blob)?;
+
+    Ok(())
+}
+
+// Receives an array of parts, each either `{ bytes }` with an `ArrayBuffer`, or `{ id, start, end }`
+// for a slice of another blob, and returns the `[id, handle]` of their concatenation
+fn create_blob(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [parts] => {
+            let length = parts.get_property("length")?.as_u32_unchecked();
+            let parts = (0..length)
+                .map(|index| {
+                    let part = parts.get_property(index.to_string())?;
+                    let bytes = part.get_property("bytes")?;
+
+                    if bytes.is_undefined() {
+                        let (id, start, end) = range(
+                            &part.get_property("id")?,
+                            &part.get_property("start")?,
+                            &part.get_property("end")?,
+                        )?;
+
+                        read(id, start, end)
+                    } else {
+                        Ok(bytes.as_bytes()?.to_vec())
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            BLOBS.insert_with_handle(context, parts.concat().into_boxed_slice())
+        }
+        _ => Err(anyhow!("expected 1 argument, got {}", args.len())),
+    }
+}
+
+fn read_blob(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, start, end] => {
+            let (id, start, end) = range(id, start, end)?;
+
+            context.array_buffer_value(&read(id, start, end)?)
+        }
+        _ => Err(anyhow!("expected 3 arguments, got {}", args.len())),
+    }
+}
+
+// Decodes as UTF-8, like `TextDecoder`, so the text isn't copied to JS as bytes first
+fn read_blob_text(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, start, end] => {
+            let (id, start, end) = range(id, start, end)?;
+            let bytes = read(id, start, end)?;
+            let bytes = bytes.strip_prefix(UTF8_BOM).unwrap_or(&bytes);
+
+            context.value_from_str(&String::from_utf8_lossy(bytes))
+        }
+        _ => Err(anyhow!("expected 3 arguments, got {}", args.len())),
+    }
+}
+
+fn range(id: &Value, start: &Value, end: &Value) -> Result<(usize, usize, usize)> {
+    Ok((index(id)?, index(start)?, index(end)?))
+}
@@ -0,0 +1,330 @@
+use std::{error::Error, fmt, io, io::Write, mem};
+
+use anyhow::{anyhow, Result};
+use brotli_decompressor::DecompressorWriter;
+use flate2::{
+    write::{DeflateDecoder, DeflateEncoder, GzDecoder, GzEncoder, ZlibDecoder, ZlibEncoder},
+    Compression,
+};
+use quickjs_wasm_rs::{Context, JSError, Value};
+
+use super::registry::{index, Registry};
+
+// The `CompressionStream` and `DecompressionStream` in progress, removed once finished
+static STREAMS: Registry<Stream> = Registry::new("compression stream");
+
+// The size of the buffer of the brotli decoder
+const BROTLI_BUFFER_SIZE: usize = 4096;
+
+// The maximum output of a chunk written to a `DecompressionStream`, so a small chunk can't exhaust
+// the memory
+const STREAM_CHUNK_LIMIT: usize = 64 * 1024 * 1024;
+
+/// The compression formats, `deflate` being the zlib format and `deflate-raw` the bare DEFLATE one.
+///
+/// @see: https://compression.spec.whatwg.org/#supported-formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Gzip,
+    Deflate,
+    DeflateRaw,
+    Brotli,
+}
+
+impl Format {
+    /// The format of `CompressionStream` and `DecompressionStream`, brotli isn't one of them.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "gzip" => Some(Self::Gzip),
+            "deflate" => Some(Self::Deflate),
+            "deflate-raw" => Some(Self::DeflateRaw),
+            _ => None,
+        }
+    }
+
+    /// The format of a `Content-Encoding`, as decoded by `fetch`.
+    pub fn from_content_encoding(coding: &str) -> Option<Self> {
+        match coding.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(Self::Gzip),
+            "deflate" => Some(Self::Deflate),
+            "br" => Some(Self::Brotli),
+            _ => None,
+        }
+    }
+}
+
+/// Compresses the chunks written to it. The output is buffered, so a chunk may not output
+/// anything until the next ones, or the end.
+pub enum Compressor {
+    Gzip(GzEncoder<Vec<u8>>),
+    Deflate(ZlibEncoder<Vec<u8>>),
+    DeflateRaw(DeflateEncoder<Vec<u8>>),
+}
+
+impl Compressor {
+    pub fn new(format: Format) -> Result<Self> {
+        let level = Compression::default();
+
+        Ok(match format {
+            Format::Gzip => Self::Gzip(GzEncoder::new(vec![], level)),
+            Format::Deflate => Self::Deflate(ZlibEncoder::new(vec![], level)),
+            Format::DeflateRaw => Self::DeflateRaw(DeflateEncoder::new(vec![], level)),
+            Format::Brotli => return Err(anyhow!("The brotli compression isn't supported")),
+        })
+    }
+
+    /// Returns the output of the chunk, if any.
+    pub fn write(&mut self, bytes: &[u8]) -> Result<Vec<u8>> {
+        let output = match self {
+            Self::Gzip(encoder) => {
+                encoder.write_all(bytes)?;
+                encoder.get_mut()
+            }
+            Self::Deflate(encoder) => {
+                encoder.write_all(bytes)?;
+                encoder.get_mut()
+            }
+            Self::DeflateRaw(encoder) => {
+                encoder.write_all(bytes)?;
+                encoder.get_mut()
+            }
+        };
+
+        Ok(mem::take(output))
+    }
+
+    /// Returns the rest of the output, with the trailer of the format.
+    pub fn finish(self) -> Result<Vec<u8>> {
+        Ok(match self {
+            Self::Gzip(encoder) => encoder.finish()?,
+            Self::Deflate(encoder) => encoder.finish()?,
+            Self::DeflateRaw(encoder) => encoder.finish()?,
+        })
+    }
+}
+
+/// Decompresses the chunks written to it. It fails on malformed data, on data after the end of the
+/// compressed stream, when it finishes before the end, and with a `TypeError` as soon as it holds
+/// more than its limit of output.
+pub enum Decompressor {
+    Gzip(GzDecoder<LimitedOutput>),
+    Deflate(ZlibDecoder<LimitedOutput>),
+    DeflateRaw(DeflateDecoder<LimitedOutput>),
+    Brotli(Box<DecompressorWriter<LimitedOutput>>),
+}
+
+impl Decompressor {
+    /// The limit applies to the output not returned yet, by `write` or `finish`.
+    pub fn new(format: Format, limit: usize) -> Self {
+        let output = LimitedOutput {
+            bytes: vec![],
+            limit,
+        };
+
+        match format {
+            Format::Gzip => Self::Gzip(GzDecoder::new(output)),
+            Format::Deflate => Self::Deflate(ZlibDecoder::new(output)),
+            Format::DeflateRaw => Self::DeflateRaw(DeflateDecoder::new(output)),
+            Format::Brotli => Self::Brotli(Box::new(DecompressorWriter::new(
+                output,
+                BROTLI_BUFFER_SIZE,
+            ))),
+        }
+    }
+
+    /// Returns the output of the chunk, which is flushed so it isn't held until the next one.
+    pub fn write(&mut self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.push(bytes)?;
+
+        Ok(mem::take(&mut self.output().bytes))
+    }
+
+    /// Returns the rest of the output, failing when the compressed stream is truncated.
+    pub fn finish(self) -> Result<Vec<u8>> {
+        match self {
+            Self::Gzip(decoder) => decoder.finish().map(|output| output.bytes),
+            Self::Deflate(decoder) => decoder.finish().map(|output| output.bytes),
+            Self::DeflateRaw(decoder) => decoder.finish().map(|output| output.bytes),
+            Self::Brotli(mut decoder) => decoder
+                .close()
+                .map(|_| mem::take(&mut decoder.get_mut().bytes)),
+        }
+        .map_err(decompress_error)
+    }
+
+    // Decompresses the chunk, keeping its output with the one not returned yet
+    fn push(&mut self, bytes: &[u8]) -> Result<()> {
+        fn write<W: Write>(decoder: &mut W, bytes: &[u8]) -> Result<()> {
+            decoder
+                .write_all(bytes)
+                .and_then(|_| decoder.flush())
+                .map_err(decompress_error)
+        }
+
+        match self {
+            Self::Gzip(decoder) => write(decoder, bytes),
+            Self::Deflate(decoder) => write(decoder, bytes),
+            Self::DeflateRaw(decoder) => write(decoder, bytes),
+            Self::Brotli(decoder) => write(decoder.as_mut(), bytes),
+        }
+    }
+
+    fn output(&mut self) -> &mut LimitedOutput {
+        match self {
+            Self::Gzip(decoder) => decoder.get_mut(),
+            Self::Deflate(decoder) => decoder.get_mut(),
+            Self::DeflateRaw(decoder) => decoder.get_mut(),
+            Self::Brotli(decoder) => decoder.get_mut(),
+        }
+    }
+}
+
+/// The output of a `Decompressor`, which refuses the bytes past its limit, so the decoders stop
+/// before the output grows any further.
+pub struct LimitedOutput {
+    bytes: Vec<u8>,
+    limit: usize,
+}
+
+impl Write for LimitedOutput {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        if self.bytes.len() + bytes.len() > self.limit {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                OutputTooLarge(self.limit),
+            ));
+        }
+
+        self.bytes.extend_from_slice(bytes);
+
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// The error of a `LimitedOutput`, with its limit
+#[derive(Debug)]
+struct OutputTooLarge(usize);
+
+impl fmt::Display for OutputTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The decompressed data exceeds {} bytes", self.0)
+    }
+}
+
+impl Error for OutputTooLarge {}
+
+/// Decompresses a whole body, as the `Content-Encoding` of a response. It fails with a `TypeError`
+/// once the output is larger than `limit` bytes.
+pub fn decompress(format: Format, bytes: &[u8], limit: usize) -> Result<Vec<u8>> {
+    let mut decompressor = Decompressor::new(format, limit);
+
+    decompressor.push(bytes)?;
+    decompressor.finish()
+}
+
+enum Stream {
+    Compress(Compressor),
+    Decompress(Decompressor),
+}
+
+pub(crate) fn set_global_compression(context: &Context) -> Result<()> {
+    let global = context.global_object()?;
+    let compression = context.object_value()?;
+
+    compression.set_property("compressor", context.wrap_callback(create_compressor)?)?;
+    compression.set_property("decompressor", context.wrap_callback(create_decompressor)?)?;
+    compression.set_property("write", context.wrap_callback(write_chunk)?)?;
+    compression.set_property("finish", context.wrap_callback(finish_stream)?)?;
+
+    global.set_property("___compression", compression)?;
+
+    Ok(())
+}
+
+fn create_compressor(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [format] => {
+            let compressor = Compressor::new(stream_format(format)?)?;
+
+            context.value_from_u32(STREAMS.insert(Stream::Compress(compressor)) as u32)
+        }
+        _ => Err(anyhow!("expected 1 argument, got {}", args.len())),
+    }
+}
+
+fn create_decompressor(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [format] => {
+            let decompressor = Decompressor::new(stream_format(format)?, STREAM_CHUNK_LIMIT);
+
+            context.value_from_u32(STREAMS.insert(Stream::Decompress(decompressor)) as u32)
+        }
+        _ => Err(anyhow!("expected 1 argument, got {}", args.len())),
+    }
+}
+
+// Returns the output of the chunk as an `ArrayBuffer`. A failed chunk ends the stream.
+fn write_chunk(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, buffer, byte_offset, byte_length] => {
+            let start = index(byte_offset)?;
+            let end = start + index(byte_length)?;
+            let bytes = buffer
+                .as_bytes()?
+                .get(start..end)
+                .ok_or_else(|| anyhow!("The offset and length are not valid for the buffer"))?;
+            let id = index(id)?;
+            let output = STREAMS.get(id, |stream| match stream {
+                Stream::Compress(compressor) => compressor.write(bytes),
+                Stream::Decompress(decompressor) => decompressor.write(bytes),
+            });
+
+            if output.is_err() {
+                STREAMS.remove(id);
+            }
+
+            context.array_buffer_value(&output?)
+        }
+        _ => Err(anyhow!("expected 4 arguments, got {}", args.len())),
+    }
+}
+
+// Returns the rest of the output as an `ArrayBuffer`, and frees the stream
+fn finish_stream(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id] => {
+            let stream = STREAMS.take(index(id)?)?;
+            let output = match stream {
+                Stream::Compress(compressor) => compressor.finish()?,
+                Stream::Decompress(decompressor) => decompressor.finish()?,
+            };
+
+            context.array_buffer_value(&output)
+        }
+        _ => Err(anyhow!("expected 1 argument, got {}", args.len())),
+    }
+}
+
+fn stream_format(format: &Value) -> Result<Format> {
+    let format = format.as_str()?;
+
+    Format::from_name(format)
+        .ok_or_else(|| JSError::Type(format!("Unsupported compression format: '{format}'")).into())
+}
+
+// A `TypeError`, for the output past the limit or for the data which isn't valid
+fn decompress_error(error: io::Error) -> anyhow::Error {
+    let message = match error
+        .get_ref()
+        .and_then(|error| error.downcast_ref::<OutputTooLarge>())
+    {
+        Some(error) => error.to_string(),
+        None => "The compressed data is not valid".to_string(),
+    };
+
+    JSError::Type(message).into()
+}
@@ -1,23 +1,137 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    io::Write,
+    rc::Rc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
 use anyhow::Result;
 use quickjs_wasm_rs::{Context, Value};
-use std::io::Write;
+use serde::Serialize;
+
+// Same depth used by Node.js `util.inspect`
+const MAX_DEPTH: usize = 2;
+const DEFAULT_LABEL: &str = "default";
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+// Every console entry is written as a JSON line, so the host can parse it as a structured record
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LogRecord<'a> {
+    pub level: Level,
+    pub message: &'a str,
+    pub timestamp: u64,
+    pub worker_id: &'a str,
+}
+
+struct Console<T1, T2> {
+    log_stream: T1,
+    error_stream: T2,
+    worker_id: String,
+    counters: HashMap<String, u64>,
+    timers: HashMap<String, Instant>,
+}
+
+impl<T1, T2> Console<T1, T2>
+where
+    T1: Write,
+    T2: Write,
+{
+    fn write(&mut self, level: Level, message: &str) -> Result<()> {
+        let record = LogRecord {
+            level,
+            message,
+            timestamp: timestamp(),
+            worker_id: &self.worker_id,
+        };
+        let line = serde_json::to_string(&record)?;
+
+        match level {
+            Level::Warn | Level::Error => writeln!(self.error_stream, "{line}")?,
+            _ => writeln!(self.log_stream, "{line}")?,
+        }
+
+        Ok(())
+    }
+}
 
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/console
+// @see: https://console.spec.whatwg.org/
 pub(crate) fn set_global_console<T1, T2>(
     context: &Context,
     log_stream: T1,
     error_stream: T2,
+    worker_id: &str,
 ) -> Result<()>
 where
     T1: Write + 'static,
     T2: Write + 'static,
 {
     let global = context.global_object()?;
+    let console = Rc::new(RefCell::new(Console {
+        log_stream,
+        error_stream,
+        worker_id: worker_id.to_string(),
+        counters: HashMap::new(),
+        timers: HashMap::new(),
+    }));
 
     let console_object = context.object_value()?;
-    console_object.set_property("log", context.wrap_callback(console_log_to(log_stream))?)?;
+
+    for (name, level) in [
+        ("log", Level::Info),
+        ("info", Level::Info),
+        ("debug", Level::Debug),
+        ("warn", Level::Warn),
+        ("error", Level::Error),
+    ] {
+        console_object.set_property(
+            name,
+            context.wrap_callback(console_log_to(console.clone(), level))?,
+        )?;
+    }
+
     console_object.set_property(
-        "error",
-        context.wrap_callback(console_log_to(error_stream))?,
+        "trace",
+        context.wrap_callback(console_trace(console.clone()))?,
+    )?;
+    console_object.set_property(
+        "assert",
+        context.wrap_callback(console_assert(console.clone()))?,
+    )?;
+    console_object.set_property(
+        "table",
+        context.wrap_callback(console_table(console.clone()))?,
+    )?;
+    console_object.set_property(
+        "count",
+        context.wrap_callback(console_count(console.clone()))?,
+    )?;
+    console_object.set_property(
+        "countReset",
+        context.wrap_callback(console_count_reset(console.clone()))?,
+    )?;
+    console_object.set_property(
+        "time",
+        context.wrap_callback(console_time(console.clone()))?,
+    )?;
+    console_object.set_property(
+        "timeLog",
+        context.wrap_callback(console_time_log(console.clone(), false))?,
+    )?;
+    console_object.set_property(
+        "timeEnd",
+        context.wrap_callback(console_time_log(console, true))?,
     )?;
 
     global.set_property("console", console_object)?;
@@ -25,20 +139,702 @@ where
     Ok(())
 }
 
-fn console_log_to<T>(mut stream: T) -> impl FnMut(&Context, &Value, &[Value]) -> Result<Value>
+fn console_log_to<T1, T2>(
+    console: Rc<RefCell<Console<T1, T2>>>,
+    level: Level,
+) -> impl FnMut(&Context, &Value, &[Value]) -> Result<Value>
 where
-    T: Write + 'static,
+    T1: Write + 'static,
+    T2: Write + 'static,
 {
     move |ctx: &Context, _this: &Value, args: &[Value]| {
-        for (i, arg) in args.iter().enumerate() {
-            if i != 0 {
-                write!(stream, " ")?;
-            }
+        // The message is formatted before borrowing the console, as inspecting a value can call back into JS
+        let message = format(args)?;
+
+        console.borrow_mut().write(level, &message)?;
+
+        ctx.undefined_value()
+    }
+}
+
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/console/trace
+fn console_trace<T1, T2>(
+    console: Rc<RefCell<Console<T1, T2>>>,
+) -> impl FnMut(&Context, &Value, &[Value]) -> Result<Value>
+where
+    T1: Write + 'static,
+    T2: Write + 'static,
+{
+    move |ctx: &Context, _this: &Value, args: &[Value]| {
+        let message = format(args)?;
+        let message = if message.is_empty() {
+            "Trace".to_string()
+        } else {
+            format!("Trace: {message}")
+        };
+
+        console.borrow_mut().write(Level::Trace, &message)?;
+
+        ctx.undefined_value()
+    }
+}
+
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/console/assert
+fn console_assert<T1, T2>(
+    console: Rc<RefCell<Console<T1, T2>>>,
+) -> impl FnMut(&Context, &Value, &[Value]) -> Result<Value>
+where
+    T1: Write + 'static,
+    T2: Write + 'static,
+{
+    move |ctx: &Context, _this: &Value, args: &[Value]| {
+        let assertion = match args.first() {
+            Some(value) => is_truthy(value)?,
+            None => false,
+        };
+
+        if !assertion {
+            let message = format(args.get(1..).unwrap_or_default())?;
+            let message = if message.is_empty() {
+                "Assertion failed".to_string()
+            } else {
+                format!("Assertion failed: {message}")
+            };
+
+            console.borrow_mut().write(Level::Error, &message)?;
+        }
+
+        ctx.undefined_value()
+    }
+}
 
-            stream.write_all(arg.as_str()?.as_bytes())?;
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/console/table
+fn console_table<T1, T2>(
+    console: Rc<RefCell<Console<T1, T2>>>,
+) -> impl FnMut(&Context, &Value, &[Value]) -> Result<Value>
+where
+    T1: Write + 'static,
+    T2: Write + 'static,
+{
+    move |ctx: &Context, _this: &Value, args: &[Value]| {
+        let message = match args.first() {
+            Some(data) if data.is_object() && !data.is_function() => table(data, args.get(1))?,
+            _ => format(args)?,
+        };
+
+        console.borrow_mut().write(Level::Info, &message)?;
+
+        ctx.undefined_value()
+    }
+}
+
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/console/count
+fn console_count<T1, T2>(
+    console: Rc<RefCell<Console<T1, T2>>>,
+) -> impl FnMut(&Context, &Value, &[Value]) -> Result<Value>
+where
+    T1: Write + 'static,
+    T2: Write + 'static,
+{
+    move |ctx: &Context, _this: &Value, args: &[Value]| {
+        let label = label(args)?;
+        let mut console = console.borrow_mut();
+        let count = console.counters.entry(label.clone()).or_insert(0);
+
+        *count += 1;
+
+        let message = format!("{label}: {count}");
+
+        console.write(Level::Info, &message)?;
+
+        ctx.undefined_value()
+    }
+}
+
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/console/countReset
+fn console_count_reset<T1, T2>(
+    console: Rc<RefCell<Console<T1, T2>>>,
+) -> impl FnMut(&Context, &Value, &[Value]) -> Result<Value>
+where
+    T1: Write + 'static,
+    T2: Write + 'static,
+{
+    move |ctx: &Context, _this: &Value, args: &[Value]| {
+        let label = label(args)?;
+        let mut console = console.borrow_mut();
+
+        match console.counters.get_mut(&label) {
+            Some(count) => *count = 0,
+            None => console.write(Level::Warn, &format!("Count for '{label}' does not exist"))?,
         }
 
-        writeln!(stream)?;
         ctx.undefined_value()
     }
 }
+
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/console/time
+fn console_time<T1, T2>(
+    console: Rc<RefCell<Console<T1, T2>>>,
+) -> impl FnMut(&Context, &Value, &[Value]) -> Result<Value>
+where
+    T1: Write + 'static,
+    T2: Write + 'static,
+{
+    move |ctx: &Context, _this: &Value, args: &[Value]| {
+        let label = label(args)?;
+        let mut console = console.borrow_mut();
+
+        if console.timers.contains_key(&label) {
+            console.write(Level::Warn, &format!("Timer '{label}' already exists"))?;
+        } else {
+            console.timers.insert(label, Instant::now());
+        }
+
+        ctx.undefined_value()
+    }
+}
+
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/console/timeLog
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/console/timeEnd
+fn console_time_log<T1, T2>(
+    console: Rc<RefCell<Console<T1, T2>>>,
+    end: bool,
+) -> impl FnMut(&Context, &Value, &[Value]) -> Result<Value>
+where
+    T1: Write + 'static,
+    T2: Write + 'static,
+{
+    move |ctx: &Context, _this: &Value, args: &[Value]| {
+        let label = label(args)?;
+        let data = if end {
+            String::new()
+        } else {
+            format(args.get(1..).unwrap_or_default())?
+        };
+        let mut console = console.borrow_mut();
+
+        let started = if end {
+            console.timers.remove(&label)
+        } else {
+            console.timers.get(&label).copied()
+        };
+
+        match started {
+            Some(started) => {
+                let elapsed = started.elapsed().as_secs_f64() * 1000.0;
+                let message = if data.is_empty() {
+                    format!("{label}: {elapsed:.3}ms")
+                } else {
+                    format!("{label}: {elapsed:.3}ms {data}")
+                };
+
+                console.write(Level::Info, &message)?;
+            }
+            None => {
+                console.write(Level::Warn, &format!("Timer '{label}' does not exist"))?;
+            }
+        }
+
+        ctx.undefined_value()
+    }
+}
+
+fn label(args: &[Value]) -> Result<String> {
+    match args.first() {
+        Some(label) if !label.is_undefined() => Ok(label.as_str()?.to_string()),
+        _ => Ok(DEFAULT_LABEL.to_string()),
+    }
+}
+
+fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+// Formats the arguments as the Console Standard Formatter does, supporting %s, %d, %i, %f, %o, %O, %c and %%
+// @see: https://console.spec.whatwg.org/#formatter
+pub(crate) fn format(args: &[Value]) -> Result<String> {
+    let mut message = String::new();
+    let mut rest = args;
+
+    if let Some((first, tail)) = args.split_first() {
+        if first.is_str() {
+            let template = first.as_str()?;
+            let mut chars = template.chars().peekable();
+
+            rest = tail;
+
+            while let Some(c) = chars.next() {
+                if c != '%' {
+                    message.push(c);
+                    continue;
+                }
+
+                let specifier = match chars.peek() {
+                    Some(specifier) => *specifier,
+                    None => {
+                        message.push(c);
+                        continue;
+                    }
+                };
+
+                if specifier == '%' {
+                    chars.next();
+                    message.push('%');
+                    continue;
+                }
+
+                if !matches!(specifier, 's' | 'd' | 'i' | 'f' | 'o' | 'O' | 'j' | 'c') {
+                    message.push(c);
+                    continue;
+                }
+
+                let Some((arg, tail)) = rest.split_first() else {
+                    message.push(c);
+                    continue;
+                };
+
+                chars.next();
+                rest = tail;
+
+                match specifier {
+                    's' => {
+                        if arg.is_str() {
+                            message.push_str(arg.as_str()?);
+                        } else {
+                            message.push_str(&inspect(arg, 0)?);
+                        }
+                    }
+                    'd' | 'f' => message.push_str(&format_number(to_number(arg)?)),
+                    'i' => message.push_str(&format_number(to_number(arg)?.trunc())),
+                    'o' | 'O' | 'j' => message.push_str(&inspect(arg, 0)?),
+                    // CSS styles don't apply to a text stream
+                    'c' => {}
+                    _ => unreachable!(),
+                }
+            }
+        }
+    }
+
+    for (i, arg) in rest.iter().enumerate() {
+        // The arguments left after the template are always separated by a space
+        if i != 0 || rest.len() != args.len() {
+            message.push(' ');
+        }
+
+        if arg.is_str() {
+            message.push_str(arg.as_str()?);
+        } else {
+            message.push_str(&inspect(arg, 0)?);
+        }
+    }
+
+    Ok(message)
+}
+
+fn to_number(value: &Value) -> Result<f64> {
+    if value.is_repr_as_i32() {
+        Ok(value.as_i32_unchecked() as f64)
+    } else if value.is_repr_as_f64() {
+        value.as_f64()
+    } else if value.is_bool() {
+        Ok(if value.as_bool()? { 1.0 } else { 0.0 })
+    } else if value.is_null() {
+        Ok(0.0)
+    } else if value.is_str() {
+        let value = value.as_str()?.trim();
+
+        if value.is_empty() {
+            Ok(0.0)
+        } else {
+            Ok(value.parse::<f64>().unwrap_or(f64::NAN))
+        }
+    } else {
+        Ok(f64::NAN)
+    }
+}
+
+fn format_number(number: f64) -> String {
+    if number.is_nan() {
+        "NaN".to_string()
+    } else if number.is_infinite() {
+        if number.is_sign_positive() {
+            "Infinity".to_string()
+        } else {
+            "-Infinity".to_string()
+        }
+    } else if number.fract() == 0.0 && number.abs() < 1e21 {
+        format!("{}", number as i128)
+    } else {
+        format!("{number}")
+    }
+}
+
+fn is_truthy(value: &Value) -> Result<bool> {
+    if value.is_null_or_undefined() {
+        Ok(false)
+    } else if value.is_bool() {
+        value.as_bool()
+    } else if value.is_str() {
+        Ok(!value.as_str()?.is_empty())
+    } else if value.is_repr_as_i32() || value.is_repr_as_f64() {
+        let number = to_number(value)?;
+        Ok(number != 0.0 && !number.is_nan())
+    } else {
+        Ok(true)
+    }
+}
+
+// Returns a string representation of the value, similar to the one returned by Node.js `util.inspect`
+// @see: https://nodejs.org/api/util.html#utilinspectobject-options
+pub(crate) fn inspect(value: &Value, depth: usize) -> Result<String> {
+    if value.is_str() {
+        return Ok(if depth == 0 {
+            value.as_str()?.to_string()
+        } else {
+            quote(value.as_str()?)
+        });
+    }
+
+    if value.is_big_int() {
+        return Ok(format!("{}n", value.as_str()?));
+    }
+
+    if value.is_function() {
+        let name = value.get_property("name")?;
+        let name = if name.is_str() {
+            name.as_str()?.to_string()
+        } else {
+            String::new()
+        };
+
+        return Ok(if name.is_empty() {
+            "[Function (anonymous)]".to_string()
+        } else {
+            format!("[Function: {name}]")
+        });
+    }
+
+    if !value.is_object() {
+        return Ok(value.as_str()?.to_string());
+    }
+
+    if is_error(value)? {
+        return error_to_string(value);
+    }
+
+    if value.is_array_buffer() {
+        return Ok(format!(
+            "ArrayBuffer {{ byteLength: {} }}",
+            value.as_bytes()?.len()
+        ));
+    }
+
+    let constructor = constructor_name(value)?;
+
+    if value.is_array() {
+        if depth > MAX_DEPTH {
+            return Ok("[Array]".to_string());
+        }
+
+        let length = value.get_property("length")?.as_u32_unchecked();
+        let mut items = Vec::with_capacity(length as usize);
+
+        for i in 0..length {
+            items.push(inspect(&value.get_property(i.to_string())?, depth + 1)?);
+        }
+
+        return Ok(wrap("", "[", &items, "]"));
+    }
+
+    match constructor.as_str() {
+        "Date" => {
+            let to_iso_string = value.get_property("toISOString")?;
+
+            return Ok(to_iso_string.call(value, &[])?.as_str()?.to_string());
+        }
+        "RegExp" => {
+            let to_string = value.get_property("toString")?;
+
+            return Ok(to_string.call(value, &[])?.as_str()?.to_string());
+        }
+        "Promise" => return Ok("Promise { <pending> }".to_string()),
+        "Map" | "Set" => {
+            let size = value.get_property("size")?.as_u32_unchecked();
+            let prefix = format!("{constructor}({size}) ");
+
+            if depth > MAX_DEPTH {
+                return Ok(format!("[{constructor}]"));
+            }
+
+            let mut items = Vec::with_capacity(size as usize);
+            let iterator = value.get_property("entries")?.call(value, &[])?;
+            let next = iterator.get_property("next")?;
+
+            loop {
+                let result = next.call(&iterator, &[])?;
+
+                if result.get_property("done")?.as_bool()? {
+                    break;
+                }
+
+                let entry = result.get_property("value")?;
+                let key = inspect(&entry.get_property("0")?, depth + 1)?;
+
+                if constructor == "Map" {
+                    let value = inspect(&entry.get_property("1")?, depth + 1)?;
+                    items.push(format!("{key} => {value}"));
+                } else {
+                    items.push(key);
+                }
+            }
+
+            return Ok(wrap(&prefix, "{", &items, "}"));
+        }
+        _ => {}
+    }
+
+    let prefix = if constructor.is_empty() {
+        "[Object: null prototype] ".to_string()
+    } else if constructor == "Object" {
+        String::new()
+    } else {
+        format!("{constructor} ")
+    };
+
+    if depth > MAX_DEPTH {
+        return Ok(if constructor.is_empty() || constructor == "Object" {
+            "[Object]".to_string()
+        } else {
+            format!("[{constructor}]")
+        });
+    }
+
+    let mut items = vec![];
+    let mut properties = value.properties()?;
+
+    while let Some(key) = properties.next_key()? {
+        let key = key.as_str()?.to_string();
+        let value = properties.next_value()?;
+
+        items.push(format!(
+            "{}: {}",
+            quote_key(&key),
+            inspect(&value, depth + 1)?
+        ));
+    }
+
+    Ok(wrap(&prefix, "{", &items, "}"))
+}
+
+fn wrap(prefix: &str, open: &str, items: &[String], close: &str) -> String {
+    if items.is_empty() {
+        format!("{prefix}{open}{close}")
+    } else {
+        format!("{prefix}{open} {} {close}", items.join(", "))
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!(
+        "'{}'",
+        value
+            .replace('\\', "\\\\")
+            .replace('\'', "\\'")
+            .replace('\n', "\\n")
+    )
+}
+
+fn quote_key(key: &str) -> String {
+    let is_identifier = key
+        .chars()
+        .enumerate()
+        .all(|(i, c)| c == '_' || c == '$' || c.is_alphabetic() || (i > 0 && c.is_numeric()));
+
+    if is_identifier && !key.is_empty() {
+        key.to_string()
+    } else {
+        quote(key)
+    }
+}
+
+fn constructor_name(value: &Value) -> Result<String> {
+    let constructor = value.get_property("constructor")?;
+
+    if !constructor.is_function() {
+        return Ok(String::new());
+    }
+
+    let name = constructor.get_property("name")?;
+
+    Ok(if name.is_str() {
+        name.as_str()?.to_string()
+    } else {
+        String::new()
+    })
+}
+
+fn is_error(value: &Value) -> Result<bool> {
+    Ok(value.get_property("stack")?.is_str()
+        && value.get_property("message")?.is_str()
+        && value.get_property("name")?.is_str())
+}
+
+// QuickJS doesn't add the error header to the stack, so it is prepended
+fn error_to_string(error: &Value) -> Result<String> {
+    let name = error.get_property("name")?;
+    let message = error.get_property("message")?;
+    let stack = error.get_property("stack")?;
+
+    let name = name.as_str()?;
+    let message = message.as_str()?;
+    let stack = stack.as_str()?.trim_end();
+
+    let header = if message.is_empty() {
+        name.to_string()
+    } else {
+        format!("{name}: {message}")
+    };
+
+    Ok(if stack.is_empty() {
+        header
+    } else {
+        format!("{header}\n{stack}")
+    })
+}
+
+fn table(data: &Value, columns: Option<&Value>) -> Result<String> {
+    const INDEX: &str = "(index)";
+    const VALUES: &str = "Values";
+
+    let mut keys: Vec<String> = vec![];
+    let mut rows: Vec<(String, Value)> = vec![];
+
+    if data.is_array() {
+        let length = data.get_property("length")?.as_u32_unchecked();
+
+        for i in 0..length {
+            rows.push((i.to_string(), data.get_property(i.to_string())?));
+        }
+    } else {
+        let mut properties = data.properties()?;
+
+        while let Some(key) = properties.next_key()? {
+            let key = key.as_str()?.to_string();
+            rows.push((key, properties.next_value()?));
+        }
+    }
+
+    let filter = match columns {
+        Some(columns) if columns.is_array() => {
+            let length = columns.get_property("length")?.as_u32_unchecked();
+            let mut filter = vec![];
+
+            for i in 0..length {
+                filter.push(columns.get_property(i.to_string())?.as_str()?.to_string());
+            }
+
+            Some(filter)
+        }
+        _ => None,
+    };
+
+    let mut has_values = false;
+    let mut cells: Vec<HashMap<String, String>> = vec![];
+
+    for (_, row) in rows.iter() {
+        let mut cell = HashMap::new();
+
+        if row.is_object() && !row.is_function() {
+            let mut properties = row.properties()?;
+
+            while let Some(key) = properties.next_key()? {
+                let key = key.as_str()?.to_string();
+                let value = properties.next_value()?;
+
+                if let Some(filter) = &filter {
+                    if !filter.contains(&key) {
+                        continue;
+                    }
+                }
+
+                if !keys.contains(&key) {
+                    keys.push(key.clone());
+                }
+
+                cell.insert(key, inspect(&value, MAX_DEPTH + 1)?);
+            }
+        } else {
+            has_values = true;
+            cell.insert(VALUES.to_string(), inspect(row, 1)?);
+        }
+
+        cells.push(cell);
+    }
+
+    if let Some(filter) = filter {
+        keys = filter;
+    }
+
+    let mut header = vec![INDEX.to_string()];
+    header.extend(keys.iter().cloned());
+
+    if has_values {
+        keys.push(VALUES.to_string());
+        header.push(VALUES.to_string());
+    }
+
+    let body: Vec<Vec<String>> = rows
+        .iter()
+        .zip(cells.iter())
+        .map(|((index, _), cell)| {
+            let mut line = vec![index.clone()];
+            line.extend(
+                keys.iter()
+                    .map(|key| cell.get(key).cloned().unwrap_or_default()),
+            );
+            line
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            body.iter()
+                .map(|line| line[i].chars().count())
+                .chain([header[i].chars().count()])
+                .max()
+                .unwrap_or_default()
+                + 2
+        })
+        .collect();
+
+    let border = |left: &str, middle: &str, right: &str| {
+        let line = widths
+            .iter()
+            .map(|width| "─".repeat(*width))
+            .collect::<Vec<String>>()
+            .join(middle);
+
+        format!("{left}{line}{right}")
+    };
+    let line = |cells: &[String]| {
+        let line = cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| {
+                format!(" {cell}{} ", " ".repeat(width - 2 - cell.chars().count()))
+            })
+            .collect::<Vec<String>>()
+            .join("│");
+
+        format!("│{line}│")
+    };
+
+    let mut table = vec![border("┌", "┬", "┐"), line(&header), border("├", "┼", "┤")];
+    table.extend(body.iter().map(|cells| line(cells)));
+    table.push(border("└", "┴", "┘"));
+
+    Ok(table.join("\n"))
+}
@@ -0,0 +1,407 @@
+use anyhow::{anyhow, Result};
+use http::header::{HeaderName, SET_COOKIE};
+use quickjs_wasm_rs::{Context, JSError, Value};
+
+use super::registry::{index, Registry};
+
+// The header lists of the `Headers` objects, which keep their handle
+static HEADER_LISTS: Registry<HeaderList> = Registry::new("header list");
+
+// @see: https://fetch.spec.whatwg.org/#http-whitespace
+const HTTP_WHITESPACE: [char; 4] = ['\t', '\n', '\r', ' '];
+
+const CORS_SAFELISTED_CONTENT_TYPES: [&str; 3] = [
+    "application/x-www-form-urlencoded",
+    "multipart/form-data",
+    "text/plain",
+];
+
+/// What the `Headers` object allows to modify.
+///
+/// The forbidden request and response header names only apply to browsers, so the `request` and
+/// `response` guards don't restrict anything, as in Cloudflare Workers.
+///
+/// @see: https://fetch.spec.whatwg.org/#concept-headers-guard
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Guard {
+    #[default]
+    None,
+    Request,
+    /// Only the CORS-safelisted request headers can be set
+    RequestNoCors,
+    Response,
+    /// The headers of the responses returned by `fetch`
+    Immutable,
+}
+
+impl Guard {
+    pub fn parse(guard: &str) -> Result<Self> {
+        match guard {
+            "none" => Ok(Self::None),
+            "request" => Ok(Self::Request),
+            "request-no-cors" => Ok(Self::RequestNoCors),
+            "response" => Ok(Self::Response),
+            "immutable" => Ok(Self::Immutable),
+            _ => Err(anyhow!("Unknown headers guard: {guard}")),
+        }
+    }
+}
+
+/// The entries of a `Headers` object, in insertion order, with one entry per appended value.
+///
+/// @see: https://fetch.spec.whatwg.org/#concept-header-list
+#[derive(Debug, Default, Clone)]
+pub struct HeaderList {
+    entries: Vec<(HeaderName, String)>,
+    guard: Guard,
+}
+
+impl HeaderList {
+    pub fn set_guard(&mut self, guard: Guard) {
+        self.guard = guard;
+    }
+
+    // @see: https://fetch.spec.whatwg.org/#concept-headers-append
+    pub fn append(&mut self, name: &str, value: &str) -> Result<()> {
+        let name = normalize_name(name)?;
+        let value = normalize_value(value)?;
+
+        self.check_mutable()?;
+
+        if self.guard == Guard::RequestNoCors {
+            let combined = match self.combined(&name) {
+                Some(existing) => format!("{existing}, {value}"),
+                None => value.clone(),
+            };
+
+            if !is_cors_safelisted(&name, &combined) {
+                return Ok(());
+            }
+        }
+
+        self.entries.push((name, value));
+
+        Ok(())
+    }
+
+    // @see: https://fetch.spec.whatwg.org/#dom-headers-set
+    pub fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        let name = normalize_name(name)?;
+        let value = normalize_value(value)?;
+
+        self.check_mutable()?;
+
+        if self.guard == Guard::RequestNoCors && !is_cors_safelisted(&name, &value) {
+            return Ok(());
+        }
+
+        match self.entries.iter().position(|(entry, _)| *entry == name) {
+            Some(index) => {
+                self.entries[index].1 = value;
+
+                // Only the first entry of the name is kept
+                let mut current = 0;
+
+                self.entries.retain(|(entry, _)| {
+                    let keep = current <= index || *entry != name;
+
+                    current += 1;
+
+                    keep
+                });
+            }
+            None => self.entries.push((name, value)),
+        }
+
+        Ok(())
+    }
+
+    // @see: https://fetch.spec.whatwg.org/#dom-headers-delete
+    pub fn delete(&mut self, name: &str) -> Result<()> {
+        let name = normalize_name(name)?;
+
+        self.check_mutable()?;
+
+        if self.guard == Guard::RequestNoCors && !is_no_cors_safelisted_name(&name) {
+            return Ok(());
+        }
+
+        self.entries.retain(|(entry, _)| *entry != name);
+
+        Ok(())
+    }
+
+    // @see: https://fetch.spec.whatwg.org/#dom-headers-get
+    pub fn get(&self, name: &str) -> Result<Option<String>> {
+        Ok(self.combined(&normalize_name(name)?))
+    }
+
+    pub fn has(&self, name: &str) -> Result<bool> {
+        let name = normalize_name(name)?;
+
+        Ok(self.entries.iter().any(|(entry, _)| *entry == name))
+    }
+
+    // @see: https://fetch.spec.whatwg.org/#dom-headers-getsetcookie
+    pub fn get_set_cookie(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|(name, _)| *name == SET_COOKIE)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    /// The names with their combined values, except `set-cookie`, which has an entry per value.
+    ///
+    /// Unlike the sort-and-combine of the spec, which sorts the names, they are kept in the order
+    /// they were first appended, as the previous implementation did. The values of a name are
+    /// combined at its first position.
+    pub fn entries(&self) -> Vec<(&str, String)> {
+        let mut entries: Vec<(&str, String)> = vec![];
+
+        for (name, value) in &self.entries {
+            if *name == SET_COOKIE {
+                entries.push((name.as_str(), value.clone()));
+            } else if !entries.iter().any(|(entry, _)| *entry == name.as_str()) {
+                entries.push((name.as_str(), self.combined(name).unwrap_or_default()));
+            }
+        }
+
+        entries
+    }
+
+    // The values of the name joined with `, `
+    fn combined(&self, name: &HeaderName) -> Option<String> {
+        let values = self
+            .entries
+            .iter()
+            .filter(|(entry, _)| entry == name)
+            .map(|(_, value)| value.as_str())
+            .collect::<Vec<_>>();
+
+        (!values.is_empty()).then(|| values.join(", "))
+    }
+
+    fn check_mutable(&self) -> Result<()> {
+        if self.guard == Guard::Immutable {
+            return Err(JSError::Type("Headers are immutable".to_string()).into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Validates the name and lowercases it. The leading and trailing whitespace is trimmed, as the
+/// previous implementation did, instead of being rejected.
+///
+/// @see: https://fetch.spec.whatwg.org/#header-name
+pub fn normalize_name(name: &str) -> Result<HeaderName> {
+    let name = name.trim_matches(HTTP_WHITESPACE);
+
+    HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+        JSError::Type(format!(
+            "Invalid character in header field name: \"{name}\""
+        ))
+        .into()
+    })
+}
+
+/// Removes the leading and trailing HTTP whitespace, and validates the value is a byte string
+/// without NUL, CR or LF.
+///
+/// @see: https://fetch.spec.whatwg.org/#concept-header-value-normalize
+/// @see: https://fetch.spec.whatwg.org/#header-value
+pub fn normalize_value(value: &str) -> Result<String> {
+    let value = value.trim_matches(HTTP_WHITESPACE);
+
+    if value
+        .chars()
+        .any(|c| c == '\0' || c == '\r' || c == '\n' || c as u32 > 0xFF)
+    {
+        return Err(JSError::Type(format!(
+            "Invalid character in header field value: \"{value}\""
+        ))
+        .into());
+    }
+
+    Ok(value.to_string())
+}
+
+// @see: https://fetch.spec.whatwg.org/#cors-safelisted-request-header
+fn is_cors_safelisted(name: &HeaderName, value: &str) -> bool {
+    if value.len() > 128 {
+        return false;
+    }
+
+    match name.as_str() {
+        "accept" => !value.chars().any(is_cors_unsafe_request_header_char),
+        "accept-language" | "content-language" => value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || " *,-.;=".contains(c)),
+        "content-type" => {
+            let essence = value
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim_matches(HTTP_WHITESPACE)
+                .to_ascii_lowercase();
+
+            !value.chars().any(is_cors_unsafe_request_header_char)
+                && CORS_SAFELISTED_CONTENT_TYPES.contains(&essence.as_str())
+        }
+        _ => false,
+    }
+}
+
+// @see: https://fetch.spec.whatwg.org/#no-cors-safelisted-request-header-name
+fn is_no_cors_safelisted_name(name: &HeaderName) -> bool {
+    matches!(
+        name.as_str(),
+        "accept" | "accept-language" | "content-language" | "content-type"
+    )
+}
+
+// @see: https://fetch.spec.whatwg.org/#cors-unsafe-request-header-byte
+fn is_cors_unsafe_request_header_char(c: char) -> bool {
+    (c < ' ' && c != '\t') || "\"():<>?@[\\]{}\u{7f}".contains(c)
+}
+
+pub(crate) fn set_global_headers(context: &Context) -> Result<()> {
+    let global = context.global_object()?;
+    let header_list = context.object_value()?;
+
+    header_list.set_property("create", context.wrap_callback(create)?)?;
+    header_list.set_property("setGuard", context.wrap_callback(set_guard)?)?;
+    header_list.set_property("clone", context.wrap_callback(clone)?)?;
+    header_list.set_property("append", context.wrap_callback(append)?)?;
+    header_list.set_property("set", context.wrap_callback(set)?)?;
+    header_list.set_property("delete", context.wrap_callback(delete)?)?;
+    header_list.set_property("get", context.wrap_callback(get)?)?;
+    header_list.set_property("has", context.wrap_callback(has)?)?;
+    header_list.set_property("entries", context.wrap_callback(entries)?)?;
+    header_list.set_property("getSetCookie", context.wrap_callback(get_set_cookie)?)?;
+
+    global.set_property("___headerList", header_list)?;
+
+    Ok(())
+}
+
+// Returns the `[id, handle]` of the new header list
+fn create(context: &Context, _this: &Value, _args: &[Value]) -> Result<Value> {
+    HEADER_LISTS.insert_with_handle(context, HeaderList::default())
+}
+
+fn set_guard(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, guard] => {
+            with_header_list(id, |header_list| {
+                header_list.set_guard(Guard::parse(guard.as_str()?)?);
+
+                Ok(())
+            })?;
+
+            context.undefined_value()
+        }
+        _ => Err(anyhow!("expected 2 arguments, got {}", args.len())),
+    }
+}
+
+// Copies the entries and the guard into a new header list, returning its `[id, handle]`
+fn clone(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id] => {
+            let header_list = with_header_list(id, |header_list| Ok(header_list.clone()))?;
+
+            HEADER_LISTS.insert_with_handle(context, header_list)
+        }
+        _ => Err(anyhow!("expected 1 argument, got {}", args.len())),
+    }
+}
+
+fn append(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, name, value] => {
+            with_header_list(id, |header_list| {
+                header_list.append(name.as_str()?, value.as_str()?)
+            })?;
+
+            context.undefined_value()
+        }
+        _ => Err(anyhow!("expected 3 arguments, got {}", args.len())),
+    }
+}
+
+fn set(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, name, value] => {
+            with_header_list(id, |header_list| {
+                header_list.set(name.as_str()?, value.as_str()?)
+            })?;
+
+            context.undefined_value()
+        }
+        _ => Err(anyhow!("expected 3 arguments, got {}", args.len())),
+    }
+}
+
+fn delete(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, name] => {
+            with_header_list(id, |header_list| header_list.delete(name.as_str()?))?;
+
+            context.undefined_value()
+        }
+        _ => Err(anyhow!("expected 2 arguments, got {}", args.len())),
+    }
+}
+
+fn get(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, name] => match with_header_list(id, |header_list| header_list.get(name.as_str()?))? {
+            Some(value) => context.value_from_str(&value),
+            None => context.null_value(),
+        },
+        _ => Err(anyhow!("expected 2 arguments, got {}", args.len())),
+    }
+}
+
+fn has(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, name] => context.value_from_bool(with_header_list(id, |header_list| {
+            header_list.has(name.as_str()?)
+        })?),
+        _ => Err(anyhow!("expected 2 arguments, got {}", args.len())),
+    }
+}
+
+// The entries as a JSON array of `[name, value]` pairs
+fn entries(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id] => {
+            let entries = with_header_list(id, |header_list| {
+                Ok(serde_json::to_string(&header_list.entries())?)
+            })?;
+
+            context.value_from_str(&entries)
+        }
+        _ => Err(anyhow!("expected 1 argument, got {}", args.len())),
+    }
+}
+
+// The values as a JSON array
+fn get_set_cookie(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id] => {
+            let values = with_header_list(id, |header_list| {
+                Ok(serde_json::to_string(&header_list.get_set_cookie())?)
+            })?;
+
+            context.value_from_str(&values)
+        }
+        _ => Err(anyhow!("expected 1 argument, got {}", args.len())),
+    }
+}
+
+fn with_header_list<T>(id: &Value, f: impl FnOnce(&mut HeaderList) -> Result<T>) -> Result<T> {
+    HEADER_LISTS.get(index(id)?, f)
+}
@@ -0,0 +1,44 @@
+use anyhow::{anyhow, Result};
+use quickjs_wasm_rs::{Context, Value};
+
+use crate::fetch::mem::{stack_pop, FromMem, ToMem};
+
+extern "C" {
+    fn import_kv(ptr: *const u8, value_ptr: *const u8) -> *mut u8;
+}
+
+pub fn set_global_kv(context: &Context) -> Result<()> {
+    let global = context.global_object()?;
+
+    global.set_property("___kv", context.wrap_callback(kv)?)?;
+
+    Ok(())
+}
+
+// Runs an operation, as JSON, on a KV namespace binding of the host. The value to put is passed as
+// an `ArrayBuffer`, which is undefined for the other operations, and the value of a key is
+// returned as one, the other results being JSON.
+fn kv(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [operation, value] => {
+            let value = if value.is_undefined() {
+                &[][..]
+            } else {
+                value.as_bytes()?
+            };
+            let (result, is_bytes) = unsafe {
+                let ptr = import_kv(operation.as_str()?.to_mem(), value.to_mem());
+                let is_bytes = stack_pop() == 1;
+
+                (Vec::<u8>::from_mem(ptr), is_bytes)
+            };
+
+            if is_bytes {
+                context.array_buffer_value(&result)
+            } else {
+                context.value_from_str(std::str::from_utf8(&result)?)
+            }
+        }
+        _ => Err(anyhow!("expected 2 arguments, got {}", args.len())),
+    }
+}
@@ -0,0 +1,429 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Result};
+use quickjs_wasm_rs::{Context, JSError, Value};
+
+use super::registry::{index, optional_str, Registry};
+
+// The parsers of the `formData()` calls in progress, removed once finished
+static PARSERS: Registry<MultipartParser> = Registry::new("multipart parser");
+
+static BOUNDARY_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+const DEFAULT_FILE_TYPE: &str = "application/octet-stream";
+
+/// An entry of a `FormData`, as a part of a `multipart/form-data` body. The entries with a
+/// filename are files, the other ones are text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Part {
+    pub name: String,
+    pub filename: Option<String>,
+    pub content_type: Option<String>,
+    pub data: Vec<u8>,
+}
+
+/// Generates a boundary which is unlikely to be in the body, without a source of randomness.
+pub fn boundary() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or_default();
+    let count = BOUNDARY_COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    // SplitMix64, so consecutive boundaries don't look alike
+    let mut seed = nanos ^ count.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+
+    seed = (seed ^ (seed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    seed = (seed ^ (seed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    seed ^= seed >> 31;
+
+    format!("----FormDataBoundary{seed:016x}{count:04x}")
+}
+
+/// Serializes the entries as a `multipart/form-data` body.
+///
+/// @see: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#multipart-form-data
+pub fn encode(parts: &[Part], boundary: &str) -> Vec<u8> {
+    let mut body = vec![];
+
+    for part in parts {
+        body.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
+        body.extend_from_slice(
+            format!(
+                "Content-Disposition: form-data; name=\"{}\"",
+                escape(&part.name)
+            )
+            .as_bytes(),
+        );
+
+        if let Some(filename) = &part.filename {
+            body.extend_from_slice(
+                format!(
+                    "; filename=\"{}\"\r\nContent-Type: {}",
+                    escape(filename),
+                    part.content_type
+                        .as_deref()
+                        .filter(|content_type| !content_type.is_empty())
+                        .unwrap_or(DEFAULT_FILE_TYPE)
+                )
+                .as_bytes(),
+            );
+        }
+
+        body.extend_from_slice(b"\r\n\r\n");
+        body.extend_from_slice(&part.data);
+        body.extend_from_slice(b"\r\n");
+    }
+
+    body.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+
+    body
+}
+
+// @see: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#multipart/form-data-encoding-algorithm
+fn escape(value: &str) -> String {
+    value
+        .replace('\n', "%0A")
+        .replace('\r', "%0D")
+        .replace('"', "%22")
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("%0A", "\n")
+        .replace("%0D", "\r")
+        .replace("%22", "\"")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    // Before the first delimiter
+    Preamble,
+    // After a delimiter, before the end of its line
+    Delimiter,
+    Headers,
+    Body,
+    Done,
+}
+
+/// Parses a `multipart/form-data` body as it is received, returning each part once it is complete.
+/// The lines can end with CRLF, as the spec requires, or with LF.
+///
+/// @see: https://datatracker.ietf.org/doc/html/rfc7578
+#[derive(Debug)]
+pub struct MultipartParser {
+    delimiter: Vec<u8>,
+    buffer: Vec<u8>,
+    state: State,
+    part: Part,
+}
+
+impl MultipartParser {
+    pub fn new(boundary: &str) -> Self {
+        Self {
+            delimiter: format!("--{boundary}").into_bytes(),
+            buffer: vec![],
+            state: State::Preamble,
+            part: Part::default(),
+        }
+    }
+
+    /// Parses a chunk of the body, returning the parts it completes.
+    pub fn push(&mut self, chunk: &[u8]) -> Result<Vec<Part>> {
+        let mut parts = vec![];
+
+        self.buffer.extend_from_slice(chunk);
+
+        while let Some(part) = self.step()? {
+            if let Some(part) = part {
+                parts.push(part);
+            }
+        }
+
+        Ok(parts)
+    }
+
+    /// Ends the body, which fails if it is truncated.
+    pub fn finish(mut self) -> Result<Vec<Part>> {
+        let parts = self.push(&[])?;
+
+        match self.state {
+            // A body without any delimiter has no parts
+            State::Done | State::Preamble => Ok(parts),
+            _ => Err(JSError::Type("Failed to parse the multipart body".to_string()).into()),
+        }
+    }
+
+    // Moves to the next state, if the buffer has enough data, and returns the completed part
+    fn step(&mut self) -> Result<Option<Option<Part>>> {
+        match self.state {
+            State::Preamble => {
+                let Some(index) = self.find_delimiter(true) else {
+                    // Only the data which can be the start of a delimiter is kept
+                    let keep = self.delimiter.len() + 1;
+                    let drain = self.buffer.len().saturating_sub(keep);
+
+                    self.buffer.drain(..drain);
+
+                    return Ok(None);
+                };
+
+                self.buffer.drain(..index + self.delimiter.len());
+                self.state = State::Delimiter;
+
+                Ok(Some(None))
+            }
+            State::Delimiter => {
+                if self.buffer.starts_with(b"--") {
+                    self.buffer.clear();
+                    self.state = State::Done;
+
+                    return Ok(Some(None));
+                }
+
+                let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') else {
+                    return Ok(None);
+                };
+
+                // The transport padding after the delimiter is ignored
+                self.buffer.drain(..=end);
+                self.state = State::Headers;
+
+                Ok(Some(None))
+            }
+            State::Headers => {
+                let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') else {
+                    return Ok(None);
+                };
+                let line = String::from_utf8_lossy(&self.buffer[..end])
+                    .trim_end_matches('\r')
+                    .to_string();
+
+                self.buffer.drain(..=end);
+
+                if line.is_empty() {
+                    if self.part.name.is_empty() {
+                        return Err(JSError::Type(
+                            "A multipart part is missing its Content-Disposition name".to_string(),
+                        )
+                        .into());
+                    }
+
+                    self.state = State::Body;
+                } else {
+                    self.parse_header(&line);
+                }
+
+                Ok(Some(None))
+            }
+            State::Body => {
+                let Some(index) = self.find_delimiter(false) else {
+                    // The data before a possible delimiter is part of the body
+                    let keep = self.delimiter.len() + 2;
+                    let drain = self.buffer.len().saturating_sub(keep);
+
+                    self.part.data.extend(self.buffer.drain(..drain));
+
+                    return Ok(None);
+                };
+
+                // The line break before the delimiter isn't part of the body
+                let mut end = index;
+
+                if end > 0 && self.buffer[end - 1] == b'\r' {
+                    end -= 1;
+                }
+
+                self.part.data.extend_from_slice(&self.buffer[..end]);
+                self.buffer.drain(..index + 1 + self.delimiter.len());
+                self.state = State::Delimiter;
+
+                Ok(Some(Some(std::mem::take(&mut self.part))))
+            }
+            State::Done => {
+                // The epilogue is ignored
+                self.buffer.clear();
+
+                Ok(None)
+            }
+        }
+    }
+
+    // Finds a delimiter at the start of a line, returning the index of its line break, or of the
+    // delimiter itself for the first one when it starts the body
+    fn find_delimiter(&self, allow_start: bool) -> Option<usize> {
+        if allow_start && self.buffer.starts_with(&self.delimiter) {
+            return Some(0);
+        }
+
+        self.buffer
+            .windows(self.delimiter.len() + 1)
+            .position(|window| window[0] == b'\n' && window[1..] == self.delimiter[..])
+            .map(|index| if allow_start { index + 1 } else { index })
+    }
+
+    fn parse_header(&mut self, line: &str) {
+        let Some((name, value)) = line.split_once(':') else {
+            return;
+        };
+
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-disposition" => {
+                for (key, value) in parameters(value) {
+                    match key.as_str() {
+                        "name" => self.part.name = value,
+                        "filename" => self.part.filename = Some(value),
+                        _ => {}
+                    }
+                }
+            }
+            "content-type" => self.part.content_type = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+}
+
+// The parameters of a header value like `form-data; name="field"; filename="a.txt"`
+fn parameters(value: &str) -> Vec<(String, String)> {
+    let mut parameters = vec![];
+    let mut rest = value;
+
+    while let Some(index) = rest.find(';') {
+        rest = &rest[index + 1..];
+
+        let Some((key, after)) = rest.split_once('=') else {
+            break;
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let after = after.trim_start();
+
+        if let Some(quoted) = after.strip_prefix('"') {
+            let end = quoted.find('"').unwrap_or(quoted.len());
+
+            parameters.push((key, unescape(&quoted[..end])));
+            rest = quoted.get(end + 1..).unwrap_or_default();
+        } else {
+            let end = after.find(';').unwrap_or(after.len());
+
+            parameters.push((key, after[..end].trim().to_string()));
+            rest = &after[end..];
+        }
+    }
+
+    parameters
+}
+
+pub(crate) fn set_global_multipart(context: &Context) -> Result<()> {
+    let global = context.global_object()?;
+    let multipart = context.object_value()?;
+
+    multipart.set_property("boundary", context.wrap_callback(create_boundary)?)?;
+    multipart.set_property("encode", context.wrap_callback(encode_parts)?)?;
+    multipart.set_property("parser", context.wrap_callback(create_parser)?)?;
+    multipart.set_property("push", context.wrap_callback(push_chunk)?)?;
+    multipart.set_property("finish", context.wrap_callback(finish_parser)?)?;
+
+    global.set_property("___multipart", multipart)?;
+
+    Ok(())
+}
+
+fn create_boundary(context: &Context, _this: &Value, _args: &[Value]) -> Result<Value> {
+    context.value_from_str(&boundary())
+}
+
+// Receives an array of `{ name, data, filename, type }`, with `data` as an `ArrayBuffer`
+fn encode_parts(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [parts, boundary] => {
+            let length = parts.get_property("length")?.as_u32_unchecked();
+            let parts = (0..length)
+                .map(|index| {
+                    let part = parts.get_property(index.to_string())?;
+
+                    Ok(Part {
+                        name: part.get_property("name")?.as_str()?.to_string(),
+                        filename: optional_str(&part.get_property("filename")?)?.map(String::from),
+                        content_type: optional_str(&part.get_property("type")?)?.map(String::from),
+                        data: part.get_property("data")?.as_bytes()?.to_vec(),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            context.array_buffer_value(&encode(&parts, boundary.as_str()?))
+        }
+        _ => Err(anyhow!("expected 2 arguments, got {}", args.len())),
+    }
+}
+
+fn create_parser(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [boundary] => {
+            let id = PARSERS.insert(MultipartParser::new(boundary.as_str()?));
+
+            context.value_from_u32(id as u32)
+        }
+        _ => Err(anyhow!("expected 1 argument, got {}", args.len())),
+    }
+}
+
+fn push_chunk(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, chunk] => {
+            let id = index(id)?;
+            let parts = PARSERS.get(id, |parser| parser.push(chunk.as_bytes()?));
+
+            // A body which fails to parse can't be resumed
+            if parts.is_err() {
+                PARSERS.remove(id);
+            }
+
+            parts_value(context, parts?)
+        }
+        _ => Err(anyhow!("expected 2 arguments, got {}", args.len())),
+    }
+}
+
+fn finish_parser(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id] => {
+            let parts = PARSERS.take(index(id)?)?.finish()?;
+
+            parts_value(context, parts)
+        }
+        _ => Err(anyhow!("expected 1 argument, got {}", args.len())),
+    }
+}
+
+// The text parts have their value as a string, and the files as an `ArrayBuffer`
+fn parts_value(context: &Context, parts: Vec<Part>) -> Result<Value> {
+    let array = context.array_value()?;
+
+    for part in parts {
+        let value = context.object_value()?;
+
+        value.set_property("name", context.value_from_str(&part.name)?)?;
+
+        match &part.filename {
+            Some(filename) => {
+                value.set_property("filename", context.value_from_str(filename)?)?;
+                value.set_property(
+                    "type",
+                    context.value_from_str(part.content_type.as_deref().unwrap_or_default())?,
+                )?;
+                value.set_property("value", context.array_buffer_value(&part.data)?)?;
+            }
+            None => value.set_property(
+                "value",
+                context.value_from_str(&String::from_utf8_lossy(&part.data))?,
+            )?,
+        }
+
+        array.append_property(value)?;
+    }
+
+    Ok(array)
+}
@@ -0,0 +1,140 @@
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use quickjs_wasm_rs::{Context, Value};
+
+/// The values kept in Rust for the JS objects, like the header lists of the `Headers`, indexed by
+/// their id. The slots of the removed values are reused.
+///
+/// The values of the objects whose lifetime JS doesn't end explicitly, as `finish` does for a
+/// stream, are owned by a handle the object keeps. The QuickJS of the engine doesn't have a
+/// `FinalizationRegistry`, so the handle is a function, whose closure is dropped once the object
+/// is collected, removing the value.
+pub struct Registry<T> {
+    name: &'static str,
+    entries: Mutex<Vec<Option<T>>>,
+    // The ids of the collected handles. They are removed by the next `insert`, as the garbage
+    // collection can run while the entries are locked.
+    released: Mutex<Vec<usize>>,
+}
+
+impl<T> Registry<T> {
+    /// The name is the one of the values in the errors, e.g. `header list`.
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            entries: Mutex::new(vec![]),
+            released: Mutex::new(vec![]),
+        }
+    }
+
+    /// Stores the value, returning its id.
+    pub fn insert(&self, value: T) -> usize {
+        let mut entries = self.entries.lock().unwrap();
+
+        for id in self.released.lock().unwrap().drain(..) {
+            entries[id] = None;
+        }
+
+        match entries.iter().position(Option::is_none) {
+            Some(id) => {
+                entries[id] = Some(value);
+                id
+            }
+            None => {
+                entries.push(Some(value));
+                entries.len() - 1
+            }
+        }
+    }
+
+    /// Runs `f` with the value of the id.
+    pub fn get<R>(&self, id: usize, f: impl FnOnce(&mut T) -> Result<R>) -> Result<R> {
+        let mut entries = self.entries.lock().unwrap();
+        let value = entries
+            .get_mut(id)
+            .and_then(Option::as_mut)
+            .ok_or_else(|| self.unknown(id))?;
+
+        f(value)
+    }
+
+    /// Removes the value of the id and returns it.
+    pub fn take(&self, id: usize) -> Result<T> {
+        self.entries
+            .lock()
+            .unwrap()
+            .get_mut(id)
+            .and_then(Option::take)
+            .ok_or_else(|| self.unknown(id))
+    }
+
+    /// Removes the value of the id, if it's still there.
+    pub fn remove(&self, id: usize) {
+        if let Some(entry) = self.entries.lock().unwrap().get_mut(id) {
+            *entry = None;
+        }
+    }
+
+    /// The number of values stored.
+    pub fn len(&self) -> usize {
+        let released = self.released.lock().unwrap().len();
+        let entries = self.entries.lock().unwrap();
+
+        entries.iter().filter(|entry| entry.is_some()).count() - released
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn unknown(&self, id: usize) -> anyhow::Error {
+        anyhow!("Unknown {}: {id}", self.name)
+    }
+}
+
+impl<T: Send + 'static> Registry<T> {
+    /// Stores the value, returning `[id, handle]`. The value is removed once the handle is
+    /// collected, so the object the value belongs to keeps the handle.
+    pub fn insert_with_handle(&'static self, context: &Context, value: T) -> Result<Value> {
+        let id = self.insert(value);
+        let handle = Handle { registry: self, id };
+        let result = context.array_value()?;
+
+        result.append_property(context.value_from_u32(id as u32)?)?;
+        result.append_property(context.wrap_callback(move |context, _this, _args| {
+            context.value_from_u32(handle.id as u32)
+        })?)?;
+
+        Ok(result)
+    }
+}
+
+struct Handle<T: 'static> {
+    registry: &'static Registry<T>,
+    id: usize,
+}
+
+impl<T: 'static> Drop for Handle<T> {
+    fn drop(&mut self) {
+        self.registry.released.lock().unwrap().push(self.id);
+    }
+}
+
+/// Validates an id or an index sent by JS, which is an unsigned 32-bit integer.
+pub fn index(value: &Value) -> Result<usize> {
+    if !value.is_repr_as_i32() {
+        return Err(anyhow!("expected an index, as an u32"));
+    }
+
+    Ok(value.as_u32_unchecked() as usize)
+}
+
+/// A string argument which is optional, `undefined` or `null` when it isn't given.
+pub fn optional_str(value: &Value) -> Result<Option<&str>> {
+    if value.is_undefined() || value.is_null() {
+        return Ok(None);
+    }
+
+    Ok(Some(value.as_str()?))
+}
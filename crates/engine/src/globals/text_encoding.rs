@@ -0,0 +1,220 @@
+use anyhow::{anyhow, Result};
+use encoding_rs::{CoderResult, Decoder, DecoderResult, Encoding, REPLACEMENT};
+use quickjs_wasm_rs::{Context, JSError, Value};
+
+use super::registry::{index, Registry};
+
+// The decoders of the `decode(chunk, { stream: true })` calls in progress, removed by the last one
+static DECODERS: Registry<TextDecoder> = Registry::new("text decoder");
+
+/// Returns the encoding of a label, as named by the Encoding Standard.
+///
+/// @see: https://encoding.spec.whatwg.org/#concept-encoding-get
+pub fn encoding_for_label(label: &str) -> Option<&'static Encoding> {
+    // The replacement encoding is only used to prevent decoding as unsafe encodings
+    Encoding::for_label(label.as_bytes()).filter(|encoding| *encoding != REPLACEMENT)
+}
+
+/// Decodes to UTF-8, keeping the incomplete sequence at the end of a chunk for the next one.
+pub struct TextDecoder {
+    decoder: Decoder,
+    fatal: bool,
+}
+
+impl TextDecoder {
+    /// Unless `ignore_bom` is set, the BOM of the encoding is removed from the start.
+    pub fn new(encoding: &'static Encoding, fatal: bool, ignore_bom: bool) -> Self {
+        let decoder = if ignore_bom {
+            encoding.new_decoder_without_bom_handling()
+        } else {
+            encoding.new_decoder_with_bom_removal()
+        };
+
+        Self { decoder, fatal }
+    }
+
+    /// Decodes a chunk, the `last` one flushing the incomplete sequence. A `fatal` decoder fails on
+    /// malformed input, instead of replacing it with U+FFFD.
+    pub fn decode(&mut self, bytes: &[u8], last: bool) -> Result<String> {
+        let mut output = String::with_capacity(bytes.len());
+        let mut input = bytes;
+
+        loop {
+            let capacity = if self.fatal {
+                self.decoder
+                    .max_utf8_buffer_length_without_replacement(input.len())
+            } else {
+                self.decoder.max_utf8_buffer_length(input.len())
+            };
+
+            output.reserve(capacity.unwrap_or(input.len() * 3));
+
+            let (full, read) = if self.fatal {
+                let (result, read) =
+                    self.decoder
+                        .decode_to_string_without_replacement(input, &mut output, last);
+
+                match result {
+                    DecoderResult::InputEmpty => (false, read),
+                    DecoderResult::OutputFull => (true, read),
+                    DecoderResult::Malformed(..) => {
+                        return Err(JSError::Type(format!(
+                            "The encoded data was not valid {}",
+                            self.decoder.encoding().name().to_ascii_lowercase()
+                        ))
+                        .into())
+                    }
+                }
+            } else {
+                let (result, read, _) = self.decoder.decode_to_string(input, &mut output, last);
+
+                (result == CoderResult::OutputFull, read)
+            };
+
+            if !full {
+                return Ok(output);
+            }
+
+            input = &input[read..];
+        }
+    }
+}
+
+/// Encodes as UTF-8 into `destination`, as long as the next character fits. Returns the UTF-16
+/// code units read and the bytes written, as `TextEncoder.encodeInto`.
+///
+/// @see: https://encoding.spec.whatwg.org/#dom-textencoder-encodeinto
+pub fn encode_into(source: &str, destination: &mut [u8]) -> (usize, usize) {
+    let mut read = 0;
+    let mut written = 0;
+
+    for char in source.chars() {
+        let length = char.len_utf8();
+
+        if written + length > destination.len() {
+            break;
+        }
+
+        char.encode_utf8(&mut destination[written..]);
+        read += char.len_utf16();
+        written += length;
+    }
+
+    (read, written)
+}
+
+pub(crate) fn set_global_text_encoding(context: &Context) -> Result<()> {
+    let global = context.global_object()?;
+    let text_encoding = context.object_value()?;
+
+    text_encoding.set_property("encoding", context.wrap_callback(encoding)?)?;
+    text_encoding.set_property("decode", context.wrap_callback(decode)?)?;
+    text_encoding.set_property("decoder", context.wrap_callback(create_decoder)?)?;
+    text_encoding.set_property("decodeChunk", context.wrap_callback(decode_chunk)?)?;
+    text_encoding.set_property("encodeInto", context.wrap_callback(encode_into_buffer)?)?;
+
+    global.set_property("___textEncoding", text_encoding)?;
+
+    Ok(())
+}
+
+// The name of the encoding of a label, or null when it isn't supported
+fn encoding(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [label] => match encoding_for_label(label.as_str()?) {
+            Some(encoding) => context.value_from_str(&encoding.name().to_ascii_lowercase()),
+            None => context.null_value(),
+        },
+        _ => Err(anyhow!("expected 1 argument, got {}", args.len())),
+    }
+}
+
+fn decode(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [label, fatal, ignore_bom, buffer, byte_offset, byte_length] => {
+            let mut decoder = TextDecoder::new(
+                label_encoding(label)?,
+                fatal.as_bool()?,
+                ignore_bom.as_bool()?,
+            );
+            let bytes = view(buffer, byte_offset, byte_length)?;
+
+            context.value_from_str(&decoder.decode(bytes, true)?)
+        }
+        _ => Err(anyhow!("expected 6 arguments, got {}", args.len())),
+    }
+}
+
+fn create_decoder(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [label, fatal, ignore_bom] => {
+            let decoder = TextDecoder::new(
+                label_encoding(label)?,
+                fatal.as_bool()?,
+                ignore_bom.as_bool()?,
+            );
+            let id = DECODERS.insert(decoder);
+
+            context.value_from_u32(id as u32)
+        }
+        _ => Err(anyhow!("expected 3 arguments, got {}", args.len())),
+    }
+}
+
+// The last chunk ends the decoder, as does a malformed chunk of a fatal one
+fn decode_chunk(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, buffer, byte_offset, byte_length, last] => {
+            let last = last.as_bool()?;
+            let id = index(id)?;
+            let bytes = view(buffer, byte_offset, byte_length)?;
+            let text = DECODERS.get(id, |decoder| decoder.decode(bytes, last));
+
+            if last || text.is_err() {
+                DECODERS.remove(id);
+            }
+
+            context.value_from_str(&text?)
+        }
+        _ => Err(anyhow!("expected 5 arguments, got {}", args.len())),
+    }
+}
+
+fn encode_into_buffer(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [source, buffer, byte_offset, byte_length] => {
+            let source = source.as_str_lossy();
+            let start = index(byte_offset)?;
+            let end = start + index(byte_length)?;
+            let destination = buffer
+                .as_bytes_mut()?
+                .get_mut(start..end)
+                .ok_or_else(|| anyhow!("The offset and length are not valid for the buffer"))?;
+            let (read, written) = encode_into(&source, destination);
+            let result = context.object_value()?;
+
+            result.set_property("read", context.value_from_u32(read as u32)?)?;
+            result.set_property("written", context.value_from_u32(written as u32)?)?;
+
+            Ok(result)
+        }
+        _ => Err(anyhow!("expected 4 arguments, got {}", args.len())),
+    }
+}
+
+fn label_encoding(label: &Value) -> Result<&'static Encoding> {
+    let label = label.as_str()?;
+
+    encoding_for_label(label)
+        .ok_or_else(|| JSError::Range(format!("The encoding label '{label}' is invalid")).into())
+}
+
+fn view<'a>(buffer: &'a Value, byte_offset: &Value, byte_length: &Value) -> Result<&'a [u8]> {
+    let start = index(byte_offset)?;
+    let end = start + index(byte_length)?;
+
+    buffer
+        .as_bytes()?
+        .get(start..end)
+        .ok_or_else(|| anyhow!("The offset and length are not valid for the buffer"))
+}
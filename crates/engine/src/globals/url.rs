@@ -0,0 +1,137 @@
+use anyhow::{anyhow, Result};
+use quickjs_wasm_rs::{Context, JSError, Value};
+use url::{quirks, Url};
+
+use super::registry::{index, optional_str, Registry};
+
+// The parsed URLs of the `URL` instances, which keep their handle. The setters update them in
+// place, instead of parsing the whole href again.
+static URLS: Registry<Url> = Registry::new("URL");
+
+/// Parses a URL against an optional base, as the `URL` constructor does.
+///
+/// @see: https://url.spec.whatwg.org/#api-url-parser
+pub fn parse(input: &str, base: Option<&str>) -> Result<Url> {
+    let base = base
+        .map(Url::parse)
+        .transpose()
+        .map_err(|_| JSError::Type(format!("Invalid base URL: {}", base.unwrap_or_default())))?;
+
+    Url::options()
+        .base_url(base.as_ref())
+        .parse(input)
+        .map_err(|_| JSError::Type(format!("Invalid URL: {input}")).into())
+}
+
+/// Reads a component of the URL, serialized as the getter of the `URL` attribute.
+pub fn get(url: &Url, component: &str) -> Result<String> {
+    Ok(match component {
+        "href" => quirks::href(url).to_string(),
+        "origin" => quirks::origin(url),
+        "protocol" => quirks::protocol(url).to_string(),
+        "username" => quirks::username(url).to_string(),
+        "password" => quirks::password(url).to_string(),
+        "host" => quirks::host(url).to_string(),
+        "hostname" => quirks::hostname(url).to_string(),
+        "port" => quirks::port(url).to_string(),
+        "pathname" => quirks::pathname(url).to_string(),
+        "search" => quirks::search(url).to_string(),
+        "hash" => quirks::hash(url).to_string(),
+        _ => return Err(anyhow!("Unknown URL component {component}")),
+    })
+}
+
+/// Updates a component of the URL, as the setter of the `URL` attribute. Only an invalid href
+/// fails, the other invalid values leave the URL unchanged.
+pub fn set(url: &mut Url, component: &str, value: &str) -> Result<()> {
+    // The setters report their failures, but the spec ignores them
+    match component {
+        "href" => {
+            quirks::set_href(url, value)
+                .map_err(|_| JSError::Type(format!("Invalid URL: {value}")))?;
+        }
+        "protocol" => {
+            let _ = quirks::set_protocol(url, value);
+        }
+        "username" => {
+            let _ = quirks::set_username(url, value);
+        }
+        "password" => {
+            let _ = quirks::set_password(url, value);
+        }
+        "host" => {
+            let _ = quirks::set_host(url, value);
+        }
+        "hostname" => {
+            let _ = quirks::set_hostname(url, value);
+        }
+        "port" => {
+            let _ = quirks::set_port(url, value);
+        }
+        "pathname" => quirks::set_pathname(url, value),
+        "search" => quirks::set_search(url, value),
+        "hash" => quirks::set_hash(url, value),
+        _ => return Err(anyhow!("Unknown URL component {component}")),
+    }
+
+    Ok(())
+}
+
+pub(crate) fn set_global_url(context: &Context) -> Result<()> {
+    let global = context.global_object()?;
+    let url = context.object_value()?;
+
+    url.set_property("parse", context.wrap_callback(parse_url)?)?;
+    url.set_property("canParse", context.wrap_callback(can_parse_url)?)?;
+    url.set_property("get", context.wrap_callback(get_component)?)?;
+    url.set_property("set", context.wrap_callback(set_component)?)?;
+
+    global.set_property("___url", url)?;
+
+    Ok(())
+}
+
+// Returns the `[id, handle]` of the parsed URL, or throws a `TypeError`
+fn parse_url(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [input, base] => {
+            let url = parse(input.as_str()?, optional_str(base)?)?;
+
+            URLS.insert_with_handle(context, url)
+        }
+        _ => Err(anyhow!("expected 2 arguments, got {}", args.len())),
+    }
+}
+
+fn can_parse_url(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [input, base] => {
+            context.value_from_bool(parse(input.as_str()?, optional_str(base)?).is_ok())
+        }
+        _ => Err(anyhow!("expected 2 arguments, got {}", args.len())),
+    }
+}
+
+fn get_component(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, component] => {
+            let value = URLS.get(index(id)?, |url| get(url, component.as_str()?))?;
+
+            context.value_from_str(&value)
+        }
+        _ => Err(anyhow!("expected 2 arguments, got {}", args.len())),
+    }
+}
+
+fn set_component(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, component, value] => {
+            URLS.get(index(id)?, |url| {
+                set(url, component.as_str()?, value.as_str()?)
+            })?;
+
+            context.undefined_value()
+        }
+        _ => Err(anyhow!("expected 3 arguments, got {}", args.len())),
+    }
+}
@@ -0,0 +1,209 @@
+use anyhow::{anyhow, Result};
+use quickjs_wasm_rs::{Context, JSError, Value};
+use urlpattern::{
+    quirks::{self, StringOrInit, UrlPatternInit},
+    UrlPattern, UrlPatternComponentResult, UrlPatternResult,
+};
+
+use super::registry::{index, optional_str, Registry};
+
+// The compiled patterns of the `URLPattern` instances, which keep their handle
+static URL_PATTERNS: Registry<CompiledPattern> = Registry::new("URL pattern");
+
+/// A pattern, with the names of the groups of its components in their order in the pattern, as
+/// the groups of a match are hashed.
+pub struct CompiledPattern {
+    pub pattern: UrlPattern,
+    components: quirks::UrlPattern,
+}
+
+/// Compiles a pattern, either a string relative to `base_url` or the patterns of the components.
+///
+/// @see: https://urlpattern.spec.whatwg.org/#dom-urlpattern-urlpattern
+pub fn compile(input: StringOrInit, base_url: Option<&str>) -> Result<CompiledPattern> {
+    quirks::process_construct_pattern_input(input, base_url)
+        .and_then(|init| {
+            Ok(CompiledPattern {
+                components: quirks::parse_pattern(init.clone())?,
+                pattern: UrlPattern::parse(init)?,
+            })
+        })
+        .map_err(|error| JSError::Type(format!("Failed to construct 'URLPattern': {error}")).into())
+}
+
+/// Matches a URL, or the components of one, against the pattern. An input which can't be parsed
+/// doesn't match.
+///
+/// @see: https://urlpattern.spec.whatwg.org/#url-pattern-match
+pub fn exec(
+    pattern: &UrlPattern,
+    input: StringOrInit,
+    base_url: Option<&str>,
+) -> Result<Option<UrlPatternResult>> {
+    let input = quirks::process_match_input(input, base_url)
+        .map_err(|error| JSError::Type(error.to_string()))?;
+
+    match input {
+        Some((input, _)) => pattern
+            .exec(input)
+            .map_err(|error| JSError::Type(error.to_string()).into()),
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn set_global_url_pattern(context: &Context) -> Result<()> {
+    let global = context.global_object()?;
+    let url_pattern = context.object_value()?;
+
+    url_pattern.set_property("create", context.wrap_callback(create_pattern)?)?;
+    url_pattern.set_property("get", context.wrap_callback(get_component)?)?;
+    url_pattern.set_property("test", context.wrap_callback(test_pattern)?)?;
+    url_pattern.set_property("exec", context.wrap_callback(exec_pattern)?)?;
+
+    global.set_property("___urlPattern", url_pattern)?;
+
+    Ok(())
+}
+
+// Returns the `[id, handle]` of the compiled pattern, or throws a `TypeError`
+fn create_pattern(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [input, base_url] => {
+            let pattern = compile(string_or_init(input)?, optional_str(base_url)?)?;
+
+            URL_PATTERNS.insert_with_handle(context, pattern)
+        }
+        _ => Err(anyhow!("expected 2 arguments, got {}", args.len())),
+    }
+}
+
+// The normalized pattern of a component
+fn get_component(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, component] => {
+            let component =
+                URL_PATTERNS.get(index(id)?, |CompiledPattern { pattern, .. }| {
+                    Ok(match component.as_str()? {
+                        "protocol" => pattern.protocol(),
+                        "username" => pattern.username(),
+                        "password" => pattern.password(),
+                        "hostname" => pattern.hostname(),
+                        "port" => pattern.port(),
+                        "pathname" => pattern.pathname(),
+                        "search" => pattern.search(),
+                        "hash" => pattern.hash(),
+                        component => {
+                            return Err(anyhow!("Unknown URL pattern component {component}"))
+                        }
+                    }
+                    .to_string())
+                })?;
+
+            context.value_from_str(&component)
+        }
+        _ => Err(anyhow!("expected 2 arguments, got {}", args.len())),
+    }
+}
+
+fn test_pattern(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, input, base_url] => {
+            let input = string_or_init(input)?;
+            let base_url = optional_str(base_url)?;
+            let result = URL_PATTERNS.get(index(id)?, |CompiledPattern { pattern, .. }| {
+                exec(pattern, input, base_url)
+            })?;
+
+            context.value_from_bool(result.is_some())
+        }
+        _ => Err(anyhow!("expected 3 arguments, got {}", args.len())),
+    }
+}
+
+// Returns the `{ input, groups }` of each component, or null when the input doesn't match. The
+// `inputs` of the result are added in JS.
+fn exec_pattern(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [id, input, base_url] => {
+            let input = string_or_init(input)?;
+            let base_url = optional_str(base_url)?;
+
+            URL_PATTERNS.get(
+                index(id)?,
+                |CompiledPattern {
+                     pattern,
+                     components,
+                 }| {
+                    let result = match exec(pattern, input, base_url)? {
+                        Some(result) => result,
+                        None => return context.null_value(),
+                    };
+                    let value = context.object_value()?;
+
+                    for (name, component, pattern) in [
+                        ("protocol", &result.protocol, &components.protocol),
+                        ("username", &result.username, &components.username),
+                        ("password", &result.password, &components.password),
+                        ("hostname", &result.hostname, &components.hostname),
+                        ("port", &result.port, &components.port),
+                        ("pathname", &result.pathname, &components.pathname),
+                        ("search", &result.search, &components.search),
+                        ("hash", &result.hash, &components.hash),
+                    ] {
+                        value.set_property(
+                            name,
+                            component_result(context, component, &pattern.group_name_list)?,
+                        )?;
+                    }
+
+                    Ok(value)
+                },
+            )
+        }
+        _ => Err(anyhow!("expected 3 arguments, got {}", args.len())),
+    }
+}
+
+// The groups are set in the order of their names in the pattern
+fn component_result(
+    context: &Context,
+    component: &UrlPatternComponentResult,
+    names: &[String],
+) -> Result<Value> {
+    let result = context.object_value()?;
+    let groups = context.object_value()?;
+
+    for name in names {
+        if let Some(value) = component.groups.get(name) {
+            groups.set_property(name.as_str(), context.value_from_str(value)?)?;
+        }
+    }
+
+    result.set_property("input", context.value_from_str(&component.input)?)?;
+    result.set_property("groups", groups)?;
+
+    Ok(result)
+}
+
+// Either a string, or an object with the components and the `baseURL` as strings
+fn string_or_init(value: &Value) -> Result<StringOrInit> {
+    if value.is_str() {
+        return Ok(StringOrInit::String(value.as_str()?.to_string()));
+    }
+
+    let component = |name: &str| -> Result<Option<String>> {
+        Ok(optional_str(&value.get_property(name)?)?.map(String::from))
+    };
+
+    Ok(StringOrInit::Init(UrlPatternInit {
+        protocol: component("protocol")?,
+        username: component("username")?,
+        password: component("password")?,
+        hostname: component("hostname")?,
+        port: component("port")?,
+        pathname: component("pathname")?,
+        search: component("search")?,
+        hash: component("hash")?,
+        base_url: component("baseURL")?,
+    }))
+}
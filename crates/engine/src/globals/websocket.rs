@@ -0,0 +1,79 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{anyhow, Result};
+use quickjs_wasm_rs::{Context, Value};
+use serde::Deserialize;
+
+use crate::fetch::mem::{FromMem, ToMem};
+
+extern "C" {
+    fn import_websocket_send(ptr: *const u8);
+    fn import_websocket_receive() -> *mut u8;
+}
+
+// Set once the response upgraded the connection, the host then relays the messages of the client
+static UPGRADED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Deserialize)]
+struct Message {
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+pub fn set_global_websocket(context: &Context) -> Result<()> {
+    let global = context.global_object()?;
+    let websocket = context.object_value()?;
+
+    websocket.set_property("send", context.wrap_callback(send)?)?;
+    websocket.set_property("upgrade", context.wrap_callback(upgrade)?)?;
+
+    global.set_property("___websocket", websocket)?;
+
+    Ok(())
+}
+
+pub fn is_upgraded() -> bool {
+    UPGRADED.load(Ordering::SeqCst)
+}
+
+/// Dispatches the messages of the client to the server end of the `WebSocketPair`, until the
+/// connection is closed.
+pub fn relay(context: &Context) -> Result<()> {
+    let global = context.global_object()?;
+    let dispatch = global.get_property("___dispatchWebSocketMessage")?;
+
+    loop {
+        let message = unsafe { String::from_mem(import_websocket_receive()) };
+        let Message { kind } = serde_json::from_str(&message)?;
+
+        dispatch.call(&global, &[context.value_from_str(&message)?])?;
+        context.execute_pending()?;
+
+        if kind == "close" {
+            return Ok(());
+        }
+    }
+}
+
+// Sends a message to the client, as JSON
+fn send(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [message] => {
+            unsafe { import_websocket_send(message.as_str()?.to_mem()) };
+
+            context.undefined_value()
+        }
+        _ => Err(anyhow!("expected 1 argument, got {}", args.len())),
+    }
+}
+
+fn upgrade(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
+    match args {
+        [] => {
+            UPGRADED.store(true, Ordering::SeqCst);
+
+            context.undefined_value()
+        }
+        _ => Err(anyhow!("expected 0 arguments, got {}", args.len())),
+    }
+}
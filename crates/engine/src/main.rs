@@ -1,6 +1,6 @@
 use std::{
     env,
-    io::{stderr, stdin, stdout, Read, Write},
+    io::{stderr, stdin, Read},
     ops::Deref,
     str,
     sync::Mutex,
@@ -8,45 +8,94 @@ use std::{
 
 use anyhow::{anyhow, Result};
 use once_cell::sync::{Lazy, OnceCell};
-use quickjs_wasm_rs::{json, Context, Exception, Value};
+use quickjs_wasm_rs::{Context, Value};
 use send_wrapper::SendWrapper;
 
+mod compile;
+mod error;
 mod fetch;
 mod globals;
+mod modules;
 mod request;
+mod response;
 mod tests;
+mod trace;
 
+use compile::{compile, COMPILE_ENV};
+use error::HandlerError;
 use fetch::fetch::fetch;
-use globals::{console::set_global_console, utils::set_global_utils};
+use globals::websocket::{is_upgraded, relay};
+use globals::{
+    blob::set_global_blob, compression::set_global_compression, console::set_global_console,
+    headers::set_global_headers, kv::set_global_kv, multipart::set_global_multipart,
+    text_encoding::set_global_text_encoding, url::set_global_url,
+    url_pattern::set_global_url_pattern, utils::set_global_utils, websocket::set_global_websocket,
+};
+use modules::Source;
+use response::respond;
+use trace::span;
 
+#[cfg(not(feature = "bytecode"))]
 static WEB_PLATFORM_APIS: &str = include_str!("../dist/web-platform-apis.js");
 
+// Compiled from `dist/web-platform-apis.js` by the engine itself, see `make engine-bytecode`
+#[cfg(feature = "bytecode")]
+static WEB_PLATFORM_APIS: &[u8] = include_bytes!("../dist/web-platform-apis.bc");
+
 static ON_RESOLVE: OnceCell<SendWrapper<Value>> = OnceCell::new();
 static ON_REJECT: OnceCell<SendWrapper<Value>> = OnceCell::new();
-static RESPONSE: Lazy<Mutex<Option<Result<SendWrapper<Value>>>>> = Lazy::new(|| Mutex::new(None));
+static RESPONDED: Lazy<Mutex<Option<Result<()>>>> = Lazy::new(|| Mutex::new(None));
 
 fn main() -> Result<()> {
+    if let Ok(mode) = env::var(COMPILE_ENV) {
+        return compile(&mode);
+    }
+
+    let result = run();
+
+    if let Err(error) = &result {
+        error
+            .downcast_ref::<HandlerError>()
+            .cloned()
+            .unwrap_or_else(|| HandlerError::from_error(error))
+            .report();
+    }
+
+    result
+}
+
+fn run() -> Result<()> {
     let context = Context::default();
 
     fetch(&context)?;
     set_global_utils(&context)?;
-    set_global_console(&context, stderr(), stderr())?;
+    set_global_headers(&context)?;
+    set_global_kv(&context)?;
+    set_global_websocket(&context)?;
+    set_global_multipart(&context)?;
+    set_global_blob(&context)?;
+    set_global_text_encoding(&context)?;
+    set_global_compression(&context)?;
+    set_global_url(&context)?;
+    set_global_url_pattern(&context)?;
+    set_global_console(
+        &context,
+        stderr(),
+        stderr(),
+        &env::var("WORKER_ID").unwrap_or_default(),
+    )?;
 
-    let mut contents = String::new();
-    let mut source = String::new();
-
-    contents.push_str(WEB_PLATFORM_APIS);
+    let global = context.global_object()?;
 
-    stdin().read_to_string(&mut source)?;
+    let mut input = String::new();
 
-    contents.push_str(&source);
+    stdin().read_to_string(&mut input)?;
 
-    let _ = context.eval_global("handler.js", &contents)?;
-
-    let global = context.global_object()?;
+    let source = Source::parse(&input);
 
+    // Only the bindings set by the host, its environment isn't exposed to the handler
     let env = context.object_value()?;
-    for (key, value) in env::vars() {
+    for (key, value) in source.process_env() {
         env.set_property(key, context.value_from_str(&value)?)?;
     }
 
@@ -55,6 +104,13 @@ fn main() -> Result<()> {
 
     global.set_property("process", process)?;
 
+    #[cfg(not(feature = "bytecode"))]
+    context.eval_global("web-platform-apis.js", WEB_PLATFORM_APIS)?;
+    #[cfg(feature = "bytecode")]
+    context.eval_binary(WEB_PLATFORM_APIS)?;
+
+    let handle_request = span("handler_evaluation", || modules::load(&context, &source))?;
+
     let on_resolve_wrap = context.wrap_callback(on_resolve)?;
     let on_reject_wrap = context.wrap_callback(on_reject)?;
 
@@ -64,37 +120,42 @@ fn main() -> Result<()> {
     let on_resolve = ON_RESOLVE.get().unwrap();
     let on_reject = ON_REJECT.get().unwrap();
 
-    let handler_request = global.get_property("handleRequest")?;
-
-    if !handler_request.is_function() {
-        panic!(r#"Expected "handleRequest" function"#);
-    }
-
     let handler = global.get_property("___handleResponse")?;
 
     let args = env::args().collect::<Vec<String>>();
 
+    let bindings = context.value_from_str(&serde_json::to_string(&source.env)?)?;
+
     // @see: https://github.com/fermyon/spin-js-sdk/blob/569b76d32c06d44d9b6c928e526c82594782c4cb/crates/spin-js-engine/src/lib.rs#L552
-    let output = handler.call(&global, &[request::request(args, &context)?])?;
+    let output = handler.call(
+        &global,
+        &[handle_request, request::request(args, &context)?, bindings],
+    )?;
     let then = output.get_property("then")?;
-    let response = if then.is_function() {
+
+    if then.is_function() {
         then.call(
             &output,
             &[on_resolve.deref().clone(), on_reject.deref().clone()],
         )?;
 
+        // The response is sent once it resolves, and the pending jobs include the ones registered
+        // with `ctx.waitUntil`, which keep running after it
         context.execute_pending()?;
 
-        json::transcode_output(RESPONSE.lock().unwrap().take().unwrap()?.take())?
+        RESPONDED
+            .lock()
+            .unwrap()
+            .take()
+            .unwrap_or_else(|| Err(anyhow!("The handler didn't return a response")))?;
     } else {
-        json::transcode_output(output)?
-    };
-
-    stdout()
-        .write_all(&response)
-        .expect("Error when returning the response");
+        respond(output)?;
+    }
 
-    stdout().flush().expect("Error when returning the response");
+    // The response upgraded the connection, the invocation lasts until the WebSocket is closed
+    if is_upgraded() {
+        relay(&context)?;
+    }
 
     Ok(())
 }
@@ -102,7 +163,7 @@ fn main() -> Result<()> {
 fn on_resolve(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
     match args {
         [response] => {
-            *RESPONSE.lock().unwrap() = Some(Ok(SendWrapper::new(response.clone())));
+            *RESPONDED.lock().unwrap() = Some(respond(response.clone()));
 
             context.undefined_value()
         }
@@ -114,7 +175,7 @@ fn on_resolve(context: &Context, _this: &Value, args: &[Value]) -> Result<Value>
 fn on_reject(context: &Context, _this: &Value, args: &[Value]) -> Result<Value> {
     match args {
         [error] => {
-            *RESPONSE.lock().unwrap() = Some(Err(Exception::from(error.clone())?.into_error()));
+            *RESPONDED.lock().unwrap() = Some(Err(HandlerError::from_value(error)?.into()));
 
             context.undefined_value()
         }
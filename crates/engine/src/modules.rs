@@ -0,0 +1,251 @@
+use std::{
+    collections::BTreeMap,
+    ffi::{c_char, c_void, CStr, CString},
+    ptr,
+};
+
+use anyhow::Result;
+use quickjs_wasm_rs::{Context, Value};
+use quickjs_wasm_sys::{
+    JSContext, JSModuleDef, JS_Eval, JS_GetRuntime, JS_ReadObject, JS_SetModuleLoaderFunc,
+    JS_ThrowReferenceError, JS_EVAL_FLAG_COMPILE_ONLY, JS_EVAL_TYPE_MODULE, JS_READ_OBJ_BYTECODE,
+    JS_TAG_EXCEPTION,
+};
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+
+use crate::error::HandlerError;
+
+/// The name the handler module is evaluated with, so relative imports resolve from the bundle root
+pub const HANDLER_MODULE: &str = "handler.js";
+
+const ENTRY_MODULE: &str = "___entry.js";
+const HANDLER_NAMESPACE: &str = "___handlerNamespace";
+
+/// The handler and the modules it can import, as sent by the host through stdin.
+#[derive(Deserialize, Debug, Default)]
+pub struct Source {
+    #[serde(default)]
+    pub handler: String,
+    /// Modules keyed by their path relative to the handler, e.g. `lib/utils.js`
+    #[serde(default)]
+    pub modules: BTreeMap<String, String>,
+    /// The bindings of the `env` passed to the handler, keyed by their name
+    #[serde(default)]
+    pub env: BTreeMap<String, serde_json::Value>,
+    /// The handler and its modules compiled ahead of time, used instead of their source
+    #[serde(default)]
+    pub bytecode: Vec<CompiledModule>,
+}
+
+/// A module compiled to QuickJS bytecode, which keeps the name it was compiled with.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CompiledModule {
+    pub name: String,
+    pub bytecode: ByteBuf,
+}
+
+impl Source {
+    /// Parses the bundle sent by the host, falling back to a single module handler.
+    pub fn parse(input: &str) -> Self {
+        serde_json::from_str(input).unwrap_or_else(|_| Source {
+            handler: input.to_string(),
+            ..Default::default()
+        })
+    }
+
+    /// The vars and secrets of the bindings, as the strings of `process.env`. The JSON vars are
+    /// serialized.
+    pub fn process_env(&self) -> BTreeMap<&str, String> {
+        self.env
+            .iter()
+            .filter(|(_, binding)| {
+                matches!(
+                    binding.get("type").and_then(serde_json::Value::as_str),
+                    Some("var" | "secret")
+                )
+            })
+            .filter_map(|(name, binding)| {
+                let value = binding.get("value")?;
+
+                Some((
+                    name.as_str(),
+                    value
+                        .as_str()
+                        .map_or_else(|| value.to_string(), str::to_string),
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Evaluates the handler as an ES module and returns its handler function. Its imports, static
+/// or dynamic, are loaded by QuickJS from the modules of the source when they are first imported.
+pub fn load(context: &Context, source: &Source) -> Result<Value> {
+    set_module_loader(context, Modules::new(source));
+
+    context.eval_module(
+        ENTRY_MODULE,
+        &format!(
+            r#"import * as handler from "{HANDLER_MODULE}"; globalThis.{HANDLER_NAMESPACE} = handler;"#
+        ),
+    )?;
+
+    let global = context.global_object()?;
+    let namespace = global.get_property(HANDLER_NAMESPACE)?;
+
+    handler(&global, &namespace)
+}
+
+/// Compiles the handler and each of its modules to bytecode, as they are loaded by `load`.
+pub fn compile(context: &Context, source: &Source) -> Result<Vec<CompiledModule>> {
+    sources(source)
+        .into_iter()
+        .map(|(name, contents)| {
+            Ok(CompiledModule {
+                bytecode: ByteBuf::from(context.compile_module(&name, &contents)?),
+                name,
+            })
+        })
+        .collect()
+}
+
+// The handler and its modules, keyed by the names the QuickJS normalizer resolves the imports to
+fn sources(source: &Source) -> BTreeMap<String, String> {
+    source
+        .modules
+        .iter()
+        .map(|(name, contents)| (name.trim_start_matches("./").to_string(), contents.clone()))
+        .chain([(HANDLER_MODULE.to_string(), source.handler.clone())])
+        .collect()
+}
+
+/// Finds the handler through a named `handleRequest` export, a default export with a `fetch`
+/// method, or the `fetch` event listeners. A `handleRequest` set on the global object is still
+/// supported.
+pub fn handler(global: &Value, namespace: &Value) -> Result<Value> {
+    let handle_request = namespace.get_property("handleRequest")?;
+
+    if handle_request.is_function() {
+        return Ok(handle_request);
+    }
+
+    let default = namespace.get_property("default")?;
+
+    if default.is_object() {
+        let fetch = default.get_property("fetch")?;
+
+        if fetch.is_function() {
+            return fetch.get_property("bind")?.call(&fetch, &[default]);
+        }
+    }
+
+    // Set once a script calls `addEventListener("fetch", listener)`
+    let fetch_event_handler = global.get_property("___fetchEventHandler")?;
+
+    if fetch_event_handler.is_function() {
+        return Ok(fetch_event_handler);
+    }
+
+    let handle_request = global.get_property("handleRequest")?;
+
+    if handle_request.is_function() {
+        return Ok(handle_request);
+    }
+
+    Err(HandlerError::missing_handler(
+        r#"Expected a "handleRequest" export, a default export with a "fetch" method or a "fetch" event listener"#,
+    )
+    .into())
+}
+
+/// The modules the loader can return, as source or as bytecode.
+struct Modules {
+    sources: BTreeMap<String, String>,
+    bytecode: BTreeMap<String, Vec<u8>>,
+}
+
+impl Modules {
+    fn new(source: &Source) -> Self {
+        if source.bytecode.is_empty() {
+            Self {
+                sources: sources(source),
+                bytecode: BTreeMap::new(),
+            }
+        } else {
+            Self {
+                sources: BTreeMap::new(),
+                bytecode: source
+                    .bytecode
+                    .iter()
+                    .map(|module| (module.name.clone(), module.bytecode.to_vec()))
+                    .collect(),
+            }
+        }
+    }
+}
+
+// Registered on the runtime of the context, the modules live as long as it does. QuickJS keeps the
+// default normalizer, which resolves the `./` and `../` specifiers against the importing module.
+fn set_module_loader(context: &Context, modules: Modules) {
+    let opaque = Box::into_raw(Box::new(modules)) as *mut c_void;
+
+    unsafe {
+        JS_SetModuleLoaderFunc(
+            JS_GetRuntime(context.inner()),
+            None,
+            Some(load_module),
+            opaque,
+        );
+    }
+}
+
+// Called by QuickJS with the normalized name of a module imported for the first time, returns the
+// compiled module, not evaluated yet, or null with a pending exception
+unsafe extern "C" fn load_module(
+    ctx: *mut JSContext,
+    module_name: *const c_char,
+    opaque: *mut c_void,
+) -> *mut JSModuleDef {
+    let modules = &*(opaque as *const Modules);
+    let name = CStr::from_ptr(module_name).to_string_lossy();
+
+    let value = if let Some(contents) = modules.sources.get(name.as_ref()) {
+        // The source has to be null terminated
+        let Ok(input) = CString::new(contents.as_str()) else {
+            return throw_reference_error(ctx, &format!("invalid module '{name}'"));
+        };
+
+        JS_Eval(
+            ctx,
+            input.as_ptr(),
+            contents.len() as _,
+            module_name,
+            (JS_EVAL_TYPE_MODULE | JS_EVAL_FLAG_COMPILE_ONLY) as i32,
+        )
+    } else if let Some(bytecode) = modules.bytecode.get(name.as_ref()) {
+        JS_ReadObject(
+            ctx,
+            bytecode.as_ptr(),
+            bytecode.len() as _,
+            JS_READ_OBJ_BYTECODE as i32,
+        )
+    } else {
+        return throw_reference_error(ctx, &format!("could not load module '{name}'"));
+    };
+
+    // The value is NaN-boxed on wasm32, with the tag in its high bits and the pointer in its low ones
+    if (value >> 32) as i32 == JS_TAG_EXCEPTION as i32 {
+        return ptr::null_mut();
+    }
+
+    value as u32 as usize as *mut JSModuleDef
+}
+
+unsafe fn throw_reference_error(ctx: *mut JSContext, message: &str) -> *mut JSModuleDef {
+    let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+
+    JS_ThrowReferenceError(ctx, b"%s\0".as_ptr() as *const c_char, message.as_ptr());
+
+    ptr::null_mut()
+}
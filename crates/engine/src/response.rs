@@ -0,0 +1,20 @@
+use anyhow::Result;
+use quickjs_wasm_rs::{json, Value};
+
+use crate::{fetch::mem::ToMem, trace::span};
+
+extern "C" {
+    fn import_respond(ptr: *const u8);
+}
+
+// Sends the response to the host as soon as it is ready, so the host doesn't have to wait for the
+// work registered with `ctx.waitUntil`
+pub fn respond(output: Value) -> Result<()> {
+    span("response_serialization", || {
+        let response = String::from_utf8(json::transcode_output(output)?)?;
+
+        unsafe { import_respond(response.as_str().to_mem()) };
+
+        Ok(())
+    })
+}
@@ -1,4 +1,7 @@
 mod console;
 mod core;
+mod error;
 mod fetch;
+mod modules;
 mod test_utils;
+mod workers;
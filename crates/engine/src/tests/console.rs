@@ -2,26 +2,30 @@
 mod tests {
     use anyhow::Result;
     use quickjs_wasm_rs::Context;
+    use serde_json::Value;
     use std::cell::RefCell;
     use std::io;
     use std::rc::Rc;
 
     use crate::globals::console::set_global_console;
 
+    const WORKER_ID: &str = "worker-test";
+
     #[test]
     fn test_console_log() -> Result<()> {
         let mut stream = SharedStream::default();
 
         let ctx = Context::default();
-        set_global_console(&ctx, stream.clone(), stream.clone())?;
+        set_global_console(&ctx, stream.clone(), stream.clone(), WORKER_ID)?;
 
         ctx.eval_global("main", "console.log(\"hello world\");")?;
-        assert_eq!(b"hello world\n", stream.0.borrow().as_slice());
+        assert_eq!(vec!["hello world"], stream.messages());
+        assert_eq!(vec!["info"], stream.levels());
 
         stream.clear();
 
         ctx.eval_global("main", "console.log(\"bonjour\", \"le\", \"monde\")")?;
-        assert_eq!(b"bonjour le monde\n", stream.0.borrow().as_slice());
+        assert_eq!(vec!["bonjour le monde"], stream.messages());
 
         stream.clear();
 
@@ -30,8 +34,8 @@ mod tests {
             "console.log(2.3, true, { foo: 'bar' }, null, undefined)",
         )?;
         assert_eq!(
-            b"2.3 true [object Object] null undefined\n",
-            stream.0.borrow().as_slice()
+            vec!["2.3 true { foo: 'bar' } null undefined"],
+            stream.messages()
         );
         Ok(())
     }
@@ -41,15 +45,16 @@ mod tests {
         let mut stream = SharedStream::default();
 
         let ctx = Context::default();
-        set_global_console(&ctx, stream.clone(), stream.clone())?;
+        set_global_console(&ctx, stream.clone(), stream.clone(), WORKER_ID)?;
 
         ctx.eval_global("main", "console.error(\"hello world\");")?;
-        assert_eq!(b"hello world\n", stream.0.borrow().as_slice());
+        assert_eq!(vec!["hello world"], stream.messages());
+        assert_eq!(vec!["error"], stream.levels());
 
         stream.clear();
 
         ctx.eval_global("main", "console.error(\"bonjour\", \"le\", \"monde\")")?;
-        assert_eq!(b"bonjour le monde\n", stream.0.borrow().as_slice());
+        assert_eq!(vec!["bonjour le monde"], stream.messages());
 
         stream.clear();
 
@@ -58,9 +63,212 @@ mod tests {
             "console.error(2.3, true, { foo: 'bar' }, null, undefined)",
         )?;
         assert_eq!(
-            b"2.3 true [object Object] null undefined\n",
-            stream.0.borrow().as_slice()
+            vec!["2.3 true { foo: 'bar' } null undefined"],
+            stream.messages()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_console_levels() -> Result<()> {
+        let log_stream = SharedStream::default();
+        let error_stream = SharedStream::default();
+
+        let ctx = Context::default();
+        set_global_console(&ctx, log_stream.clone(), error_stream.clone(), WORKER_ID)?;
+
+        ctx.eval_global(
+            "main",
+            r#"
+                console.debug("debug");
+                console.info("info");
+                console.trace("trace");
+                console.warn("warn");
+                console.error("error");
+            "#,
+        )?;
+
+        assert_eq!(vec!["debug", "info", "trace"], log_stream.levels());
+        assert_eq!(vec!["debug", "info", "Trace: trace"], log_stream.messages());
+        assert_eq!(vec!["warn", "error"], error_stream.levels());
+
+        let record = &log_stream.records()[0];
+        assert_eq!(WORKER_ID, record["workerId"]);
+        assert!(record["timestamp"].is_u64());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_console_format() -> Result<()> {
+        let mut stream = SharedStream::default();
+
+        let ctx = Context::default();
+        set_global_console(&ctx, stream.clone(), stream.clone(), WORKER_ID)?;
+
+        ctx.eval_global("main", r#"console.log("%s is %d years", "Bob", 42.5)"#)?;
+        assert_eq!(vec!["Bob is 42.5 years"], stream.messages());
+
+        stream.clear();
+
+        ctx.eval_global("main", r#"console.log("%i%% %f", "42.5", "1.5", "extra")"#)?;
+        assert_eq!(vec!["42% 1.5 extra"], stream.messages());
+
+        stream.clear();
+
+        ctx.eval_global("main", r#"console.log("%o and %s", { a: [1, 'b'] }, [1])"#)?;
+        assert_eq!(vec!["{ a: [ 1, 'b' ] } and [ 1 ]"], stream.messages());
+
+        stream.clear();
+
+        ctx.eval_global("main", r#"console.log("%c styled %x", "color: red")"#)?;
+        assert_eq!(vec![" styled %x"], stream.messages());
+
+        stream.clear();
+
+        ctx.eval_global("main", r#"console.log("missing %s")"#)?;
+        assert_eq!(vec!["missing %s"], stream.messages());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_console_inspect() -> Result<()> {
+        let mut stream = SharedStream::default();
+
+        let ctx = Context::default();
+        set_global_console(&ctx, stream.clone(), stream.clone(), WORKER_ID)?;
+
+        ctx.eval_global(
+            "main",
+            r#"console.log({ a: { b: { c: { d: 1 } } }, "my-key": "x", fn() {} })"#,
+        )?;
+        assert_eq!(
+            vec!["{ a: { b: { c: [Object] } }, 'my-key': 'x', fn: [Function: fn] }"],
+            stream.messages()
+        );
+
+        stream.clear();
+
+        ctx.eval_global(
+            "main",
+            r#"console.log([], {}, new Map([["a", 1]]), new Set([1]), class Foo {})"#,
+        )?;
+        assert_eq!(
+            vec!["[] {} Map(1) { 'a' => 1 } Set(1) { 1 } [Function: Foo]"],
+            stream.messages()
+        );
+
+        stream.clear();
+
+        ctx.eval_global(
+            "main",
+            r#"
+                class Point { constructor() { this.x = 1; } }
+                var cyclic = { name: "cyclic" };
+                cyclic.self = cyclic;
+                console.log(new Point(), cyclic);
+            "#,
+        )?;
+        assert_eq!(
+            vec!["Point { x: 1 } { name: 'cyclic', self: { name: 'cyclic', self: { name: 'cyclic', self: [Object] } } }"],
+            stream.messages()
         );
+
+        stream.clear();
+
+        ctx.eval_global("main", r#"console.error(new TypeError("boom"))"#)?;
+        assert!(stream.messages()[0].starts_with("TypeError: boom"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_console_table() -> Result<()> {
+        let mut stream = SharedStream::default();
+
+        let ctx = Context::default();
+        set_global_console(&ctx, stream.clone(), stream.clone(), WORKER_ID)?;
+
+        ctx.eval_global(
+            "main",
+            r#"console.table([{ a: 1, b: "Y" }, { a: "Z", b: 2 }])"#,
+        )?;
+        assert_eq!(
+            vec![[
+                "┌─────────┬─────┬─────┐",
+                "│ (index) │ a   │ b   │",
+                "├─────────┼─────┼─────┤",
+                "│ 0       │ 1   │ 'Y' │",
+                "│ 1       │ 'Z' │ 2   │",
+                "└─────────┴─────┴─────┘",
+            ]
+            .join("\n")],
+            stream.messages()
+        );
+
+        stream.clear();
+
+        ctx.eval_global("main", r#"console.table(["apples", "oranges"])"#)?;
+        assert_eq!(
+            vec![[
+                "┌─────────┬───────────┐",
+                "│ (index) │ Values    │",
+                "├─────────┼───────────┤",
+                "│ 0       │ 'apples'  │",
+                "│ 1       │ 'oranges' │",
+                "└─────────┴───────────┘",
+            ]
+            .join("\n")],
+            stream.messages()
+        );
+
+        stream.clear();
+
+        ctx.eval_global("main", r#"console.table("not tabular")"#)?;
+        assert_eq!(vec!["not tabular"], stream.messages());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_console_count_and_time() -> Result<()> {
+        let mut stream = SharedStream::default();
+
+        let ctx = Context::default();
+        set_global_console(&ctx, stream.clone(), stream.clone(), WORKER_ID)?;
+
+        ctx.eval_global(
+            "main",
+            r#"
+                console.count();
+                console.count("label");
+                console.count();
+                console.countReset();
+                console.count();
+            "#,
+        )?;
+        assert_eq!(
+            vec!["default: 1", "label: 1", "default: 2", "default: 1"],
+            stream.messages()
+        );
+
+        stream.clear();
+
+        ctx.eval_global(
+            "main",
+            r#"
+                console.time("timer");
+                console.timeEnd("timer");
+                console.timeEnd("timer");
+            "#,
+        )?;
+
+        let messages = stream.messages();
+        assert!(messages[0].starts_with("timer: ") && messages[0].ends_with("ms"));
+        assert_eq!("Timer 'timer' does not exist", messages[1]);
+        assert_eq!(vec!["info", "warn"], stream.levels());
+
         Ok(())
     }
 
@@ -71,6 +279,30 @@ mod tests {
         fn clear(&mut self) {
             (*self.0).borrow_mut().clear();
         }
+
+        fn records(&self) -> Vec<Value> {
+            let output = self.0.borrow();
+
+            std::str::from_utf8(&output)
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        }
+
+        fn messages(&self) -> Vec<String> {
+            self.records()
+                .iter()
+                .map(|record| record["message"].as_str().unwrap().to_string())
+                .collect()
+        }
+
+        fn levels(&self) -> Vec<String> {
+            self.records()
+                .iter()
+                .map(|record| record["level"].as_str().unwrap().to_string())
+                .collect()
+        }
     }
 
     impl io::Write for SharedStream {
@@ -1,5 +1,12 @@
+mod base64;
 mod blob;
+mod compression;
 mod form_data;
+mod registry;
+mod structured_clone;
+mod text_encoding;
 mod url;
+mod url_pattern;
+mod url_pattern_wpt;
 mod url_search_params;
 mod url_wpt;
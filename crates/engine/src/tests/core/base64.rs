@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::{Ok, Result};
+    use serde_json::json;
+
+    use crate::globals::utils::{decode_base64, encode_base64};
+    use crate::tests::test_utils::context::Context;
+
+    #[test]
+    fn test_base64() {
+        let bytes = (0..=255).collect::<Vec<u8>>();
+
+        assert_eq!(Some(bytes.clone()), decode_base64(&encode_base64(&bytes)));
+        assert_eq!("Zm9vYg==", encode_base64(b"foob"));
+        assert_eq!(Some(b"foob".to_vec()), decode_base64("Zm9vYg"));
+        assert_eq!(Some(b"foo".to_vec()), decode_base64(" Zm9\nv "));
+        assert_eq!(None, decode_base64("Zm9vYg="));
+        assert_eq!(None, decode_base64("Zm9vY"));
+        assert_eq!(None, decode_base64("Zm9v-_"));
+    }
+
+    #[test]
+    fn test_atob_btoa() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            function error(fn) {
+                try {
+                    fn();
+                } catch (error) {
+                    return [error instanceof DOMException, error.name, error.code];
+                }
+            }
+
+            async function handler() {
+                const [scheme, credentials] = "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==".split(" ");
+                const payload = "eyJzdWIiOiIxMjM0NTY3ODkwIiwibmFtZSI6IkpvaG4gRG9lIn0";
+
+                return {
+                    binary: btoa(String.fromCharCode(0, 127, 128, 255)),
+                    credentials: atob(credentials),
+                    invalid: [error(() => atob("QQ=")), error(() => atob("é"))],
+                    jwt: JSON.parse(atob(payload)).name,
+                    latin1: error(() => btoa("€")),
+                    roundTrip: atob(btoa("café")),
+                    scheme,
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "binary": "AH+A/w==",
+                "credentials": "Aladdin:open sesame",
+                "invalid": [[true, "InvalidCharacterError", 5], [true, "InvalidCharacterError", 5]],
+                "jwt": "John Doe",
+                "latin1": [true, "InvalidCharacterError", 5],
+                "roundTrip": "café",
+                "scheme": "Basic",
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+}
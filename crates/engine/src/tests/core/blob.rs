@@ -799,4 +799,109 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_blob_slice_shares_bytes() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const blob = new Blob(["ab", new Uint8Array([99, 100]), "ef"], { type: "Text/Plain" });
+                const slice = blob.slice(1, -1);
+
+                return {
+                    concatenated: await new Blob([slice.slice(2), "gh"]).text(),
+                    slice: await slice.text(),
+                    sliceOfSlice: await slice.slice(1, 3).text(),
+                    type: blob.type,
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({"concatenated": "degh", "slice": "bcde", "sliceOfSlice": "cd", "type": "text/plain"})
+                .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_blob_stream_chunks() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const blob = new Blob([new Uint8Array(150000).fill(1)]).slice(10);
+                const reader = blob.stream().getReader();
+                const sizes = [];
+
+                for (;;) {
+                    const { done, value } = await reader.read();
+
+                    if (done) break;
+
+                    sizes.push(value instanceof Uint8Array ? value.byteLength : -1);
+                }
+
+                return sizes;
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!([65536, 65536, 18918]).to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    // @see: https://github.com/web-platform-tests/wpt/blob/3ccab5e25f082e8d055aef70b692a98786b9a9f2/FileAPI/file/File-constructor.any.js
+    #[test]
+    fn test_file_constructor() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const file = new File(["bits"], "dummy", { type: "text/plain", lastModified: 42 });
+                const fallback = new File([], "");
+
+                return {
+                    fallback: [fallback.name, typeof fallback.lastModified],
+                    file: [file instanceof Blob, file.name, file.size, file.type, file.lastModified, await file.text()],
+                    json: JSON.stringify(file),
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "fallback": ["", "number"],
+                "file": [true, "dummy", 4, "text/plain", 42, "bits"],
+                "json": r#"{"size":4,"type":"text/plain"}"#,
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        let re_arguments = Regex::new(
+            r"^Uncaught TypeError: Failed to construct 'File': 2 arguments required, but only 1 present",
+        )
+        .unwrap();
+
+        assert!(re_arguments.is_match(
+            &ctx.eval(r#"var file = new File(["bits"]);"#)
+                .unwrap_err()
+                .to_string()
+        ));
+
+        Ok(())
+    }
 }
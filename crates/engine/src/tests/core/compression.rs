@@ -0,0 +1,273 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::{Ok, Result};
+    use quickjs_wasm_rs::JSError;
+    use regex::Regex;
+    use serde_bytes::ByteBuf;
+    use serde_json::json;
+
+    use crate::fetch::{fetch::decode_response, http::Response};
+    use crate::globals::compression::{decompress, Compressor, Format};
+    use crate::tests::test_utils::context::Context;
+
+    // The limit of the decoded bodies, larger than the ones of the tests
+    const LIMIT: usize = 1024;
+
+    fn compress(format: Format, bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut compressor = Compressor::new(format)?;
+        let mut output = compressor.write(bytes)?;
+
+        output.extend(compressor.finish()?);
+
+        Ok(output)
+    }
+
+    #[test]
+    fn test_compression_stream_round_trip() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function roundTrip(format) {
+                const text = "Hello World! ".repeat(100);
+                const compressed = await new Response(
+                    new Blob([text.slice(0, 500), new TextEncoder().encode(text.slice(500))])
+                        .stream()
+                        .pipeThrough(new CompressionStream(format)),
+                ).arrayBuffer();
+                const decompressed = await new Response(
+                    new Blob([compressed]).stream().pipeThrough(new DecompressionStream(format)),
+                ).text();
+
+                return [compressed.byteLength < text.length, decompressed === text];
+            }
+
+            async function handler() {
+                return {
+                    deflate: await roundTrip("deflate"),
+                    deflateRaw: await roundTrip("deflate-raw"),
+                    gzip: await roundTrip("gzip"),
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "deflate": [true, true],
+                "deflateRaw": [true, true],
+                "gzip": [true, true],
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decompression_stream_chunks() -> Result<()> {
+        let mut ctx = Context::new();
+        let gzip = compress(Format::Gzip, "Hello World!".as_bytes())?;
+
+        ctx.eval(&format!(
+            r#"
+            async function handler() {{
+                const bytes = new Uint8Array({gzip:?});
+                const stream = new DecompressionStream("gzip");
+                const writer = stream.writable.getWriter();
+
+                // One byte at a time
+                for (let i = 0; i < bytes.length; i++) {{
+                    writer.write(bytes.subarray(i, i + 1));
+                }}
+                writer.close();
+
+                return {{
+                    text: await new Response(stream.readable).text(),
+                }};
+            }}
+            "#
+        ))?;
+
+        assert_eq!(
+            json!({ "text": "Hello World!" }).to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compression_stream_errors() -> Result<()> {
+        let mut ctx = Context::new();
+
+        let re_format =
+            Regex::new(r"^Uncaught TypeError: Unsupported compression format: 'br'").unwrap();
+
+        assert!(re_format.is_match(
+            &ctx.eval(r#"new CompressionStream("br");"#)
+                .unwrap_err()
+                .to_string()
+        ));
+
+        ctx.eval(
+            r#"
+            async function decompress(bytes) {
+                try {
+                    await new Response(
+                        new Blob([bytes]).stream().pipeThrough(new DecompressionStream("deflate")),
+                    ).text();
+                } catch (error) {
+                    return `${error.name}: ${error.message}`;
+                }
+            }
+
+            async function handler() {
+                const compressed = new Uint8Array(
+                    await new Response(
+                        new Blob(["Hello World!"]).stream().pipeThrough(new CompressionStream("deflate")),
+                    ).arrayBuffer(),
+                );
+
+                return {
+                    invalid: await decompress(new Uint8Array([1, 2, 3, 4])),
+                    trailing: await decompress(new Uint8Array([...compressed, 0])),
+                    truncated: await decompress(compressed.subarray(0, compressed.length - 2)),
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "invalid": "TypeError: The compressed data is not valid",
+                "trailing": "TypeError: The compressed data is not valid",
+                "truncated": "TypeError: The compressed data is not valid",
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_decode_response() -> Result<()> {
+        let body = "Hello World! ".repeat(10);
+        let response = |headers: serde_json::Value, body: Vec<u8>| {
+            serde_json::to_string(&Response {
+                status: 200,
+                body: Some(ByteBuf::from(body)),
+                headers: Some(headers.to_string()),
+            })
+        };
+
+        let decoded: Response = serde_json::from_str(&decode_response(
+            response(
+                json!({ "Content-Encoding": "gzip", "content-length": "42", "content-type": "text/plain" }),
+                compress(Format::Gzip, body.as_bytes())?,
+            )?,
+            LIMIT,
+        )?)?;
+
+        assert_eq!(body.as_bytes(), decoded.body.unwrap().as_slice());
+        assert_eq!(
+            json!({ "content-type": "text/plain" }),
+            serde_json::from_str::<serde_json::Value>(&decoded.headers.unwrap())?
+        );
+
+        let decoded: Response = serde_json::from_str(&decode_response(
+            response(
+                json!({ "content-encoding": "deflate" }),
+                compress(Format::Deflate, body.as_bytes())?,
+            )?,
+            LIMIT,
+        )?)?;
+
+        assert_eq!(body.as_bytes(), decoded.body.unwrap().as_slice());
+
+        // The unknown codings are left as is
+        let identity = response(json!({ "content-encoding": "zstd" }), body.clone().into())?;
+
+        assert_eq!(identity, decode_response(identity.clone(), LIMIT)?);
+        assert!(decode_response(
+            response(json!({ "content-encoding": "gzip" }), body.into())?,
+            LIMIT
+        )
+        .is_err());
+        assert_eq!(
+            b"Hello".to_vec(),
+            decompress(
+                Format::DeflateRaw,
+                &compress(Format::DeflateRaw, b"Hello")?,
+                LIMIT
+            )?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_decode_response_limit() -> Result<()> {
+        let is_too_large = |error: anyhow::Error, limit: usize| {
+            matches!(
+                error.downcast_ref::<JSError>(),
+                Some(JSError::Type(message)) if *message == format!("The decompressed data exceeds {limit} bytes")
+            )
+        };
+        let body = "Hello World! ".repeat(10);
+        let response = |body: Vec<u8>| {
+            serde_json::to_string(&Response {
+                status: 200,
+                body: Some(ByteBuf::from(body)),
+                headers: Some(json!({ "content-encoding": "gzip" }).to_string()),
+            })
+        };
+        let gzip = compress(Format::Gzip, body.as_bytes())?;
+
+        // Up to the limit
+        let decoded: Response =
+            serde_json::from_str(&decode_response(response(gzip.clone())?, body.len())?)?;
+
+        assert_eq!(body.as_bytes(), decoded.body.unwrap().as_slice());
+        assert!(is_too_large(
+            decode_response(response(gzip)?, body.len() - 1).unwrap_err(),
+            body.len() - 1
+        ));
+
+        // A body much larger than its compressed size
+        let zeros = compress(Format::Gzip, &vec![0; 16 * 1024 * 1024])?;
+
+        assert!(zeros.len() < 64 * 1024);
+        assert!(is_too_large(
+            decode_response(response(zeros)?, 1024 * 1024).unwrap_err(),
+            1024 * 1024
+        ));
+
+        Ok(())
+    }
+    #[test]
+    fn test_decompress_limit_brotli() -> Result<()> {
+        // 16 MiB of zeros compressed with brotli
+        let zeros = [
+            203, 255, 255, 63, 248, 39, 0, 226, 177, 64, 32, 247, 254, 47, 255, 255, 255, 240, 79,
+            0, 196, 97, 17, 128, 238, 253, 31,
+        ];
+
+        assert_eq!(
+            16 * 1024 * 1024,
+            decompress(Format::Brotli, &zeros, 16 * 1024 * 1024)?.len()
+        );
+
+        // The decoder stops as soon as its output passes the limit
+        let error = decompress(Format::Brotli, &zeros, 1024).unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<JSError>(),
+            Some(JSError::Type(message)) if message == "The decompressed data exceeds 1024 bytes"
+        ));
+
+        Ok(())
+    }
+}
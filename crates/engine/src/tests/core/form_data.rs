@@ -5,7 +5,10 @@ mod tests {
     use regex::Regex;
     use serde_json::json;
 
-    use crate::tests::test_utils::context::Context;
+    use crate::{
+        globals::multipart::{encode, MultipartParser, Part},
+        tests::test_utils::context::Context,
+    };
 
     // @see: https://github.com/web-platform-tests/wpt/blob/e45a9f9e65d039c76817ee2a6a1ef02c9311a1cb/xhr/formdata/append.any.js
     #[test]
@@ -470,4 +473,165 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_form_data_entries_with_filename() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            var formData = new FormData();
+            formData.append('key', 'value1');
+            formData.append('key', new Blob(['value2']), 'value2.txt');
+            formData.set('other', 'value3');
+            var formDataEntries = JSON.stringify([...formData].map(([name, value]) => [name, typeof value]));
+        "#,
+        )?;
+
+        assert_eq!(
+            r#"[["key","string"],["key","object"],["other","string"]]"#,
+            ctx.global.get_property("formDataEntries")?.as_str()?
+        );
+
+        let re_blob =
+            Regex::new(r"^Uncaught TypeError: Failed to execute 'append' on 'FormData': parameter 2 is not of type 'Blob'").unwrap();
+
+        assert!(re_blob.is_match(
+            &ctx.eval(r#"new FormData().append('key', 'value', 'value.txt');"#)
+                .unwrap_err()
+                .to_string()
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_multipart_encode() {
+        let parts = [
+            Part {
+                name: "text".to_string(),
+                data: b"Plain Text".to_vec(),
+                ..Default::default()
+            },
+            Part {
+                name: "file\"name".to_string(),
+                filename: Some("text.txt".to_string()),
+                content_type: Some("text/plain".to_string()),
+                data: b"File\r\nText".to_vec(),
+            },
+            Part {
+                name: "blob".to_string(),
+                filename: Some("blob".to_string()),
+                content_type: Some("".to_string()),
+                data: vec![0, 1],
+            },
+        ];
+
+        assert_eq!(
+            [
+                "--boundary\r\n",
+                "Content-Disposition: form-data; name=\"text\"\r\n\r\n",
+                "Plain Text\r\n",
+                "--boundary\r\n",
+                "Content-Disposition: form-data; name=\"file%22name\"; filename=\"text.txt\"\r\n",
+                "Content-Type: text/plain\r\n\r\n",
+                "File\r\nText\r\n",
+                "--boundary\r\n",
+                "Content-Disposition: form-data; name=\"blob\"; filename=\"blob\"\r\n",
+                "Content-Type: application/octet-stream\r\n\r\n",
+                "\u{0}\u{1}\r\n",
+                "--boundary--\r\n",
+            ]
+            .concat()
+            .as_bytes(),
+            encode(&parts, "boundary")
+        );
+    }
+
+    #[test]
+    fn test_multipart_parser_chunks() -> Result<()> {
+        let parts = vec![
+            Part {
+                name: "text".to_string(),
+                data: b"Plain Text\r\n--not-the-boundary".to_vec(),
+                ..Default::default()
+            },
+            Part {
+                name: "file".to_string(),
+                filename: Some("text.txt".to_string()),
+                content_type: Some("text/plain".to_string()),
+                data: b"File Text".to_vec(),
+            },
+        ];
+        let body = encode(&parts, "boundary");
+
+        // Every split of the body in two chunks gives the same parts
+        for index in 0..=body.len() {
+            let mut parser = MultipartParser::new("boundary");
+            let mut parsed = parser.push(&body[..index])?;
+
+            parsed.extend(parser.push(&body[index..])?);
+            parsed.extend(parser.finish()?);
+
+            assert_eq!(parts, parsed);
+        }
+
+        // The body is truncated
+        let mut parser = MultipartParser::new("boundary");
+
+        parser.push(&body[..body.len() - 20])?;
+
+        assert!(parser.finish().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_form_data_multipart_round_trip() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const formData = new FormData();
+
+                formData.append('text', 'Plain Text');
+                formData.append('file', new Blob(['File Text'], { type: 'text/plain' }), 'text.txt');
+
+                const response = new Response(formData);
+                const contentType = response.headers.get('content-type');
+                const body = new Uint8Array(await response.arrayBuffer());
+
+                // The body is received one byte at a time
+                const stream = new ReadableStream({
+                    start(controller) {
+                        for (const byte of body) {
+                            controller.enqueue(new Uint8Array([byte]));
+                        }
+
+                        controller.close();
+                    },
+                });
+                const parsed = await new Response(stream, {
+                    headers: { 'content-type': contentType },
+                }).formData();
+                const file = parsed.get('file');
+
+                return {
+                    contentType: /^multipart\/form-data; boundary=----FormDataBoundary[0-9a-f]+$/.test(contentType),
+                    file: [file.type, await file.text()],
+                    text: parsed.get('text'),
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({"contentType": true, "file": ["text/plain", "File Text"], "text": "Plain Text"})
+                .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
 }
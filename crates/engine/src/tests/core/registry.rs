@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use quickjs_wasm_rs::Context;
+
+    use crate::globals::registry::Registry;
+
+    #[test]
+    fn test_registry() -> Result<()> {
+        static VALUES: Registry<String> = Registry::new("value");
+
+        let a = VALUES.insert("a".to_string());
+        let b = VALUES.insert("b".to_string());
+
+        VALUES.get(a, |value| {
+            value.push('!');
+
+            Ok(())
+        })?;
+
+        assert_eq!("a!", VALUES.take(a)?);
+        assert_eq!("Unknown value: 0", VALUES.take(a).unwrap_err().to_string());
+        assert!(VALUES.get(a, |_| Ok(())).is_err());
+
+        // The slot of the removed value is reused
+        assert_eq!(a, VALUES.insert("c".to_string()));
+
+        VALUES.remove(b);
+
+        assert_eq!(1, VALUES.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_registry_handle() -> Result<()> {
+        static VALUES: Registry<String> = Registry::new("value");
+
+        let context = Context::default();
+
+        context.global_object()?.set_property(
+            "create",
+            context.wrap_callback(|context, _this, _args| {
+                VALUES.insert_with_handle(context, "value".to_string())
+            })?,
+        )?;
+        context.eval_global(
+            "registry.js",
+            r#"
+                var [id, handle] = create();
+                var kept = { id, handle };
+
+                id = handle = undefined;
+
+                // Collected as soon as it's created
+                create();
+                null;
+            "#,
+        )?;
+
+        assert_eq!(1, VALUES.len());
+
+        context.eval_global("registry.js", "kept = null;")?;
+
+        assert!(VALUES.is_empty());
+
+        // The collected values are removed by the next insert, which reuses their slot
+        assert_eq!(0, VALUES.insert("next".to_string()));
+
+        Ok(())
+    }
+}
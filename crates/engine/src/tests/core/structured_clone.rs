@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::{Ok, Result};
+    use regex::Regex;
+    use serde_json::json;
+
+    use crate::tests::test_utils::context::Context;
+
+    #[test]
+    fn test_structured_clone() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const buffer = new Uint8Array([1, 2, 3, 4]).buffer;
+                const original = {
+                    date: new Date(0),
+                    map: new Map([["key", { nested: true }]]),
+                    regexp: /^a+$/gi,
+                    set: new Set([1, 2]),
+                    view: new Uint16Array(buffer, 2, 1),
+                    bytes: new Uint8Array(buffer),
+                };
+                original.self = original;
+                original.map.set("parent", original);
+
+                const clone = structuredClone(original);
+                new Uint8Array(buffer).fill(0);
+
+                return {
+                    bytes: Array.from(clone.bytes),
+                    cycles: [clone.self === clone, clone.map.get("parent") === clone, clone !== original],
+                    date: clone.date instanceof Date && clone.date.toISOString(),
+                    map: [clone.map.get("key"), clone.map.get("key") !== original.map.get("key")],
+                    regexp: [clone.regexp instanceof RegExp, clone.regexp.source, clone.regexp.flags],
+                    set: [...clone.set],
+                    sharedBuffer: clone.view.buffer === clone.bytes.buffer && clone.bytes.buffer !== buffer,
+                    view: [clone.view instanceof Uint16Array, clone.view.byteOffset, clone.view.length],
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "bytes": [1, 2, 3, 4],
+                "cycles": [true, true, true],
+                "date": "1970-01-01T00:00:00.000Z",
+                "map": [{ "nested": true }, true],
+                "regexp": [true, "^a+$", "gi"],
+                "set": [1, 2],
+                "sharedBuffer": true,
+                "view": [true, 2, 1],
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_structured_clone_blob() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const blob = structuredClone(new Blob(["Hello"], { type: "text/plain" }));
+                const file = structuredClone(new File(["World"], "world.txt", { lastModified: 42 }));
+
+                return {
+                    blob: [blob instanceof Blob, blob.type, await blob.text()],
+                    file: [file instanceof File, file.name, file.lastModified, await file.text()],
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "blob": [true, "text/plain", "Hello"],
+                "file": [true, "world.txt", 42, "World"],
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_structured_clone_errors() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            function error(value) {
+                try {
+                    structuredClone(value);
+                } catch (error) {
+                    return [error instanceof DOMException, error.name, error.code];
+                }
+            }
+
+            class Point {
+                x = 1;
+            }
+
+            async function handler() {
+                const plain = Object.assign(Object.create(null), { x: 1 });
+
+                return {
+                    class: error(new Point()),
+                    function: error({ callback: () => {} }),
+                    headers: error(new Headers({ "content-type": "text/plain" })),
+                    nullPrototype: structuredClone(plain),
+                    promise: error(Promise.resolve()),
+                    request: error(new Request("https://example.com")),
+                    symbol: error(Symbol("id")),
+                    url: error({ url: new URL("https://example.com") }),
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "class": [true, "DataCloneError", 25],
+                "function": [true, "DataCloneError", 25],
+                "headers": [true, "DataCloneError", 25],
+                "nullPrototype": { "x": 1 },
+                "promise": [true, "DataCloneError", 25],
+                "request": [true, "DataCloneError", 25],
+                "symbol": [true, "DataCloneError", 25],
+                "url": [true, "DataCloneError", 25],
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        let re_function = Regex::new(
+            r"^Uncaught DataCloneError: Failed to execute 'structuredClone': function handler\(\) \{\} could not be cloned",
+        )
+        .unwrap();
+
+        assert!(re_function.is_match(
+            &ctx.eval("structuredClone(function handler() {});")
+                .unwrap_err()
+                .to_string()
+        ));
+
+        let re_headers = Regex::new(
+            r"^Uncaught DataCloneError: Failed to execute 'structuredClone': #<Headers> could not be cloned",
+        )
+        .unwrap();
+
+        assert!(re_headers.is_match(
+            &ctx.eval("structuredClone(new Headers());")
+                .unwrap_err()
+                .to_string()
+        ));
+
+        Ok(())
+    }
+}
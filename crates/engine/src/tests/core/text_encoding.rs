@@ -0,0 +1,172 @@
+// @see: https://github.com/web-platform-tests/wpt/tree/3ccab5e25f082e8d055aef70b692a98786b9a9f2/encoding
+#[cfg(test)]
+mod tests {
+    use anyhow::{Ok, Result};
+    use regex::Regex;
+    use serde_json::json;
+
+    use crate::tests::test_utils::context::Context;
+
+    #[test]
+    fn test_text_decoder_encodings() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const latin1 = new TextDecoder("latin1");
+                const shiftJis = new TextDecoder("Shift_JIS");
+
+                return {
+                    latin1: [latin1.encoding, latin1.decode(new Uint8Array([0x63, 0x61, 0x66, 0xe9, 0x80]))],
+                    shiftJis: [shiftJis.encoding, shiftJis.decode(new Uint8Array([0x82, 0xb1, 0x82, 0xf1, 0x82, 0xc9, 0x82, 0xbf, 0x82, 0xcd]))],
+                    utf16: new TextDecoder("utf-16le").decode(new Uint8Array([0xff, 0xfe, 0x61, 0x00])),
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "latin1": ["windows-1252", "café€"],
+                "shiftJis": ["shift_jis", "こんにちは"],
+                "utf16": "a",
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        let re_label = Regex::new(
+            r"^Uncaught RangeError: Failed to construct 'TextDecoder': The encoding label provided \('replacement'\) is invalid",
+        )
+        .unwrap();
+
+        assert!(re_label.is_match(
+            &ctx.eval(r#"new TextDecoder("replacement");"#)
+                .unwrap_err()
+                .to_string()
+        ));
+
+        Ok(())
+    }
+
+    // @see: https://github.com/web-platform-tests/wpt/blob/3ccab5e25f082e8d055aef70b692a98786b9a9f2/encoding/textdecoder-streaming.any.js
+    #[test]
+    fn test_text_decoder_stream_option() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const bytes = new TextEncoder().encode("a€😀");
+                const decoder = new TextDecoder();
+                let text = "";
+
+                for (const byte of bytes) {
+                    text += decoder.decode(new Uint8Array([byte]), { stream: true });
+                }
+
+                text += decoder.decode();
+
+                const truncated = decoder.decode(bytes.subarray(0, 2), { stream: true }) + decoder.decode();
+                const fatal = new TextDecoder("utf-8", { fatal: true });
+                let error;
+
+                fatal.decode(bytes.subarray(0, 2), { stream: true });
+
+                try {
+                    fatal.decode();
+                } catch (e) {
+                    error = e.name;
+                }
+
+                return { error, reused: fatal.decode(bytes), text, truncated };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({"error": "TypeError", "reused": "a€😀", "text": "a€😀", "truncated": "a\u{fffd}"})
+                .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    // @see: https://github.com/web-platform-tests/wpt/blob/3ccab5e25f082e8d055aef70b692a98786b9a9f2/encoding/encodeInto.any.js
+    #[test]
+    fn test_text_encoder_encode_into() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const encoder = new TextEncoder();
+                const buffer = new Uint8Array(8);
+                const result = encoder.encodeInto("a€😀", buffer.subarray(2, 7));
+
+                return {
+                    buffer: Array.from(buffer),
+                    empty: encoder.encodeInto("a", new Uint8Array(0)),
+                    result,
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "buffer": [0, 0, 97, 226, 130, 172, 0, 0],
+                "empty": {"read": 0, "written": 0},
+                "result": {"read": 2, "written": 4},
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_text_encoding_streams() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const source = new ReadableStream({
+                    start(controller) {
+                        // The emoji is split between its surrogates
+                        controller.enqueue("a\ud83d");
+                        controller.enqueue("\ude00€");
+                        controller.close();
+                    },
+                });
+                const reader = source
+                    .pipeThrough(new TextEncoderStream())
+                    .pipeThrough(new TextDecoderStream())
+                    .getReader();
+                let text = "";
+
+                for (;;) {
+                    const { done, value } = await reader.read();
+
+                    if (done) break;
+
+                    text += value;
+                }
+
+                return { encoding: new TextDecoderStream("latin1").encoding, text };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({"encoding": "windows-1252", "text": "a😀€"}).to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+}
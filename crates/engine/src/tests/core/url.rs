@@ -2,6 +2,8 @@
 #[cfg(test)]
 mod tests {
     use anyhow::{Ok, Result};
+    use regex::Regex;
+    use serde_json::json;
 
     use crate::tests::test_utils::context::Context;
 
@@ -50,7 +52,7 @@ mod tests {
         );
         assert_eq!("8080", ctx.global.get_property("port_init")?.as_str()?,);
 
-        assert_eq!("is_a_hash", ctx.global.get_property("hash")?.as_str()?,);
+        assert_eq!("#is_a_hash", ctx.global.get_property("hash")?.as_str()?,);
         assert_eq!("test.com", ctx.global.get_property("hostname")?.as_str()?,);
         assert_eq!("2023", ctx.global.get_property("port")?.as_str()?,);
         assert_eq!("test.com:2023", ctx.global.get_property("host")?.as_str()?,);
@@ -83,4 +85,80 @@ mod tests {
 
         Ok(())
     }
+
+    // @see: https://github.com/web-platform-tests/wpt/blob/7b0ebaccc62b566a1965396e5be7bb2bc06f841f/url/url-statics-canparse.any.js
+    #[test]
+    fn test_url_can_parse_and_parse() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                return {
+                    canParse: [
+                        URL.canParse("https://example.com/"),
+                        URL.canParse("/path", "https://example.com/"),
+                        URL.canParse("/path"),
+                        URL.canParse("https://example.com/", "invalid"),
+                    ],
+                    empty: new URL("", "https://example.com/path#hash").href,
+                    parse: [
+                        URL.parse("/path?query", "https://example.com/")?.href,
+                        URL.parse("/path"),
+                    ],
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "canParse": [true, true, false, false],
+                "empty": "https://example.com/path",
+                "parse": ["https://example.com/path?query", null],
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_url_errors() -> Result<()> {
+        let mut ctx = Context::new();
+
+        let re_invalid = Regex::new(r"^Uncaught TypeError: Invalid URL: /path").unwrap();
+
+        assert!(re_invalid.is_match(&ctx.eval(r#"new URL("/path");"#).unwrap_err().to_string()));
+
+        let re_invalid_base =
+            Regex::new(r"^Uncaught TypeError: Invalid base URL: invalid").unwrap();
+
+        assert!(re_invalid_base.is_match(
+            &ctx.eval(r#"new URL("/path", "invalid");"#)
+                .unwrap_err()
+                .to_string()
+        ));
+
+        // Only the href setter throws, the other ones ignore the invalid values
+        ctx.eval(
+            r#"
+            var url = new URL("https://example.com/");
+
+            url.port = "invalid";
+            url.host = "";
+
+            var href = url.href;
+            "#,
+        )?;
+
+        assert_eq!(
+            "https://example.com/",
+            ctx.global.get_property("href")?.as_str()?
+        );
+        assert!(re_invalid.is_match(&ctx.eval(r#"url.href = "/path";"#).unwrap_err().to_string()));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::{Ok, Result};
+    use regex::Regex;
+    use serde_json::json;
+
+    use crate::tests::test_utils::context::Context;
+
+    #[test]
+    fn test_url_pattern_routing() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const pattern = new URLPattern("/books/:id(\\d+)/*", "https://example.com");
+                const result = pattern.exec("https://example.com/books/12/chapters/3");
+
+                return {
+                    groups: result.pathname.groups,
+                    inputs: result.inputs,
+                    pathname: pattern.pathname,
+                    search: pattern.search,
+                    test: [
+                        pattern.test("https://example.com/books/12/chapters"),
+                        pattern.test("https://example.com/books/twelve/chapters"),
+                        pattern.test("/books/12/", "https://example.com"),
+                        pattern.test("https://example.org/books/12/"),
+                    ],
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "groups": { "0": "chapters/3", "id": "12" },
+                "inputs": ["https://example.com/books/12/chapters/3"],
+                "pathname": "/books/:id(\\d+)/*",
+                "search": "",
+                "test": [true, false, true, false],
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_url_pattern_init() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const pattern = new URLPattern({ hostname: "{:subdomain.}?example.com", pathname: "/api/*" });
+                const result = pattern.exec({ hostname: "eu.example.com", pathname: "/api/users", protocol: "https" });
+
+                return {
+                    hostname: pattern.hostname,
+                    inputs: result.inputs,
+                    null: pattern.exec("not a URL"),
+                    path: result.pathname.groups[0],
+                    protocol: pattern.protocol,
+                    subdomain: result.hostname.groups.subdomain,
+                    test: [
+                        pattern.test("https://example.com/api/"),
+                        pattern.test("http://example.com/users"),
+                    ],
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "hostname": "{:subdomain.}?example.com",
+                "inputs": [{ "hostname": "eu.example.com", "pathname": "/api/users", "protocol": "https" }],
+                "null": null,
+                "path": "users",
+                "protocol": "*",
+                "subdomain": "eu",
+                "test": [true, false],
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_url_pattern_group_order() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const pattern = new URLPattern({ hostname: ":z.:y.com", pathname: "/:b/:a" });
+                const result = pattern.exec("https://eu.api.com/books/12");
+
+                return {
+                    hostname: Object.keys(result.hostname.groups),
+                    pathname: Object.entries(result.pathname.groups),
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "hostname": ["z", "y"],
+                "pathname": [["b", "books"], ["a", "12"]],
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_url_pattern_errors() -> Result<()> {
+        let mut ctx = Context::new();
+
+        let re_base_required = Regex::new(
+            r"^Uncaught TypeError: Failed to construct 'URLPattern': a relative input without a base URL is not valid",
+        )
+        .unwrap();
+
+        assert!(re_base_required.is_match(
+            &ctx.eval(r#"new URLPattern("/books/:id");"#)
+                .unwrap_err()
+                .to_string()
+        ));
+
+        let re_invalid =
+            Regex::new(r"^Uncaught TypeError: Failed to construct 'URLPattern':").unwrap();
+
+        assert!(re_invalid.is_match(
+            &ctx.eval(r#"new URLPattern({ pathname: "/:id(" });"#)
+                .unwrap_err()
+                .to_string()
+        ));
+
+        let re_base_with_init = Regex::new(r"^Uncaught TypeError: specifying both an init object, and a seperate base URL is not valid").unwrap();
+
+        assert!(re_base_with_init.is_match(
+            &ctx.eval(r#"new URLPattern({ pathname: "/*" }).test({ pathname: "/" }, "https://example.com");"#)
+                .unwrap_err()
+                .to_string()
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+// @see: https://github.com/web-platform-tests/wpt/tree/7b0ebaccc62b566a1965396e5be7bb2bc06f841f/urlpattern
+#[cfg(test)]
+mod tests {
+    use anyhow::{Ok, Result};
+    use serde_json::Value;
+
+    use crate::tests::test_utils::context::Context;
+
+    // NOTE: the file has been edited, the entries with a `skip` reason aren't supported yet
+    static URL_PATTERN_TEST_DATA: &str = include_str!("url-pattern-test-data.json");
+
+    // @see: https://github.com/web-platform-tests/wpt/blob/7b0ebaccc62b566a1965396e5be7bb2bc06f841f/urlpattern/resources/urlpattern-test.js
+    #[test]
+    fn test_url_pattern_wpt() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            var components = ["protocol", "username", "password", "hostname", "port", "pathname", "search", "hash"];
+
+            function throwsTypeError(fn) {
+                try {
+                    fn();
+                } catch (e) {
+                    return e instanceof TypeError;
+                }
+
+                return false;
+            }
+
+            function sameGroups(actual, expected) {
+                return Object.keys(actual).length === Object.keys(expected).length &&
+                    Object.keys(expected).every((name) => actual[name] === expected[name]);
+            }
+
+            // Returns the errors of the entry
+            function run(entry) {
+                var errors = [];
+                var inputs = entry.inputs || [];
+                var exactlyEmpty = entry.exactly_empty_components || [];
+
+                if (entry.expected_obj === "error") {
+                    if (!throwsTypeError(() => new URLPattern(...entry.pattern))) {
+                        errors.push("the constructor should throw");
+                    }
+
+                    return errors;
+                }
+
+                var pattern = new URLPattern(...entry.pattern);
+                var expectedObj = entry.expected_obj || {};
+                var init = typeof entry.pattern[0] === "object" ? entry.pattern[0] : {};
+                var baseURL = typeof entry.pattern[1] === "string" ? entry.pattern[1] : init.baseURL;
+
+                for (var component of components) {
+                    var expected = expectedObj[component];
+
+                    if (expected === undefined) {
+                        if (exactlyEmpty.includes(component)) {
+                            expected = "";
+                        } else if (init[component] !== undefined) {
+                            expected = init[component];
+                        } else if (baseURL !== undefined) {
+                            expected = new URL(baseURL)[component];
+
+                            if (component === "protocol") {
+                                expected = expected.slice(0, -1);
+                            } else if (component === "search" || component === "hash") {
+                                expected = expected.slice(1);
+                            }
+                        } else {
+                            expected = "*";
+                        }
+                    }
+
+                    if (pattern[component] !== expected) {
+                        errors.push(`${component}: ${pattern[component]} !== ${expected}`);
+                    }
+                }
+
+                if (entry.expected_match === "error") {
+                    if (!throwsTypeError(() => pattern.test(...inputs)) || !throwsTypeError(() => pattern.exec(...inputs))) {
+                        errors.push("test and exec should throw");
+                    }
+
+                    return errors;
+                }
+
+                var test = pattern.test(...inputs);
+                var result = pattern.exec(...inputs);
+
+                if (test !== !!entry.expected_match) {
+                    errors.push(`test: ${test}`);
+                }
+
+                if (!entry.expected_match) {
+                    if (result !== null) {
+                        errors.push("exec should return null");
+                    }
+
+                    return errors;
+                }
+
+                if (result === null) {
+                    errors.push("exec should return a result");
+
+                    return errors;
+                }
+
+                var expectedInputs = entry.expected_match.inputs || inputs;
+
+                if (JSON.stringify(result.inputs) !== JSON.stringify(expectedInputs)) {
+                    errors.push(`inputs: ${JSON.stringify(result.inputs)}`);
+                }
+
+                for (var component of components) {
+                    var expected = entry.expected_match[component] || {
+                        input: "",
+                        groups: exactlyEmpty.includes(component) ? {} : { "0": "" },
+                    };
+                    var actual = result[component];
+
+                    if (actual.input !== expected.input || !sameGroups(actual.groups, expected.groups)) {
+                        errors.push(`${component}: ${JSON.stringify(actual)} !== ${JSON.stringify(expected)}`);
+                    }
+                }
+
+                return errors;
+            }
+            "#,
+        )?;
+
+        let tests_data: Vec<Value> = serde_json::from_str(URL_PATTERN_TEST_DATA)?;
+
+        for entry in tests_data.iter() {
+            if entry.get("skip").is_some() {
+                continue;
+            }
+
+            ctx.eval(&format!(
+                r#"
+                var entry = JSON.parse({:?});
+                var errors = run(entry);
+                var has_error = errors.length > 0;
+
+                if (has_error) {{
+                    ___logger("pattern", JSON.stringify(entry.pattern), "inputs", JSON.stringify(entry.inputs));
+                    errors.forEach((error) => ___logger(error));
+                }}
+            "#,
+                entry.to_string()
+            ))?;
+
+            assert_eq!("false", ctx.global.get_property("has_error")?.as_str()?);
+        }
+
+        Ok(())
+    }
+}
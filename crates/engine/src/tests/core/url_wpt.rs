@@ -1,4 +1,4 @@
-// @see: https://github.com/web-platform-tests/wpt/tree/7b0ebaccc62b566a1965396e5be7bb2bc06f841f/url
+// @see: https://github.com/web-platform-tests/wpt/tree/2a64dae4641fbd61bd4257df460e188f425b492e/url
 #[cfg(test)]
 mod tests {
     use anyhow::{Ok, Result};
@@ -7,11 +7,29 @@ mod tests {
 
     use crate::tests::test_utils::context::Context;
 
+    // @see: https://github.com/web-platform-tests/wpt/blob/2a64dae4641fbd61bd4257df460e188f425b492e/url/resources/urltestdata.json
     static URL_TEST_DATA: &str = include_str!("url-test-data.json");
 
+    // The inputs the url crate, which parses the URLs, skips in its own run of these tests, as its
+    // IDNA processing doesn't match the one of the spec for them
+    // @see: https://github.com/servo/rust-url/blob/v2.3.1/url/tests/data.rs
+    const IDNA_SKIPPED_INPUTS: [&str; 10] = [
+        "http://www.foo。bar.com",
+        "http://Ｇｏ.com",
+        "http://你好你好",
+        "https://faß.ExAmPlE/",
+        "http://０Ｘｃ０．０２５０．０１",
+        "ftp://%e2%98%83",
+        "https://%e2%98%83",
+        "file://a\u{ad}b/p",
+        "file://a%C2%ADb/p",
+        "http://GOO\u{200b}\u{2060}\u{feff}goo.com",
+    ];
+
     #[derive(Serialize, Deserialize, Debug)]
     struct URLTest {
-        base: String,
+        /// Without a base, the input is parsed as an absolute URL
+        base: Option<String>,
         input: String,
         href: Option<String>,
         origin: Option<String>,
@@ -27,60 +45,92 @@ mod tests {
         failure: Option<bool>,
     }
 
-    // @see: https://github.com/web-platform-tests/wpt/blob/7b0ebaccc62b566a1965396e5be7bb2bc06f841f/url/url-constructor.any.js
+    // @see: https://github.com/web-platform-tests/wpt/blob/2a64dae4641fbd61bd4257df460e188f425b492e/url/url-constructor.any.js
     #[test]
     fn test_url_wpt_constructor() -> Result<()> {
         let mut ctx = Context::new();
 
-        // NOTE: the file has been edited
-        let tests_data: Vec<URLTest> = serde_json::from_str(URL_TEST_DATA)?;
+        // The strings of the file are comments
+        let tests_data = serde_json::from_str::<Vec<serde_json::Value>>(URL_TEST_DATA)?
+            .into_iter()
+            .filter(serde_json::Value::is_object)
+            .map(serde_json::from_value)
+            .collect::<Result<Vec<URLTest>, _>>()?;
 
-        for expected in tests_data.iter() {
+        for expected in tests_data
+            .iter()
+            .filter(|expected| !IDNA_SKIPPED_INPUTS.contains(&expected.input.as_str()))
+        {
             if expected.failure.is_some() {
-                // TODO: Test failure cases
-                continue;
-            }
-
-            if !expected.input.is_empty() {
                 ctx.eval(&format!(
                     r#"
                     var expected = JSON.parse({:?});
+                    var args = expected.base === null ? [expected.input] : [expected.input, expected.base];
+                    var error = null;
 
-                    var url = new URL(expected.input, expected.base || "about:blank");
-                    var href = [url.href === expected.href, url.href, expected.href];
-                    var protocol = [url.protocol === expected.protocol, url.protocol, expected.protocol];
-                    var username = [url.username === expected.username, url.username, expected.username];
-                    var password = [url.password === expected.password, url.password, expected.password];
-                    var host = [url.host === expected.host, url.host, expected.host];
-                    var hostname = [url.hostname === expected.hostname, url.hostname, expected.hostname];
-                    var port = [url.port === expected.port, url.port, expected.port];
-                    var pathname = [url.pathname === expected.pathname, url.pathname, expected.pathname];
-                    var search = [url.search === expected.search, url.search, expected.search];
-                    var searchParams = [!expected.searchParams || url.searchParams === expected.searchParams, url.searchParams, expected.searchParams];
-
-                    var has_error = false;
-
-                    if (href[0] === false || protocol[0] === false || username[0] === false || password[0] === false || host[0] === false || hostname[0] === false || port[0] === false || pathname[0] === false || search[0] === false || searchParams[0] === false) {{
-                        ___logger("input", expected.input, "base", expected.base);
-
-                        has_error = true;
-
-                        (href[0] === false && ___logger(JSON.stringify({{href}})));
-                        (protocol[0] === false && ___logger(JSON.stringify({{protocol}})));
-                        (username[0] === false && ___logger(JSON.stringify({{username}})));
-                        (password[0] === false && ___logger(JSON.stringify({{password}})));
-                        (host[0] === false && ___logger(JSON.stringify({{host}})));
-                        (hostname[0] === false && ___logger(JSON.stringify({{hostname}})));
-                        (port[0] === false && ___logger(JSON.stringify({{port}})));
-                        (search[0] === false && ___logger(JSON.stringify({{search}})));
-                        (searchParams[0] === false && ___logger(JSON.stringify({{searchParams}})));
+                    try {{
+                        new URL(...args);
+                    }} catch (e) {{
+                        error = e;
+                    }}
+
+                    var has_error = !(error instanceof TypeError) || URL.canParse(...args) || URL.parse(...args) !== null;
+
+                    if (has_error) {{
+                        ___logger("input", expected.input, "base", expected.base, "should fail");
                     }}
                 "#,
                     json!(expected).to_string()
                 ))?;
 
-                assert_eq!("false", ctx.global.get_property("has_error")?.as_str()?,);
+                assert_eq!("false", ctx.global.get_property("has_error")?.as_str()?);
+
+                continue;
             }
+
+            ctx.eval(&format!(
+                r#"
+                var expected = JSON.parse({:?});
+                var args = expected.base === null ? [expected.input] : [expected.input, expected.base];
+
+                var url = new URL(...args);
+                var href = [url.href === expected.href, url.href, expected.href];
+                var protocol = [url.protocol === expected.protocol, url.protocol, expected.protocol];
+                var username = [url.username === expected.username, url.username, expected.username];
+                var password = [url.password === expected.password, url.password, expected.password];
+                var host = [url.host === expected.host, url.host, expected.host];
+                var hostname = [url.hostname === expected.hostname, url.hostname, expected.hostname];
+                var port = [url.port === expected.port, url.port, expected.port];
+                var pathname = [url.pathname === expected.pathname, url.pathname, expected.pathname];
+                var search = [url.search === expected.search, url.search, expected.search];
+                var hash = [url.hash === expected.hash, url.hash, expected.hash];
+                var origin = [expected.origin === null || url.origin === expected.origin, url.origin, expected.origin];
+                var searchParams = [!expected.searchParams || url.searchParams === expected.searchParams, url.searchParams, expected.searchParams];
+
+                var has_error = false;
+
+                if (href[0] === false || protocol[0] === false || username[0] === false || password[0] === false || host[0] === false || hostname[0] === false || port[0] === false || pathname[0] === false || search[0] === false || hash[0] === false || origin[0] === false || searchParams[0] === false || !URL.canParse(...args)) {{
+                    ___logger("input", expected.input, "base", expected.base);
+
+                    has_error = true;
+
+                    (href[0] === false && ___logger(JSON.stringify({{href}})));
+                    (protocol[0] === false && ___logger(JSON.stringify({{protocol}})));
+                    (username[0] === false && ___logger(JSON.stringify({{username}})));
+                    (password[0] === false && ___logger(JSON.stringify({{password}})));
+                    (host[0] === false && ___logger(JSON.stringify({{host}})));
+                    (hostname[0] === false && ___logger(JSON.stringify({{hostname}})));
+                    (port[0] === false && ___logger(JSON.stringify({{port}})));
+                    (search[0] === false && ___logger(JSON.stringify({{search}})));
+                    (hash[0] === false && ___logger(JSON.stringify({{hash}})));
+                    (origin[0] === false && ___logger(JSON.stringify({{origin}})));
+                    (searchParams[0] === false && ___logger(JSON.stringify({{searchParams}})));
+                }}
+            "#,
+                json!(expected).to_string()
+            ))?;
+
+            assert_eq!("false", ctx.global.get_property("has_error")?.as_str()?,);
         }
 
         Ok(())
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::{anyhow, Result};
+    use quickjs_wasm_rs::Context;
+
+    use crate::error::{ErrorKind, HandlerError};
+
+    #[test]
+    fn test_handler_error_from_value() -> Result<()> {
+        let context = Context::default();
+
+        let error = HandlerError::from_value(&context.eval_global(
+            "handler.js",
+            r#"
+                function fail() {
+                    return new TypeError("boom");
+                }
+
+                fail();
+            "#,
+        )?)?;
+
+        assert_eq!("TypeError", error.name);
+        assert_eq!("boom", error.message);
+        assert!(error.stack.unwrap().contains("at fail (handler.js:3)"));
+
+        let error = HandlerError::from_value(&context.eval_global("handler.js", r#""text""#)?)?;
+
+        assert_eq!(
+            HandlerError {
+                name: "Error".to_string(),
+                message: "text".to_string(),
+                stack: None,
+                kind: ErrorKind::Exception,
+            },
+            error
+        );
+
+        let error = HandlerError::from_value(
+            &context.eval_global("handler.js", r#"({ message: "plain object" })"#)?,
+        )?;
+
+        assert_eq!("Error", error.name);
+        assert_eq!("plain object", error.message);
+
+        let error = HandlerError::from_value(&context.eval_global(
+            "handler.js",
+            r#"Object.assign(new TypeError("not a response"), { ___kind: "invalid_response" })"#,
+        )?)?;
+
+        assert_eq!(ErrorKind::InvalidResponse, error.kind);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_handler_error_from_error() {
+        let error = HandlerError::from_error(&anyhow!(
+            "Uncaught SyntaxError: unexpected token in expression: ')'\n    at handler.js:2\n"
+        ));
+
+        assert_eq!("SyntaxError", error.name);
+        assert_eq!("unexpected token in expression: ')'", error.message);
+        assert_eq!(Some("    at handler.js:2\n".to_string()), error.stack);
+
+        let error = HandlerError::from_error(&anyhow!("The handler didn't return a response"));
+
+        assert_eq!("Error", error.name);
+        assert_eq!("The handler didn't return a response", error.message);
+        assert_eq!(None, error.stack);
+    }
+}
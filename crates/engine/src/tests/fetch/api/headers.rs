@@ -5,7 +5,10 @@ mod tests {
     use anyhow::Result;
     use regex::Regex;
 
-    use crate::tests::test_utils::context::Context;
+    use crate::{
+        globals::headers::{Guard, HeaderList},
+        tests::test_utils::context::Context,
+    };
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Headers/Headers
     #[test]
@@ -389,10 +392,6 @@ mod tests {
                     ["\r\n newLine1",""],
                     ["newLine2\r\n ",""],
                     ["\r\n\tnewLine3",""],
-                    // TODO: cover these cases
-                    // ["\t\f\tnewLine4\n","\f\tnewLine"],
-                    // ["newLine5\xa0",""],
-                    
                 ]);
                 var header_keys = [];
 
@@ -437,4 +436,155 @@ mod tests {
 
         Ok(())
     }
+
+    // @see: https://github.com/web-platform-tests/wpt/blob/master/fetch/api/headers/headers-normalize.any.js
+    #[test]
+    fn test_headers_normalize_whitespace() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            var headers = new Headers();
+            var expectations = [
+                [" space ", "space"],
+                ["\ttab\t", "tab"],
+                [" spaceAndTab\t", "spaceAndTab"],
+                ["\r\n newLine", "newLine"],
+                ["newLine\r\n ", "newLine"],
+                ["\r\n\tnewLine", "newLine"],
+                ["\t\f\tnewLine\n", "\f\tnewLine"],
+                ["newLine\xa0", "newLine\xa0"],
+            ];
+            var header_normalized = expectations.every(([value, expected], index) => {
+                headers.append(`X-Header-${index}`, value);
+
+                return headers.get(`X-Header-${index}`) === expected;
+            });
+            var header_errors = [];
+
+            for (const [name, value] of [["\f\tnewLine", ""], ["X-Header", "new\nline"], ["X-Header", "Ā"]]) {
+                try {
+                    headers.append(name, value);
+                } catch (error) {
+                    header_errors.push(error instanceof TypeError);
+                }
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            "true",
+            ctx.global.get_property("header_normalized")?.as_str()?
+        );
+        assert_eq!(
+            "true,true,true",
+            ctx.global.get_property("header_errors")?.as_str()?
+        );
+
+        Ok(())
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/Headers/getSetCookie
+    #[test]
+    fn test_headers_get_set_cookie() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            var headers = new Headers([
+                ["Set-Cookie", "a=1"],
+                ["Content-Type", "text/plain"],
+                ["Accept", "text/html"],
+                ["Set-Cookie", "b=2"],
+                ["Content-Type", "charset=utf-8"],
+            ]);
+            var header_set_cookie = JSON.stringify(headers.getSetCookie());
+            var header_cookie = headers.get("set-cookie");
+            var header_entries = JSON.stringify([...headers]);
+            "#,
+        )?;
+
+        assert_eq!(
+            r#"["a=1","b=2"]"#,
+            ctx.global.get_property("header_set_cookie")?.as_str()?
+        );
+        assert_eq!(
+            "a=1, b=2",
+            ctx.global.get_property("header_cookie")?.as_str()?
+        );
+        // In insertion order instead of sorted, each `set-cookie` value being an entry and the
+        // values of the other names being combined at their first position
+        assert_eq!(
+            r#"[["set-cookie","a=1"],["content-type","text/plain, charset=utf-8"],["accept","text/html"],["set-cookie","b=2"]]"#,
+            ctx.global.get_property("header_entries")?.as_str()?
+        );
+
+        Ok(())
+    }
+
+    // @see: https://fetch.spec.whatwg.org/#concept-headers-guard
+    #[test]
+    fn test_headers_request_no_cors() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            var request = new Request("https://test.test", {
+                mode: "no-cors",
+                headers: {
+                    "Accept": "text/html",
+                    "Content-Type": "application/json",
+                    "X-Custom": "value",
+                },
+            });
+            var header_entries = JSON.stringify([...request.headers]);
+            "#,
+        )?;
+
+        assert_eq!(
+            r#"[["accept","text/html"]]"#,
+            ctx.global.get_property("header_entries")?.as_str()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_header_list_guards() -> Result<()> {
+        let mut headers = HeaderList::default();
+
+        headers.append("Content-Type", "text/plain")?;
+        headers.append("Accept", "text/html")?;
+        headers.append("Accept", "application/json")?;
+        headers.set("accept", "*/*")?;
+
+        assert_eq!(
+            vec![
+                ("content-type", "text/plain".to_string()),
+                ("accept", "*/*".to_string()),
+            ],
+            headers.entries()
+        );
+
+        headers.set_guard(Guard::parse("immutable")?);
+
+        assert!(headers.append("X-Custom", "value").is_err());
+        assert!(headers.set("Accept", "text/html").is_err());
+        assert!(headers.delete("Accept").is_err());
+        assert_eq!(Some("*/*".to_string()), headers.get("Accept")?);
+
+        let mut headers = HeaderList::default();
+
+        headers.set_guard(Guard::parse("request-no-cors")?);
+        headers.append("Content-Type", "text/plain;charset=UTF-8")?;
+        headers.append("Content-Type", "application/json")?;
+        headers.append("X-Custom", "value")?;
+        headers.delete("Content-Type")?;
+        headers.delete("X-Custom")?;
+
+        assert!(headers.entries().is_empty());
+        assert!(Guard::parse("unknown").is_err());
+
+        Ok(())
+    }
 }
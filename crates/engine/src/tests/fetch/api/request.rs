@@ -496,6 +496,55 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_request_body_stream_of_form_data_and_blob() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const form = new FormData();
+                form.append("name", "value");
+
+                // The stream of the body leaves the body to the other methods
+                const formRequest = new Request("https://example.com", { method: "POST", body: form });
+                const formStream = formRequest.body;
+                const formClone = formRequest.clone();
+                const formText = new TextDecoder().decode(await formRequest.arrayBuffer());
+
+                const blobRequest = new Request("https://example.com", {
+                    method: "POST",
+                    body: new Blob(["Hello World!"], { type: "text/plain" }),
+                });
+                const blobStream = blobRequest.body;
+                const blobClone = blobRequest.clone();
+                const blobText = new TextDecoder().decode(await blobRequest.arrayBuffer());
+                const clonedBlob = await blobClone.blob();
+
+                return {
+                    blob: [blobStream === blobRequest.body, blobText, clonedBlob.type, await clonedBlob.text()],
+                    form: [
+                        formStream === formRequest.body,
+                        formText.includes('name="name"') && formText.includes("value"),
+                        (await formClone.formData()).get("name"),
+                    ],
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "blob": [true, "Hello World!", "text/plain", "Hello World!"],
+                "form": [true, true, "value"],
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_request_array_buffer() -> Result<()> {
         let mut ctx = Context::new();
@@ -546,12 +595,12 @@ mod test {
                 const arrayBuffer = await response.arrayBuffer();
 
                 return new TextDecoder().decode(arrayBuffer)
-                    .replace(/------WebKitFormBoundary?([\d\w]+)/g, '------WebKitFormBoundary0');
+                    .replace(/----FormDataBoundary[0-9a-f]+/g, '----FormDataBoundary0');
             }
             "#,
         )?;
 
-        assert_eq!(r#""------WebKitFormBoundary0\nContent-Disposition: form-data; name=\"textFile\"\n\nPlain Text 1,Plain Text 2\n------WebKitFormBoundary0--""#.to_string(), ctx.get_handler_value()?);
+        assert_eq!(r#""------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile\"\r\n\r\nPlain Text 1\r\n------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile\"\r\n\r\nPlain Text 2\r\n------FormDataBoundary0--\r\n""#.to_string(), ctx.get_handler_value()?);
 
         ctx.eval(
             r#"
@@ -565,12 +614,12 @@ mod test {
                 const arrayBuffer = await response.arrayBuffer();
 
                 return new TextDecoder().decode(arrayBuffer)
-                    .replace(/------WebKitFormBoundary?([\d\w]+)/g, '------WebKitFormBoundary0');
+                    .replace(/----FormDataBoundary[0-9a-f]+/g, '----FormDataBoundary0');
             }
             "#,
         )?;
 
-        assert_eq!(r#""------WebKitFormBoundary0\nContent-Disposition: form-data; name=\"textFile1\"\n\nPlain Text 1\n------WebKitFormBoundary0\nContent-Disposition: form-data; name=\"textFile2\"\n\nPlain Text 2\n------WebKitFormBoundary0--""#.to_string(), ctx.get_handler_value()?);
+        assert_eq!(r#""------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile1\"\r\n\r\nPlain Text 1\r\n------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile2\"\r\n\r\nPlain Text 2\r\n------FormDataBoundary0--\r\n""#.to_string(), ctx.get_handler_value()?);
 
         Ok(())
     }
@@ -683,6 +732,107 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_request_clone_stream() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            globalThis.___fetcher = (request) => JSON.stringify({
+                status: 200,
+                headers: JSON.stringify({}),
+                body: Array.from(new TextEncoder().encode(`${request.method} ${new TextDecoder().decode(request.body)}`)),
+            });
+
+            async function handler() {
+                const request = new Request("https://test.test/", {
+                    method: "POST",
+                    headers: { "content-type": "application/json" },
+                    body: new ReadableStream({
+                        start(controller) {
+                            controller.enqueue(new TextEncoder().encode('{"id":1}'));
+                            controller.close();
+                        },
+                    }),
+                });
+
+                // A middleware reads the clone, and passes the request on
+                const clone = request.clone();
+                const json = await clone.json();
+                const bodyUsed = [request.bodyUsed, clone.bodyUsed];
+                const response = await fetch(request);
+
+                clone.headers.set("x-clone", "1");
+
+                return {
+                    bodyUsed,
+                    fetched: await response.text(),
+                    headers: [request.headers.get("content-type"), request.headers.has("x-clone")],
+                    json,
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "bodyUsed": [false, true],
+                "fetched": "POST {\"id\":1}",
+                "headers": ["application/json", false],
+                "json": { "id": 1 },
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const request = new Request("https://test.test/", {
+                    method: "POST",
+                    body: "Hello World!",
+                });
+                const reader = request.body.getReader();
+                const { value } = await reader.read();
+                const errors = [];
+
+                try {
+                    request.clone();
+                } catch (error) {
+                    errors.push(error.message);
+                }
+
+                try {
+                    await request.text();
+                } catch (error) {
+                    errors.push(error.message);
+                }
+
+                return {
+                    bodyUsed: request.bodyUsed,
+                    errors,
+                    read: new TextDecoder().decode(value),
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "bodyUsed": true,
+                "errors": [
+                    "Failed to execute 'clone' on 'Request': Request body is already used",
+                    "Failed to execute 'text': body stream already read",
+                ],
+                "read": "Hello World!",
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_request_text() -> Result<()> {
         let mut ctx = Context::new();
@@ -714,12 +864,12 @@ mod test {
                 const response = new Response(formData);
                 const text = await response.text();
 
-                return text.replace(/------WebKitFormBoundary?([\d\w]+)/g, '------WebKitFormBoundary0');
+                return text.replace(/----FormDataBoundary[0-9a-f]+/g, '----FormDataBoundary0');
             }
             "#,
         )?;
 
-        assert_eq!(r#""------WebKitFormBoundary0\nContent-Disposition: form-data; name=\"textFile\"\n\nPlain Text 1,Plain Text 2\n------WebKitFormBoundary0--""#.to_string(), ctx.get_handler_value()?);
+        assert_eq!(r#""------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile\"\r\n\r\nPlain Text 1\r\n------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile\"\r\n\r\nPlain Text 2\r\n------FormDataBoundary0--\r\n""#.to_string(), ctx.get_handler_value()?);
 
         ctx.eval(
             r#"
@@ -732,12 +882,12 @@ mod test {
                 const response = new Response(formData);
                 const text = await response.text();
 
-                return text.replace(/------WebKitFormBoundary?([\d\w]+)/g, '------WebKitFormBoundary0');
+                return text.replace(/----FormDataBoundary[0-9a-f]+/g, '----FormDataBoundary0');
             }
             "#,
         )?;
 
-        assert_eq!(r#""------WebKitFormBoundary0\nContent-Disposition: form-data; name=\"textFile1\"\n\nPlain Text 1\n------WebKitFormBoundary0\nContent-Disposition: form-data; name=\"textFile2\"\n\nPlain Text 2\n------WebKitFormBoundary0--""#.to_string(), ctx.get_handler_value()?);
+        assert_eq!(r#""------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile1\"\r\n\r\nPlain Text 1\r\n------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile2\"\r\n\r\nPlain Text 2\r\n------FormDataBoundary0--\r\n""#.to_string(), ctx.get_handler_value()?);
 
         Ok(())
     }
@@ -749,27 +899,31 @@ mod test {
         ctx.eval(
             r#"
             async function handler() {
-                const body = `------cb6762ec1a35a74a
-                    Content-Disposition: form-data; name="textFile[]"; filename="text.txt"
-                    Content-Type: text/plain
-                    
-                    Plain Text
-                    ------cb6762ec1a35a74a
-                    Content-Disposition: form-data; name="textFile[]"; filename="text.txt"
-                    Content-Type: text/plain
-                    
-                    Plain Text 1
-                    ------cb6762ec1a35a74a--`;
+                const body = [
+                    '--cb6762ec1a35a74a',
+                    'Content-Disposition: form-data; name="textFile[]"; filename="text.txt"',
+                    'Content-Type: text/plain',
+                    '',
+                    'Plain Text',
+                    '--cb6762ec1a35a74a',
+                    'Content-Disposition: form-data; name="textFile[]"; filename="text.txt"',
+                    'Content-Type: text/plain',
+                    '',
+                    'Plain Text 1',
+                    '--cb6762ec1a35a74a--',
+                ].join('\r\n');
                 const request = new Request('/test', {
                     method: 'POST',
                     headers: {
-                        'Content-Type': 'multipart/form-data; boundary=------cb6762ec1a35a74a'
+                        'Content-Type': 'multipart/form-data; boundary=cb6762ec1a35a74a'
                     },
                     body
                 });
                 const formData = await request.formData();
 
-                return formData.getAll('textFile');
+                const files = formData.getAll('textFile[]');
+
+                return Promise.all(files.map((file) => file.text()));
             }
             "#,
         )?;
@@ -782,21 +936,21 @@ mod test {
         ctx.eval(
             r#"
             async function handler() {
-                var body = `------abcd
-                    Content-Disposition: form-data; name="textFile1"; filename="text.txt"
-                    Content-Type: text/plain
-                    
-                    Plain Text 1
-                    ------abcd
-                    Content-Disposition: form-data; name="textFile2"; filename="text.txt"
-                    Content-Type: text/plain
-                    
-                    Plain Text 2
-                    ------abcd--`;
+                var body = [
+                    '--abcd',
+                    'Content-Disposition: form-data; name="textFile1"',
+                    '',
+                    'Plain Text 1',
+                    '--abcd',
+                    'Content-Disposition: form-data; name="textFile2"',
+                    '',
+                    'Plain Text 2',
+                    '--abcd--',
+                ].join('\n');
                 const request = new Request('/test', {
                     method: 'POST',
                     headers: {
-                        'Content-Type': 'multipart/form-data; boundary=------abcd'
+                        'Content-Type': 'multipart/form-data; boundary=abcd'
                     },
                     body
                 });
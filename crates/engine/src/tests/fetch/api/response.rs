@@ -251,6 +251,79 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_response_body_stream_of_form_data_and_blob() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const form = new FormData();
+                form.append("name", "value");
+
+                // The stream of the body leaves the body to the other methods
+                const formResponse = new Response(form);
+                const formStream = formResponse.body;
+                const formClone = formResponse.clone();
+                const formText = new TextDecoder().decode(await formResponse.arrayBuffer());
+
+                const blobResponse = new Response(new Blob(["Hello World!"], { type: "text/plain" }));
+                const blobStream = blobResponse.body;
+                const blobClone = blobResponse.clone();
+                const blobText = new TextDecoder().decode(await blobResponse.arrayBuffer());
+                const clonedBlob = await blobClone.blob();
+
+                // Reading the stream uses the body
+                const read = new Response(form);
+                const readText = await new Response(read.body).text();
+                const errors = [];
+
+                try {
+                    await read.arrayBuffer();
+                } catch (error) {
+                    errors.push(error.message);
+                }
+
+                const locked = new Response(new Blob(["Hello"]));
+                locked.body.getReader();
+
+                try {
+                    locked.clone();
+                } catch (error) {
+                    errors.push(error.message);
+                }
+
+                return {
+                    blob: [blobStream === blobResponse.body, blobText, clonedBlob.type, await clonedBlob.text()],
+                    errors,
+                    form: [
+                        formStream === formResponse.body,
+                        formText.includes('name="name"') && formText.includes("value"),
+                        (await formClone.formData()).get("name"),
+                    ],
+                    read: [read.bodyUsed, readText.includes('name="name"') && readText.includes("value")],
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "blob": [true, "Hello World!", "text/plain", "Hello World!"],
+                "errors": [
+                    "Failed to execute 'arrayBuffer': body stream already read",
+                    "Failed to execute 'clone' on 'Response': Response body is already used",
+                ],
+                "form": [true, true, "value"],
+                "read": [true, true],
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_response_error() -> Result<()> {
         let mut ctx = Context::new();
@@ -295,6 +368,56 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_response_json() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const response = Response.json({ hello: "world" }, {
+                    status: 202,
+                    headers: { "x-custom": "value" },
+                });
+                const custom = Response.json("text", {
+                    headers: { "content-type": "application/vnd.api+json" },
+                });
+                let error = null;
+
+                try {
+                    Response.json(undefined);
+                } catch (e) {
+                    error = e.constructor.name;
+                }
+
+                return {
+                    body: await response.json(),
+                    contentType: response.headers.get("content-type"),
+                    custom: response.headers.get("x-custom"),
+                    customContentType: custom.headers.get("content-type"),
+                    error,
+                    status: response.status,
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "body": { "hello": "world" },
+                "contentType": "application/json",
+                "custom": "value",
+                "customContentType": "application/vnd.api+json",
+                "error": "TypeError",
+                "status": 202,
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_response_redirect() -> Result<()> {
         let mut ctx = Context::new();
@@ -423,12 +546,12 @@ mod tests {
                 const arrayBuffer = await response.arrayBuffer();
 
                 return new TextDecoder().decode(arrayBuffer)
-                    .replace(/------WebKitFormBoundary?([\d\w]+)/g, '------WebKitFormBoundary0');
+                    .replace(/----FormDataBoundary[0-9a-f]+/g, '----FormDataBoundary0');
             }
             "#,
         )?;
 
-        assert_eq!(r#""------WebKitFormBoundary0\nContent-Disposition: form-data; name=\"textFile\"\n\nPlain Text 1,Plain Text 2\n------WebKitFormBoundary0--""#.to_string(), ctx.get_handler_value()?);
+        assert_eq!(r#""------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile\"\r\n\r\nPlain Text 1\r\n------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile\"\r\n\r\nPlain Text 2\r\n------FormDataBoundary0--\r\n""#.to_string(), ctx.get_handler_value()?);
 
         ctx.eval(
             r#"
@@ -442,12 +565,12 @@ mod tests {
                 const arrayBuffer = await response.arrayBuffer();
 
                 return new TextDecoder().decode(arrayBuffer)
-                    .replace(/------WebKitFormBoundary?([\d\w]+)/g, '------WebKitFormBoundary0');
+                    .replace(/----FormDataBoundary[0-9a-f]+/g, '----FormDataBoundary0');
             }
             "#,
         )?;
 
-        assert_eq!(r#""------WebKitFormBoundary0\nContent-Disposition: form-data; name=\"textFile1\"\n\nPlain Text 1\n------WebKitFormBoundary0\nContent-Disposition: form-data; name=\"textFile2\"\n\nPlain Text 2\n------WebKitFormBoundary0--""#.to_string(), ctx.get_handler_value()?);
+        assert_eq!(r#""------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile1\"\r\n\r\nPlain Text 1\r\n------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile2\"\r\n\r\nPlain Text 2\r\n------FormDataBoundary0--\r\n""#.to_string(), ctx.get_handler_value()?);
 
         Ok(())
     }
@@ -510,6 +633,180 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_response_clone_stream() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const stream = new ReadableStream({
+                    start(controller) {
+                        controller.enqueue(new TextEncoder().encode("Hello "));
+                        controller.enqueue(new TextEncoder().encode("World!"));
+                        controller.close();
+                    },
+                });
+                const response = new Response(stream, {
+                    headers: { "content-type": "text/plain" },
+                    status: 201,
+                });
+                const clone = response.clone();
+                const cloneOfClone = clone.clone();
+                const bodyUsed = [];
+
+                bodyUsed.push([response.bodyUsed, clone.bodyUsed, cloneOfClone.bodyUsed]);
+
+                const text = await clone.text();
+
+                bodyUsed.push([response.bodyUsed, clone.bodyUsed, cloneOfClone.bodyUsed]);
+
+                const reader = response.body.getReader();
+                const { value } = await reader.read();
+
+                bodyUsed.push([response.bodyUsed, clone.bodyUsed, cloneOfClone.bodyUsed]);
+
+                return {
+                    bodyUsed,
+                    cloneOfClone: await cloneOfClone.arrayBuffer().then((buffer) => buffer.byteLength),
+                    first: new TextDecoder().decode(value),
+                    status: clone.status,
+                    text,
+                    type: clone.headers.get("content-type"),
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "bodyUsed": [[false, false, false], [false, true, false], [true, true, false]],
+                "cloneOfClone": 12,
+                "first": "Hello ",
+                "status": 201,
+                "text": "Hello World!",
+                "type": "text/plain",
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const response = new Response("Hello World!");
+                const clone = response.clone();
+                const errors = [];
+
+                await response.arrayBuffer();
+
+                for (const target of [response, clone]) {
+                    try {
+                        target.clone();
+                    } catch (error) {
+                        errors.push(error.message);
+                    }
+                }
+
+                return {
+                    bodyUsed: [response.bodyUsed, clone.bodyUsed],
+                    errors,
+                    text: await clone.text(),
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "bodyUsed": [true, false],
+                "errors": ["Failed to execute 'clone' on 'Response': Response body is already used"],
+                "text": "Hello World!",
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        ctx.eval(
+            r#"
+            globalThis.___fetcher = () => JSON.stringify({
+                status: 200,
+                headers: JSON.stringify({ "content-type": "text/plain" }),
+                body: Array.from(new TextEncoder().encode("Fetched")),
+            });
+
+            async function handler() {
+                const response = await fetch("https://test.test/");
+                const clone = response.clone();
+                const errors = [];
+
+                try {
+                    clone.headers.set("x-clone", "1");
+                } catch (error) {
+                    errors.push(error.name);
+                }
+
+                return {
+                    errors,
+                    texts: [await response.text(), await clone.text()],
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "errors": ["TypeError"],
+                "texts": ["Fetched", "Fetched"],
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_response_clone_copies_form_data_and_search_params() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            async function handler() {
+                const form = new FormData();
+                form.append("name", "value");
+
+                const formResponse = new Response(form);
+                const formClone = formResponse.clone();
+                (await formClone.formData()).append("clone", "value");
+
+                const params = new URLSearchParams("name=value");
+                const paramsResponse = new Response(params);
+                const paramsClone = paramsResponse.clone();
+                params.append("response", "value");
+
+                return {
+                    form: [...form.keys()],
+                    formData: [...(await formResponse.formData()).keys()],
+                    params: await paramsClone.text(),
+                };
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "form": ["name"],
+                "formData": ["name"],
+                "params": "name=value",
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_response_text() -> Result<()> {
         let mut ctx = Context::new();
@@ -538,12 +835,12 @@ mod tests {
                 const response = new Response(formData);
                 const text = await response.text();
 
-                return text.replace(/------WebKitFormBoundary?([\d\w]+)/g, '------WebKitFormBoundary0');
+                return text.replace(/----FormDataBoundary[0-9a-f]+/g, '----FormDataBoundary0');
             }
             "#,
         )?;
 
-        assert_eq!(r#""------WebKitFormBoundary0\nContent-Disposition: form-data; name=\"textFile\"\n\nPlain Text 1,Plain Text 2\n------WebKitFormBoundary0--""#.to_string(), ctx.get_handler_value()?);
+        assert_eq!(r#""------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile\"\r\n\r\nPlain Text 1\r\n------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile\"\r\n\r\nPlain Text 2\r\n------FormDataBoundary0--\r\n""#.to_string(), ctx.get_handler_value()?);
 
         ctx.eval(
             r#"
@@ -556,12 +853,12 @@ mod tests {
                 const response = new Response(formData);
 const text = await response.text();
 
-                return text.replace(/------WebKitFormBoundary?([\d\w]+)/g, '------WebKitFormBoundary0');
+                return text.replace(/----FormDataBoundary[0-9a-f]+/g, '----FormDataBoundary0');
             }
             "#,
         )?;
 
-        assert_eq!(r#""------WebKitFormBoundary0\nContent-Disposition: form-data; name=\"textFile1\"\n\nPlain Text 1\n------WebKitFormBoundary0\nContent-Disposition: form-data; name=\"textFile2\"\n\nPlain Text 2\n------WebKitFormBoundary0--""#.to_string(), ctx.get_handler_value()?);
+        assert_eq!(r#""------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile1\"\r\n\r\nPlain Text 1\r\n------FormDataBoundary0\r\nContent-Disposition: form-data; name=\"textFile2\"\r\n\r\nPlain Text 2\r\n------FormDataBoundary0--\r\n""#.to_string(), ctx.get_handler_value()?);
 
         Ok(())
     }
@@ -573,25 +870,29 @@ const text = await response.text();
         ctx.eval(
             r#"
             async function handler() {
-                const body = `------cb6762ec1a35a74a
-                    Content-Disposition: form-data; name="textFile[]"; filename="text.txt"
-                    Content-Type: text/plain
-                    
-                    Plain Text
-                    ------cb6762ec1a35a74a
-                    Content-Disposition: form-data; name="textFile[]"; filename="text.txt"
-                    Content-Type: text/plain
-                    
-                    Plain Text 1
-                    ------cb6762ec1a35a74a--`;
+                const body = [
+                    '--cb6762ec1a35a74a',
+                    'Content-Disposition: form-data; name="textFile[]"; filename="text.txt"',
+                    'Content-Type: text/plain',
+                    '',
+                    'Plain Text',
+                    '--cb6762ec1a35a74a',
+                    'Content-Disposition: form-data; name="textFile[]"; filename="text.txt"',
+                    'Content-Type: text/plain',
+                    '',
+                    'Plain Text 1',
+                    '--cb6762ec1a35a74a--',
+                ].join('\r\n');
                 const response = new Response(body, {
                     headers: {
-                        'Content-Type': 'multipart/form-data; boundary=------cb6762ec1a35a74a'
+                        'Content-Type': 'multipart/form-data; boundary=cb6762ec1a35a74a'
                     }
                 });
                 const formData = await response.formData();
 
-                return formData.getAll('textFile');
+                const files = formData.getAll('textFile[]');
+
+                return Promise.all(files.map((file) => file.text()));
             }
             "#,
         )?;
@@ -604,20 +905,20 @@ const text = await response.text();
         ctx.eval(
             r#"
             async function handler() {
-                const body = `------abcd
-                    Content-Disposition: form-data; name="textFile1"; filename="text.txt"
-                    Content-Type: text/plain
-                    
-                    Plain Text 1
-                    ------abcd
-                    Content-Disposition: form-data; name="textFile2"; filename="text.txt"
-                    Content-Type: text/plain
-                    
-                    Plain Text 2
-                    ------abcd--`;
+                const body = [
+                    '--abcd',
+                    'Content-Disposition: form-data; name="textFile1"',
+                    '',
+                    'Plain Text 1',
+                    '--abcd',
+                    'Content-Disposition: form-data; name="textFile2"',
+                    '',
+                    'Plain Text 2',
+                    '--abcd--',
+                ].join('\n');
                 const response = new Response(body, {
                     headers: {
-                        'Content-Type': 'multipart/form-data; boundary=------abcd'
+                        'Content-Type': 'multipart/form-data; boundary=abcd'
                     }
                 });
                 const formData = await response.formData();
@@ -0,0 +1,265 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use anyhow::Result;
+    use quickjs_wasm_rs::Context;
+
+    use crate::{
+        error::{ErrorKind, HandlerError},
+        modules::{compile, load, Source},
+    };
+
+    #[test]
+    fn test_source_parse() {
+        let source = Source::parse(r#"{"handler":"export default {};","modules":{"a.js":""}}"#);
+
+        assert_eq!("export default {};", source.handler);
+        assert_eq!(1, source.modules.len());
+
+        let source = Source::parse("export const handleRequest = () => {};");
+
+        assert_eq!("export const handleRequest = () => {};", source.handler);
+        assert!(source.modules.is_empty());
+    }
+
+    #[test]
+    fn test_load_handle_request() -> Result<()> {
+        let context = Context::default();
+        let source = Source::parse(
+            r#"
+                import { greeting } from "./lib/greeting.js";
+
+                export function handleRequest() {
+                    return greeting("handleRequest");
+                }
+            "#,
+        );
+        let source = Source {
+            modules: BTreeMap::from([
+                (
+                    "./lib/greeting.js".to_string(),
+                    r#"
+                    import { PREFIX } from "../constants.js";
+
+                    export const greeting = (name) => `${PREFIX} ${name}`;
+                "#
+                    .to_string(),
+                ),
+                (
+                    "constants.js".to_string(),
+                    r#"export const PREFIX = "Hello";"#.to_string(),
+                ),
+            ]),
+            ..source
+        };
+
+        let handler = load(&context, &source)?;
+        let output = handler.call(&context.global_object()?, &[])?;
+
+        assert_eq!("Hello handleRequest", output.as_str()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_default_fetch() -> Result<()> {
+        let context = Context::default();
+        let source = Source::parse(
+            r#"
+                export default {
+                    name: "default",
+                    fetch() {
+                        return `Hello ${this.name}`;
+                    },
+                };
+            "#,
+        );
+
+        let handler = load(&context, &source)?;
+        let output = handler.call(&context.global_object()?, &[])?;
+
+        assert_eq!("Hello default", output.as_str()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_fetch_event_handler() -> Result<()> {
+        let context = Context::default();
+        // What `addEventListener("fetch", listener)` sets
+        let source = Source::parse(
+            r#"
+                globalThis.___fetchEventHandler = function () {
+                    return "Hello listener";
+                };
+            "#,
+        );
+
+        let handler = load(&context, &source)?;
+        let output = handler.call(&context.global_object()?, &[])?;
+
+        assert_eq!("Hello listener", output.as_str()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compile_and_load_bytecode() -> Result<()> {
+        let source = Source {
+            modules: BTreeMap::from([(
+                "lib/name.js".to_string(),
+                r#"export const name = "bytecode";"#.to_string(),
+            )]),
+            ..Source::parse(
+                r#"
+                    import { name } from "./lib/name.js";
+
+                    export default {
+                        fetch: () => `Hello ${name}`,
+                    };
+                "#,
+            )
+        };
+
+        let bytecode = compile(&Context::default(), &source)?;
+
+        assert_eq!(
+            vec!["handler.js", "lib/name.js"],
+            bytecode
+                .iter()
+                .map(|module| module.name.as_str())
+                .collect::<Vec<_>>()
+        );
+
+        // Loaded without the source, in a context that didn't compile it
+        let context = Context::default();
+        let source = Source {
+            bytecode,
+            ..Default::default()
+        };
+
+        let handler = load(&context, &source)?;
+        let output = handler.call(&context.global_object()?, &[])?;
+
+        assert_eq!("Hello bytecode", output.as_str()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_env() {
+        let source = Source::parse(
+            r#"{
+                "handler": "",
+                "env": {
+                    "NAME": { "type": "var", "value": "worker" },
+                    "CONFIG": { "type": "var", "value": { "retries": 3 } },
+                    "TOKEN": { "type": "secret", "value": "s3cr3t" },
+                    "CACHE": { "type": "kv" }
+                }
+            }"#,
+        );
+
+        assert_eq!(
+            BTreeMap::from([
+                ("CONFIG", r#"{"retries":3}"#.to_string()),
+                ("NAME", "worker".to_string()),
+                ("TOKEN", "s3cr3t".to_string()),
+            ]),
+            source.process_env()
+        );
+    }
+
+    #[test]
+    fn test_load_missing_handler() {
+        let context = Context::default();
+        let source = Source::parse("export const answer = 42;");
+        let error = load(&context, &source).unwrap_err();
+
+        assert_eq!(
+            ErrorKind::MissingHandler,
+            error.downcast_ref::<HandlerError>().unwrap().kind
+        );
+    }
+
+    #[test]
+    fn test_load_quotes_in_comments_and_strings() -> Result<()> {
+        let context = Context::default();
+        let source = Source {
+            modules: BTreeMap::from([(
+                "a.js".to_string(),
+                r#"export const a = "It's loaded";"#.to_string(),
+            )]),
+            ..Source::parse(
+                r#"
+                    // Here's the handler
+                    import { a } from './a.js';
+                    /* import { b } from "./missing.js"; */
+                    const text = 'from "./missing.js"';
+                    const template = `import("./missing.js")`;
+
+                    export function handleRequest() {
+                        return `${a} ${text.length} ${template.length}`;
+                    }
+                "#,
+            )
+        };
+
+        let handler = load(&context, &source)?;
+        let output = handler.call(&context.global_object()?, &[])?;
+
+        assert_eq!("It's loaded 19 22", output.as_str()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_dynamic_import() -> Result<()> {
+        let context = Context::default();
+        let source = Source {
+            modules: BTreeMap::from([(
+                "lib/lazy.js".to_string(),
+                r#"
+                    globalThis.lazyEvaluated = true;
+
+                    export const name = "lazy";
+                "#
+                .to_string(),
+            )]),
+            ..Source::parse(
+                r#"
+                    export async function handleRequest() {
+                        const { name } = await import("./lib/lazy.js");
+
+                        globalThis.lazyName = name;
+                    }
+
+                    // Never called, so the missing module isn't loaded
+                    export const missing = () => import("./missing.js");
+                "#,
+            )
+        };
+
+        let handler = load(&context, &source)?;
+        let global = context.global_object()?;
+
+        assert!(global.get_property("lazyEvaluated")?.is_undefined());
+
+        handler.call(&global, &[])?;
+        context.execute_pending()?;
+
+        assert!(global.get_property("lazyEvaluated")?.as_bool()?);
+        assert_eq!("lazy", global.get_property("lazyName")?.as_str()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_missing_module() {
+        let context = Context::default();
+        let source = Source::parse(r#"import { a } from "./missing.js";"#);
+
+        assert!(load(&context, &source).is_err());
+    }
+}
@@ -12,7 +12,11 @@ use once_cell::sync::{Lazy, OnceCell};
 use quickjs_wasm_rs::{json, Context as QuickjsContext, Exception, Value};
 use send_wrapper::SendWrapper;
 
-use crate::globals::utils::set_global_utils;
+use crate::globals::{
+    blob::set_global_blob, compression::set_global_compression, headers::set_global_headers,
+    multipart::set_global_multipart, text_encoding::set_global_text_encoding, url::set_global_url,
+    url_pattern::set_global_url_pattern, utils::set_global_utils,
+};
 
 pub static CONTEXT: OnceCell<SendWrapper<Rc<QuickjsContext>>> = OnceCell::new();
 
@@ -48,6 +52,13 @@ impl Context<'_> {
         contents.push_str(code);
 
         set_global_utils(self.context).unwrap();
+        set_global_headers(self.context).unwrap();
+        set_global_multipart(self.context).unwrap();
+        set_global_blob(self.context).unwrap();
+        set_global_text_encoding(self.context).unwrap();
+        set_global_compression(self.context).unwrap();
+        set_global_url(self.context).unwrap();
+        set_global_url_pattern(self.context).unwrap();
 
         self.context.eval_global(SCRIPT_NAME, &contents)?;
 
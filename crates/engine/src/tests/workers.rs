@@ -0,0 +1,546 @@
+// @see: https://developers.cloudflare.com/workers/runtime-apis/handlers/fetch/
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use serde_json::json;
+
+    use crate::tests::test_utils::context::Context;
+
+    #[test]
+    fn test_handle_response_env_and_ctx() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            var handler = async () => {
+                const response = await ___handleResponse(
+                    (request, env, ctx) => new Response(
+                        `${request.method} ${env.NAME} ${ctx instanceof ExecutionContext}`,
+                    ),
+                    new Request("https://test.test"),
+                    JSON.stringify({ NAME: { type: "var", value: "worker" } }),
+                );
+
+                return new TextDecoder().decode(response.body);
+            };
+            "#,
+        )?;
+
+        assert_eq!(r#""GET worker true""#, ctx.get_handler_value()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_env_bindings() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            var entries = {};
+
+            globalThis.___kv = (request, value) => {
+                const { binding, operation, key, prefix } = JSON.parse(request);
+
+                if (binding !== "CACHE") {
+                    return JSON.stringify({ error: `Unknown KV namespace: ${binding}` });
+                }
+
+                switch (operation) {
+                    case "get":
+                        return entries[key] ?? JSON.stringify({ value: null });
+                    case "put":
+                        entries[key] = value;
+                        return JSON.stringify({ value: null });
+                    case "delete":
+                        delete entries[key];
+                        return JSON.stringify({ value: null });
+                    case "list":
+                        return JSON.stringify({
+                            value: Object.keys(entries).filter((key) => key.startsWith(prefix)),
+                        });
+                }
+            };
+
+            var handler = async () => {
+                const env = ___createEnv(JSON.stringify({
+                    API_URL: { type: "var", value: "https://api.test" },
+                    CONFIG: { type: "var", value: { retries: 3 } },
+                    TOKEN: { type: "secret", value: "s3cr3t" },
+                    CACHE: { type: "kv" },
+                }));
+
+                await env.CACHE.put("answer", JSON.stringify({ value: 42 }));
+
+                const json = await env.CACHE.get("answer", { type: "json" });
+                const text = await env.CACHE.get("answer");
+                const list = await env.CACHE.list({ prefix: "ans" });
+
+                await env.CACHE.delete("answer");
+                await env.CACHE.put("bytes", new Uint8Array([0xff, 0x00, 0x80]));
+
+                const bytes = await env.CACHE.get("bytes", "arrayBuffer");
+
+                return [
+                    env.API_URL,
+                    env.CONFIG.retries,
+                    env.TOKEN,
+                    env.CACHE instanceof KVNamespace,
+                    json.value,
+                    text,
+                    list.keys,
+                    await env.CACHE.get("answer"),
+                    Object.isFrozen(env),
+                    Array.from(new Uint8Array(bytes)),
+                ];
+            };
+            "#,
+        )?;
+
+        assert_eq!(
+            r#"["https://api.test",3,"s3cr3t",true,42,"{\"value\":42}",[{"name":"answer"}],null,true,[255,0,128]]"#,
+            ctx.get_handler_value()?
+        );
+
+        ctx.eval(
+            r#"
+            var handler = async () => {
+                try {
+                    await new KVNamespace("MISSING").get("key");
+                } catch (error) {
+                    return error.message;
+                }
+            };
+            "#,
+        )?;
+
+        assert_eq!(
+            r#""Unknown KV namespace: MISSING""#,
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_execution_context_wait_until() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            var waited = false;
+
+            var handler = async () => {
+                const ctx = new ExecutionContext();
+
+                ctx.waitUntil(Promise.resolve().then(() => {
+                    waited = true;
+                }));
+                ctx.waitUntil(Promise.reject(new Error("Background failure")));
+                ctx.passThroughOnException();
+
+                return waited;
+            };
+            "#,
+        )?;
+
+        assert_eq!("false", ctx.get_handler_value()?);
+        assert_eq!("true", ctx.global.get_property("waited")?.as_str()?);
+
+        Ok(())
+    }
+
+    // @see: https://developers.cloudflare.com/workers/runtime-apis/handlers/fetch/#syntax-service-worker
+    #[test]
+    fn test_fetch_event_respond_with() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            var waited = false;
+
+            self.addEventListener("fetch", (event) => {
+                event.waitUntil(Promise.resolve().then(() => {
+                    waited = true;
+                }));
+                event.respondWith(new Response(`${event.type} ${event.request.url}`));
+            });
+
+            var handler = async () => {
+                const response = await ___handleResponse(
+                    ___fetchEventHandler,
+                    new Request("https://test.test/"),
+                    JSON.stringify({}),
+                );
+
+                return new TextDecoder().decode(response.body);
+            };
+            "#,
+        )?;
+
+        assert_eq!(r#""fetch https://test.test/""#, ctx.get_handler_value()?);
+        assert_eq!("true", ctx.global.get_property("waited")?.as_str()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_event_errors() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            var handler = async () => {
+                const errors = [];
+                const event = new FetchEvent("fetch", { request: new Request("https://test.test/") });
+
+                event.respondWith(new Response("first"));
+
+                try {
+                    event.respondWith(new Response("second"));
+                } catch (error) {
+                    errors.push(error.message);
+                }
+
+                try {
+                    new FetchEvent("fetch", {});
+                } catch (error) {
+                    errors.push(error.name);
+                }
+
+                const listener = () => {};
+
+                addEventListener("fetch", listener);
+
+                try {
+                    await ___fetchEventHandler(new Request("https://test.test/"), {}, new ExecutionContext());
+                } catch (error) {
+                    errors.push(error.message);
+                }
+
+                removeEventListener("fetch", listener);
+                errors.push(typeof ___fetchEventHandler);
+
+                return errors;
+            };
+            "#,
+        )?;
+
+        assert_eq!(
+            r#"["Failed to execute 'respondWith' on 'FetchEvent': The event has already been responded to.","TypeError","No fetch event listener called respondWith()","undefined"]"#,
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pass_through_on_exception() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            globalThis.___fetcher = (request) => JSON.stringify({
+                status: 200,
+                headers: JSON.stringify({}),
+                body: Array.from(new TextEncoder().encode(`origin ${request.url}`)),
+            });
+
+            var handler = async () => {
+                const messages = [];
+
+                const response = await ___handleResponse(
+                    (_request, _env, ctx) => {
+                        ctx.passThroughOnException();
+
+                        throw new Error("Handler failure");
+                    },
+                    new Request("https://test.test/"),
+                    JSON.stringify({}),
+                );
+
+                messages.push(new TextDecoder().decode(response.body));
+
+                try {
+                    await ___handleResponse(
+                        () => {
+                            throw new Error("Handler failure");
+                        },
+                        new Request("https://test.test/"),
+                        JSON.stringify({}),
+                    );
+                } catch (error) {
+                    messages.push(error.message);
+                }
+
+                return messages;
+            };
+            "#,
+        )?;
+
+        assert_eq!(
+            r#"["origin https://test.test/","Handler failure"]"#,
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_handle_response_invalid_response() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            var handler = async () => {
+                const errors = [];
+
+                for (const value of [undefined, 42, new Map()]) {
+                    try {
+                        await ___handleResponse(
+                            () => value,
+                            new Request("https://test.test/"),
+                            JSON.stringify({}),
+                        );
+                    } catch (error) {
+                        errors.push(`${error.___kind}: ${error.message}`);
+                    }
+                }
+
+                return errors;
+            };
+            "#,
+        )?;
+
+        assert_eq!(
+            json!([
+                "invalid_response: The handler returned undefined instead of a Response, a string or a plain object",
+                "invalid_response: The handler returned a number instead of a Response, a string or a plain object",
+                "invalid_response: The handler returned an instance of Map instead of a Response, a string or a plain object",
+            ])
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_handle_response_values() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            var handler = async () => {
+                const responses = [];
+
+                for (const value of [
+                    "Hello",
+                    { hello: "world" },
+                    Response.json([1, 2], { status: 201 }),
+                    new Response(null, { status: 204 }),
+                ]) {
+                    const response = await ___handleResponse(
+                        () => value,
+                        new Request("https://test.test/"),
+                        JSON.stringify({}),
+                    );
+
+                    responses.push({
+                        body: new TextDecoder().decode(response.body),
+                        contentLength: response.headers["content-length"] ?? null,
+                        contentType: response.headers["content-type"] ?? null,
+                        status: response.status,
+                    });
+                }
+
+                return responses;
+            };
+            "#,
+        )?;
+
+        assert_eq!(
+            json!([
+                { "body": "Hello", "contentLength": "5", "contentType": "text/plain;charset=UTF-8", "status": 200 },
+                { "body": r#"{"hello":"world"}"#, "contentLength": "17", "contentType": "application/json", "status": 200 },
+                { "body": "[1,2]", "contentLength": "5", "contentType": "application/json", "status": 201 },
+                { "body": "", "contentLength": null, "contentType": null, "status": 204 },
+            ])
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_handle_response_stream_body() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            var handler = async () => {
+                const body = new ReadableStream({
+                    start(controller) {
+                        controller.enqueue(new TextEncoder().encode("Hello "));
+                        controller.enqueue("streamed ");
+                        controller.enqueue(new TextEncoder().encode("World"));
+                        controller.close();
+                    },
+                });
+                const response = await ___handleResponse(
+                    () => new Response(body),
+                    new Request("https://test.test/"),
+                    JSON.stringify({}),
+                );
+
+                return [
+                    new TextDecoder().decode(response.body),
+                    response.headers["content-length"] ?? null,
+                ];
+            };
+            "#,
+        )?;
+
+        assert_eq!(r#"["Hello streamed World",null]"#, ctx.get_handler_value()?);
+
+        Ok(())
+    }
+
+    // @see: https://developers.cloudflare.com/workers/runtime-apis/websockets/
+    #[test]
+    fn test_websocket_pair() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            var sent = [];
+            var upgraded = false;
+
+            globalThis.___websocket = {
+                send: (message) => sent.push(Object.values(JSON.parse(message))),
+                upgrade: () => {
+                    upgraded = true;
+                },
+            };
+
+            var handler = async () => {
+                const events = [];
+                const response = await ___handleResponse(
+                    () => {
+                        const [client, server] = Object.values(new WebSocketPair());
+
+                        server.accept();
+                        server.addEventListener("message", (event) => {
+                            if (event.data === "close") {
+                                server.close(3000, "Done");
+                            } else {
+                                events.push(event.data instanceof ArrayBuffer ? Array.from(new Uint8Array(event.data)) : event.data);
+                                server.send(event.data);
+                            }
+                        });
+                        server.addEventListener("close", (event) => {
+                            events.push([event.code, event.reason, event.wasClean, server.readyState]);
+                        });
+
+                        return new Response(null, { status: 101, webSocket: client });
+                    },
+                    new Request("https://test.test/"),
+                    JSON.stringify({}),
+                );
+
+                ___dispatchWebSocketMessage(JSON.stringify({ type: "text", data: "Hello" }));
+                ___dispatchWebSocketMessage(JSON.stringify({ type: "binary", data: [1, 2, 3] }));
+                ___dispatchWebSocketMessage(JSON.stringify({ type: "text", data: "close" }));
+                ___dispatchWebSocketMessage(JSON.stringify({ type: "close", code: 3000, reason: "Done" }));
+
+                return {
+                    events,
+                    sent,
+                    status: response.status,
+                    upgraded,
+                    webSocket: response.webSocket,
+                };
+            };
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "events": ["Hello", [1, 2, 3], [3000, "Done", true, 3]],
+                "sent": [["text", "Hello"], ["binary", [1, 2, 3]], ["close", 3000, "Done"]],
+                "status": 101,
+                "upgraded": true,
+                "webSocket": true,
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_websocket_errors() -> Result<()> {
+        let mut ctx = Context::new();
+
+        ctx.eval(
+            r#"
+            globalThis.___websocket = { send: () => {}, upgrade: () => {} };
+
+            function error(fn) {
+                try {
+                    fn();
+                } catch (error) {
+                    return error.name;
+                }
+            }
+
+            var handler = async () => {
+                const [client, server] = Object.values(new WebSocketPair());
+                const errors = {
+                    acceptClient: error(() => client.accept()),
+                    closeCode: error(() => {
+                        server.accept();
+                        server.close(1001);
+                    }),
+                    closeReason: error(() => server.close(1000, "a".repeat(124))),
+                    constructor: error(() => new WebSocket("wss://test.test/")),
+                    response: error(() => new Response(null, { webSocket: client })),
+                };
+
+                try {
+                    await ___handleResponse(
+                        () => new Response(null, { status: 101, webSocket: new WebSocketPair()[0] }),
+                        new Request("https://test.test/"),
+                        JSON.stringify({}),
+                    );
+                } catch (error) {
+                    errors.notAccepted = error.message;
+                }
+
+                server.close();
+                errors.sendClosed = error(() => server.send("Hello"));
+
+                return errors;
+            };
+            "#,
+        )?;
+
+        assert_eq!(
+            json!({
+                "acceptClient": "TypeError",
+                "closeCode": "InvalidAccessError",
+                "closeReason": "SyntaxError",
+                "constructor": "TypeError",
+                "notAccepted": "The server end of the WebSocketPair must be accepted before returning the client end",
+                "response": "TypeError",
+                "sendClosed": "TypeError",
+            })
+            .to_string(),
+            ctx.get_handler_value()?
+        );
+
+        Ok(())
+    }
+}
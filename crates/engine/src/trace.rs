@@ -0,0 +1,17 @@
+use crate::fetch::mem::ToMem;
+
+extern "C" {
+    fn import_span_enter(ptr: *const u8);
+    fn import_span_exit();
+}
+
+// Runs the function inside of a host span, so the host can trace where the time goes
+pub fn span<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    unsafe { import_span_enter(name.to_mem()) };
+
+    let result = f();
+
+    unsafe { import_span_exit() };
+
+    result
+}
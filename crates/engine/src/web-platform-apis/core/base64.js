@@ -0,0 +1,46 @@
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/btoa
+// @see: https://html.spec.whatwg.org/multipage/webappapis.html#atob
+function btoa(data) {
+    if (arguments.length === 0) {
+        throw new TypeError(
+            "Failed to execute 'btoa': 1 argument required, but only 0 present.",
+        );
+    }
+
+    data = String(data);
+
+    if (/[^\u0000-\u00ff]/.test(data)) {
+        throw new DOMException(
+            "Failed to execute 'btoa': The string to be encoded contains characters outside of the Latin1 range.",
+            "InvalidCharacterError",
+        );
+    }
+
+    return ___base64Encode(data);
+}
+
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/atob
+function atob(data) {
+    if (arguments.length === 0) {
+        throw new TypeError(
+            "Failed to execute 'atob': 1 argument required, but only 0 present.",
+        );
+    }
+
+    data = String(data);
+
+    // The characters outside of the ASCII range are never valid, and aren't sent to Rust
+    const decoded = /[^\u0000-\u007f]/.test(data) ? null : ___base64Decode(data);
+
+    if (decoded === null) {
+        throw new DOMException(
+            "Failed to execute 'atob': The string to be decoded is not correctly encoded.",
+            "InvalidCharacterError",
+        );
+    }
+
+    return decoded;
+}
+
+globalThis.atob = atob;
+globalThis.btoa = btoa;
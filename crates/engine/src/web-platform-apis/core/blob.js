@@ -1,15 +1,18 @@
-const ___parts = Symbol();
-const ___endings = Symbol();
+const ___bytes = Symbol();
 
 // 64 KiB (same size chrome slice theirs blob into Uint8array's)
 const POOL_SIZE = 65536;
 
+// The bytes of the empty blobs, like the ones created by `slice`
+let emptyBytes;
+
 // @see: https://developer.mozilla.org/en-US/docs/Web/API/Blob/Blob
+// @see: https://w3c.github.io/FileAPI/#blob-section
 // @see: https://github.com/node-fetch/fetch-blob/blob/57e4daef36081936581d14509b6cc618d87ab9e2/index.js
 class Blob {
     constructor(blobParts, options) {
-        this[___parts] = [];
-        this[___endings] = "transparent";
+        // The bytes are stored in Rust, as a range of an immutable buffer shared with the slices
+        const parts = [];
 
         this.size = 0;
         this.type = "";
@@ -31,28 +34,26 @@ class Blob {
                 let part;
 
                 if (ArrayBuffer.isView(element)) {
-                    part = new Uint8Array(
-                        element.buffer.slice(
+                    part = {
+                        bytes: element.buffer.slice(
                             element.byteOffset,
                             element.byteOffset + element.byteLength,
                         ),
-                    );
+                    };
                 } else if (element instanceof ArrayBuffer) {
-                    part = new Uint8Array(element.slice(0));
+                    part = { bytes: element };
                 } else if (element instanceof Blob) {
-                    part = element;
+                    part = element[___bytes];
                 } else {
-                    part = new TextEncoder().encode(`${element}`);
+                    part = { bytes: new TextEncoder().encode(`${element}`).buffer };
                 }
 
-                const size = ArrayBuffer.isView(part)
-                    ? part.byteLength
-                    : part.size;
+                const size = part.bytes ? part.bytes.byteLength : part.end - part.start;
 
-                // Avoid pushing empty parts into the array to better GC them
+                // Avoid sending empty parts to Rust
                 if (size) {
                     this.size += size;
-                    this[___parts].push(part);
+                    parts.push(part);
                 }
             }
         }
@@ -69,57 +70,51 @@ class Blob {
 
         if (options === null || options === undefined) options = {};
 
-        this[___endings] = `${
-            options.endings === undefined ? "transparent" : options.endings
-        }`;
         const type = options.type === undefined ? "" : String(options.type);
-        this.type = /^[\x20-\x7E]*$/.test(type) ? type : "";
+        this.type = /^[\x20-\x7E]*$/.test(type) ? type.toLowerCase() : "";
+
+        if (parts.length === 0) {
+            emptyBytes ??= bytesOf([], 0);
+            this[___bytes] = emptyBytes;
+        } else if (parts.length === 1 && !parts[0].bytes) {
+            // A single blob is shared instead of copied
+            this[___bytes] = parts[0];
+        } else {
+            this[___bytes] = bytesOf(parts, this.size);
+        }
     }
 
     async text() {
-        // More optimized than using this.arrayBuffer()
-        // that requires twice as much ram
-        const decoder = new TextDecoder();
-
-        let str = "";
+        const { id, start, end } = this[___bytes];
 
-        for await (const part of toIterator(this[___parts], false)) {
-            str += decoder.decode(part);
-        }
-        // Remaining
-        str += decoder.decode();
-
-        return str;
+        return ___blob.text(id, start, end);
     }
 
     async arrayBuffer() {
-        // Easier way... Just a unnecessary overhead
-        // const view = new Uint8Array(this.size);
-        // await this.stream().getReader({mode: 'byob'}).read(view);
-        // return view.buffer;
-
-        const data = new Uint8Array(this.size);
-        let offset = 0;
-        for await (const chunk of toIterator(this[___parts], false)) {
-            data.set(chunk, offset);
-            offset += chunk.length;
-        }
+        const { id, start, end } = this[___bytes];
 
-        return data.buffer;
+        return ___blob.read(id, start, end);
     }
 
+    // The chunks are only copied from Rust when they are read
     stream() {
-        const it = toIterator(this[___parts], true);
+        // The stream keeps the handle of the bytes, which the blob can outlive
+        const bytes = this[___bytes];
+        let position = bytes.start;
 
         return new ReadableStream({
             type: "bytes",
-            async pull(ctrl) {
-                const chunk = await it.next();
-                chunk.done ? ctrl.close() : ctrl.enqueue(chunk.value);
-            },
+            pull(ctrl) {
+                if (position >= bytes.end) {
+                    ctrl.close();
+
+                    return;
+                }
+
+                const chunkEnd = Math.min(position + POOL_SIZE, bytes.end);
 
-            async cancel() {
-                await it.return();
+                ctrl.enqueue(new Uint8Array(___blob.read(bytes.id, position, chunkEnd)));
+                position = chunkEnd;
             },
         });
     }
@@ -127,90 +122,31 @@ class Blob {
     slice(start = 0, end = this.size, type = "") {
         const { size } = this;
 
-        let relativeStart =
+        const relativeStart =
             start < 0 ? Math.max(size + start, 0) : Math.min(start, size);
-        let relativeEnd =
+        const relativeEnd =
             end < 0 ? Math.max(size + end, 0) : Math.min(end, size);
-
         const span = Math.max(relativeEnd - relativeStart, 0);
-        const parts = this[___parts];
-        const blobParts = [];
-        let added = 0;
-
-        for (const part of parts) {
-            // don't add the overflow to new blobParts
-            if (added >= span) {
-                break;
-            }
-
-            const size = ArrayBuffer.isView(part) ? part.byteLength : part.size;
-            if (relativeStart && size <= relativeStart) {
-                // Skip the beginning and change the relative
-                // start & end position as we skip the unwanted parts
-                relativeStart -= size;
-                relativeEnd -= size;
-            } else {
-                let chunk;
-                if (ArrayBuffer.isView(part)) {
-                    chunk = part.subarray(
-                        relativeStart,
-                        Math.min(size, relativeEnd),
-                    );
-                    added += chunk.byteLength;
-                } else {
-                    chunk = part.slice(
-                        relativeStart,
-                        Math.min(size, relativeEnd),
-                    );
-                    added += chunk.size;
-                }
-                relativeEnd -= size;
-                blobParts.push(chunk);
-                relativeStart = 0; // All next sequential parts should start at 0
-            }
-        }
+        const bytes = this[___bytes];
 
         const blob = new Blob([], { type: String(type).toLowerCase() });
         blob.size = span;
-        blob[___parts] = blobParts;
+        blob[___bytes] = {
+            id: bytes.id,
+            handle: bytes.handle,
+            start: bytes.start + relativeStart,
+            end: bytes.start + relativeStart + span,
+        };
 
         return blob;
     }
 }
 
-globalThis.Blob = Blob;
+// The bytes are freed once the handle is collected, with the blob and its slices
+function bytesOf(parts, size) {
+    const [id, handle] = ___blob.create(parts);
 
-async function* toIterator(parts, clone = true) {
-    for (const part of parts) {
-        if ("stream" in part) {
-            yield* part.stream();
-        } else if (ArrayBuffer.isView(part)) {
-            if (clone) {
-                let position = part.byteOffset;
-                const end = part.byteOffset + part.byteLength;
-                while (position !== end) {
-                    const size = Math.min(end - position, POOL_SIZE);
-                    const chunk = part.buffer.slice(position, position + size);
-                    position += chunk.byteLength;
-                    yield new Uint8Array(chunk);
-                }
-            } else {
-                yield part;
-            }
-            /* c8 ignore next 10 */
-        } else {
-            // For blobs that have arrayBuffer but no stream method (nodes buffer.Blob)
-            let position = 0;
-            let b = part;
-            while (position !== b.size) {
-                const chunk = b.slice(
-                    position,
-                    Math.min(b.size, position + POOL_SIZE),
-                );
-                const buffer = await chunk.arrayBuffer();
-                position += buffer.byteLength;
-                yield new Uint8Array(buffer);
-            }
-        }
-    }
+    return { id, handle, start: 0, end: size };
 }
+
+globalThis.Blob = Blob;
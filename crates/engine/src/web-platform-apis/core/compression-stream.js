@@ -0,0 +1,73 @@
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/Compression_Streams_API
+// @see: https://compression.spec.whatwg.org/
+(function () {
+    const ___compression = globalThis.___compression;
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/CompressionStream
+    class CompressionStream {
+        constructor(format) {
+            if (arguments.length === 0) {
+                throw new TypeError(
+                    "Failed to construct 'CompressionStream': 1 argument required, but only 0 present.",
+                );
+            }
+
+            const transform = compressionTransform(___compression.compressor(String(format)));
+
+            Object.defineProperties(this, {
+                readable: { value: transform.readable, enumerable: true },
+                writable: { value: transform.writable, enumerable: true },
+            });
+        }
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/DecompressionStream
+    class DecompressionStream {
+        constructor(format) {
+            if (arguments.length === 0) {
+                throw new TypeError(
+                    "Failed to construct 'DecompressionStream': 1 argument required, but only 0 present.",
+                );
+            }
+
+            const transform = compressionTransform(___compression.decompressor(String(format)));
+
+            Object.defineProperties(this, {
+                readable: { value: transform.readable, enumerable: true },
+                writable: { value: transform.writable, enumerable: true },
+            });
+        }
+    }
+
+    // The chunks are compressed or decompressed by the Rust stream with the id, an invalid chunk
+    // errors both sides of the transform
+    function compressionTransform(id) {
+        return new TransformStream({
+            transform(chunk, controller) {
+                const output = ___compression.write(id, ...bufferSource(chunk));
+
+                if (output.byteLength) controller.enqueue(new Uint8Array(output));
+            },
+            flush(controller) {
+                const output = ___compression.finish(id);
+
+                if (output.byteLength) controller.enqueue(new Uint8Array(output));
+            },
+        });
+    }
+
+    function bufferSource(chunk) {
+        if (chunk instanceof ArrayBuffer) {
+            return [chunk, 0, chunk.byteLength];
+        }
+
+        if (ArrayBuffer.isView(chunk)) {
+            return [chunk.buffer, chunk.byteOffset, chunk.byteLength];
+        }
+
+        throw new TypeError("The provided value is not of type '(ArrayBuffer or ArrayBufferView)'");
+    }
+
+    globalThis.CompressionStream = CompressionStream;
+    globalThis.DecompressionStream = DecompressionStream;
+})();
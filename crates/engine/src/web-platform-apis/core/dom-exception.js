@@ -0,0 +1,47 @@
+// The legacy codes of the error names which have one
+// @see: https://webidl.spec.whatwg.org/#dfn-error-names-table
+const CODES = {
+    IndexSizeError: 1,
+    HierarchyRequestError: 3,
+    WrongDocumentError: 4,
+    InvalidCharacterError: 5,
+    NoModificationAllowedError: 7,
+    NotFoundError: 8,
+    NotSupportedError: 9,
+    InvalidStateError: 11,
+    SyntaxError: 12,
+    InvalidModificationError: 13,
+    NamespaceError: 14,
+    InvalidAccessError: 15,
+    TypeMismatchError: 17,
+    SecurityError: 18,
+    NetworkError: 19,
+    AbortError: 20,
+    URLMismatchError: 21,
+    QuotaExceededError: 22,
+    TimeoutError: 23,
+    InvalidNodeTypeError: 24,
+    DataCloneError: 25,
+};
+
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/DOMException
+// @see: https://webidl.spec.whatwg.org/#idl-DOMException
+class DOMException extends Error {
+    constructor(message = "", name = "Error") {
+        super(String(message));
+
+        // The name is an own property, like the message, so the errors are logged with it
+        Object.defineProperty(this, "name", {
+            value: String(name),
+            configurable: true,
+            writable: true,
+        });
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/DOMException/code
+    get code() {
+        return CODES[this.name] ?? 0;
+    }
+}
+
+globalThis.DOMException = DOMException;
@@ -0,0 +1,35 @@
+const ___name = Symbol();
+const ___lastModified = Symbol();
+
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/File/File
+// @see: https://w3c.github.io/FileAPI/#file-section
+class File extends Blob {
+    constructor(fileBits, fileName, options) {
+        if (arguments.length < 2) {
+            throw new TypeError(
+                `Failed to construct 'File': 2 arguments required, but only ${arguments.length} present.`,
+            );
+        }
+
+        super(fileBits, options);
+
+        this[___name] = String(fileName);
+        this[___lastModified] =
+            options?.lastModified === undefined
+                ? Date.now()
+                : Number(options.lastModified);
+    }
+
+    // The name and the last modification are getters, so `File` serializes like a `Blob`
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/File/name
+    get name() {
+        return this[___name];
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/File/lastModified
+    get lastModified() {
+        return this[___lastModified];
+    }
+}
+
+globalThis.File = File;
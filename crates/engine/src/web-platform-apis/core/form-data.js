@@ -1,9 +1,11 @@
-const ___formData = Symbol();
+const ___entries = Symbol();
 
 // @see: https://developer.mozilla.org/en-US/docs/Web/API/FormData
+// @see: https://xhr.spec.whatwg.org/#interface-formdata
 class FormData {
     constructor(form) {
-        this[___formData] = [];
+        // Each entry is `[name, value]`, with the value as a string or a `File`
+        this[___entries] = [];
 
         if (form !== undefined) {
             throw new TypeError(
@@ -13,97 +15,151 @@ class FormData {
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/FormData/append
-    append(key, value) {
-        if (this.has(key)) {
-            this[___formData] = this[___formData].map((pair) => {
-                if (pair[0] === key) {
-                    const oldValue = pair[1];
-                    const newValue = stringifyValue(value);
-
-                    if (Array.isArray(oldValue)) {
-                        oldValue.push(newValue);
-                        return [key, oldValue];
-                    } else {
-                        return [key, [oldValue, newValue]];
-                    }
-                } else {
-                    return pair;
-                }
-            });
-        } else {
-            this[___formData].push([key, stringifyValue(value)]);
-        }
+    append(name, value, filename) {
+        this[___entries].push(toEntry("append", arguments.length, name, value, filename));
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/FormData/get
-    get(key) {
-        const result = this[___formData].find((pair) => pair[0] === key);
-
-        if (result === undefined) {
-            return null;
-        }
-
-        const value = result[1];
+    get(name) {
+        const entry = this[___entries].find((entry) => entry[0] === String(name));
 
-        if (value === undefined) {
-            return null;
-        }
-
-        return Array.isArray(value) ? value[0] : value;
+        return entry === undefined ? null : entry[1];
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/FormData/getAll
-    getAll(key) {
-        const result = this[___formData].find((pair) => pair[0] === key);
-
-        if (result === undefined) {
-            return [];
-        }
-
-        return result[1];
+    getAll(name) {
+        return this[___entries]
+            .filter((entry) => entry[0] === String(name))
+            .map((entry) => entry[1]);
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/FormData/has
-    has(key) {
-        return this[___formData].some((pair) => pair[0] === key);
+    has(name) {
+        return this[___entries].some((entry) => entry[0] === String(name));
     }
 
+    // The first entry with the name is replaced, and the other ones are removed
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/FormData/set
-    set(key, value) {
-        if (this.has(key)) {
-            this[___formData] = this[___formData].map((pair) =>
-                pair[0] === key ? [key, stringifyValue(value)] : pair,
-            );
-        } else {
-            this[___formData].push([key, stringifyValue(value)]);
+    set(name, value, filename) {
+        const entry = toEntry("set", arguments.length, name, value, filename);
+        const index = this[___entries].findIndex((other) => other[0] === entry[0]);
+
+        if (index === -1) {
+            this[___entries].push(entry);
+
+            return;
         }
+
+        this[___entries] = this[___entries].filter(
+            (other, otherIndex) => otherIndex <= index || other[0] !== entry[0],
+        );
+        this[___entries][index] = entry;
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/FormData/delete
-    delete(key) {
-        this[___formData] = this[___formData].filter((pair) => pair[0] !== key);
+    delete(name) {
+        this[___entries] = this[___entries].filter((entry) => entry[0] !== String(name));
     }
 
-    // @see: https://developer.mozilla.org/en-US/docs/Web/API/FormData/forEach
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/FormData/entries
     entries() {
-        return this[___formData][Symbol.iterator]();
+        return this[___entries].map((entry) => [...entry])[Symbol.iterator]();
     }
 
-    // @see: https://developer.mozilla.org/en-US/docs/Web/API/FormData/forEach
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/FormData/keys
     keys() {
-        return this[___formData].map((pair) => pair[0])[Symbol.iterator]();
+        return this[___entries].map((entry) => entry[0])[Symbol.iterator]();
     }
 
-    // @see: https://developer.mozilla.org/en-US/docs/Web/API/FormData/forEach
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/FormData/values
     values() {
-        return this[___formData].map((pair) => pair[1])[Symbol.iterator]();
+        return this[___entries].map((entry) => entry[1])[Symbol.iterator]();
+    }
+
+    forEach(callback, thisArg) {
+        for (const [name, value] of this.entries()) {
+            callback.call(thisArg, value, name, this);
+        }
+    }
+
+    [Symbol.iterator]() {
+        return this.entries();
     }
 }
 
 globalThis.FormData = FormData;
 
-function stringifyValue(value) {
-    return typeof value === "string" || value instanceof Blob
-        ? value
-        : String(value);
+/**
+ * Serializes the entries as a `multipart/form-data` body, in Rust, returning an `ArrayBuffer`.
+ * The `File` values are sent with their name and type.
+ */
+export async function ___encodeFormData(formData, boundary) {
+    const parts = [];
+
+    for (const [name, value] of formData[___entries]) {
+        if (typeof value === "string") {
+            parts.push({ name, data: new TextEncoder().encode(value).buffer });
+        } else {
+            parts.push({
+                name,
+                data: await value.arrayBuffer(),
+                filename: value.name,
+                type: value.type,
+            });
+        }
+    }
+
+    return ___multipart.encode(parts, boundary);
+}
+
+// Appends the parts parsed by `___multipart`, whose files have their value as an `ArrayBuffer`
+export function ___appendParts(formData, parts) {
+    for (const part of parts) {
+        if (part.filename === undefined) {
+            formData.append(part.name, part.value);
+        } else {
+            formData.append(
+                part.name,
+                new File([part.value], part.filename, { type: part.type }),
+            );
+        }
+    }
+}
+
+// A new `FormData` with the same entries, which can be modified apart from the other one
+export function ___copyFormData(formData) {
+    const copy = new FormData();
+
+    copy[___entries] = formData[___entries].map((entry) => [...entry]);
+
+    return copy;
+}
+
+// @see: https://xhr.spec.whatwg.org/#create-an-entry
+function toEntry(method, length, name, value, filename) {
+    if (length < 2) {
+        throw new TypeError(
+            `Failed to execute '${method}' on 'FormData': 2 arguments required, but only ${length} present.`,
+        );
+    }
+
+    // A `Blob` is wrapped in a `File` named "blob", which shares its bytes
+    if (value instanceof Blob) {
+        if (!(value instanceof File) || filename !== undefined) {
+            value = new File([value], filename ?? value.name ?? "blob", {
+                type: value.type,
+                lastModified: value.lastModified,
+            });
+        }
+
+        return [String(name), value];
+    }
+
+    if (length > 2) {
+        throw new TypeError(
+            `Failed to execute '${method}' on 'FormData': parameter 2 is not of type 'Blob'.`,
+        );
+    }
+
+    return [String(name), String(value)];
 }
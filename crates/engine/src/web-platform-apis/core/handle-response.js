@@ -1,16 +1,122 @@
-globalThis.___handleResponse = async function (request) {
-    const response = await handleRequest(request);
-    const body = await response.arrayBuffer();
+import { ___passThroughOnException } from "../workers/execution-context.js";
+import { ___hasStreamBody } from "../fetch-api/body.js";
+import { ___upgradeWebSocket } from "../workers/websocket.js";
+
+// @see: https://fetch.spec.whatwg.org/#null-body-status
+const NULL_BODY_STATUSES = [101, 103, 204, 205, 304];
+
+globalThis.___handleResponse = async function (
+    handleRequest,
+    request,
+    bindings,
+) {
+    const env = ___createEnv(bindings);
+    const ctx = new ExecutionContext();
+    let response;
+
+    try {
+        response = await handleRequest(request, env, ctx);
+    } catch (error) {
+        if (!ctx[___passThroughOnException]) {
+            throw error;
+        }
+
+        console.error("Uncaught (passed through to the origin)", error);
+
+        response = await fetch(request);
+    }
+
+    response = toResponse(response);
+
+    const isStream = ___hasStreamBody(response);
+    const body = toBytes(await response.arrayBuffer());
+    const headers = response.headers.getAll();
+
+    // The length of a stream body is only known once it is read, so it is left to the host
+    if (
+        !isStream &&
+        !("content-length" in headers) &&
+        !NULL_BODY_STATUSES.includes(response.status)
+    ) {
+        headers["content-length"] = String(body.byteLength);
+    }
+
+    // The host upgrades the connection, then relays the messages to the server end of the pair
+    if (response.webSocket) {
+        ___upgradeWebSocket(response.webSocket);
+    }
 
     return {
         body: body.buffer,
         bodyUsed: response.bodyUsed,
-        headers: response.headers.getAll(),
+        headers,
         ok: response.ok,
         redirected: response.redirected,
         status: response.status,
         statusText: response.statusText,
         type: response.type,
         url: response.url,
+        ...(response.webSocket && { webSocket: true }),
     };
-}
\ No newline at end of file
+}
+
+// A string is sent as text, and a plain object or array as JSON, as `Response.json` does
+function toResponse(value) {
+    if (value instanceof Response) {
+        return value;
+    }
+
+    if (typeof value === "string") {
+        return new Response(value);
+    }
+
+    if (Array.isArray(value) || isPlainObject(value)) {
+        return Response.json(value);
+    }
+
+    const error = new TypeError(
+        `The handler returned ${describe(value)} instead of a Response, a string or a plain object`,
+    );
+
+    // Read by the engine, so the host responds with the status of an invalid response
+    error.___kind = "invalid_response";
+
+    throw error;
+}
+
+// A copy is only made for the views on a part of their buffer, so `body.buffer` is the body
+function toBytes(body) {
+    if (body === null || body === undefined) {
+        return new Uint8Array(0);
+    }
+
+    if (body instanceof ArrayBuffer) {
+        return new Uint8Array(body);
+    }
+
+    if (body.byteOffset === 0 && body.byteLength === body.buffer.byteLength) {
+        return body;
+    }
+
+    return new Uint8Array(body.buffer.slice(body.byteOffset, body.byteOffset + body.byteLength));
+}
+
+function isPlainObject(value) {
+    if (typeof value !== "object" || value === null) {
+        return false;
+    }
+
+    const prototype = Object.getPrototypeOf(value);
+
+    return prototype === Object.prototype || prototype === null;
+}
+
+function describe(value) {
+    if (value === null || value === undefined) {
+        return String(value);
+    }
+
+    return typeof value === "object"
+        ? `an instance of ${value.constructor?.name ?? "Object"}`
+        : `a ${typeof value}`;
+}
@@ -0,0 +1,167 @@
+// The typed arrays, cloned with their buffer
+const TYPED_ARRAYS = [
+    Int8Array,
+    Uint8Array,
+    Uint8ClampedArray,
+    Int16Array,
+    Uint16Array,
+    Int32Array,
+    Uint32Array,
+    Float32Array,
+    Float64Array,
+    BigInt64Array,
+    BigUint64Array,
+];
+
+// The errors keep their type when they are cloned
+const ERRORS = [EvalError, RangeError, ReferenceError, SyntaxError, TypeError, URIError];
+
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/structuredClone
+// @see: https://html.spec.whatwg.org/multipage/structured-data.html#structuredclone
+function structuredClone(value, options) {
+    if (arguments.length === 0) {
+        throw new TypeError(
+            "Failed to execute 'structuredClone': 1 argument required, but only 0 present.",
+        );
+    }
+
+    // The transferred buffers can't be detached, they are copied like the others
+    if (options?.transfer !== undefined && typeof options.transfer[Symbol.iterator] !== "function") {
+        throw new TypeError(
+            "Failed to execute 'structuredClone': The provided value cannot be converted to a sequence.",
+        );
+    }
+
+    // The objects already cloned, so the shared references and the cycles are kept
+    return clone(value, new Map());
+}
+
+function clone(value, memory) {
+    if (typeof value === "function" || typeof value === "symbol") {
+        throw dataCloneError(value);
+    }
+
+    if (typeof value !== "object" || value === null) {
+        return value;
+    }
+
+    if (memory.has(value)) {
+        return memory.get(value);
+    }
+
+    const cloned = cloneObject(value, memory);
+
+    memory.set(value, cloned.value);
+    cloned.fill?.();
+
+    return cloned.value;
+}
+
+// Returns the clone, and for the containers a function filling it once it is in the memory
+function cloneObject(value, memory) {
+    if (value instanceof Boolean) return { value: new Boolean(value.valueOf()) };
+    if (value instanceof Number) return { value: new Number(value.valueOf()) };
+    if (value instanceof String) return { value: new String(value.valueOf()) };
+    if (value instanceof BigInt) return { value: Object(value.valueOf()) };
+    if (value instanceof Date) return { value: new Date(value.getTime()) };
+    if (value instanceof RegExp) return { value: new RegExp(value.source, value.flags) };
+    if (value instanceof ArrayBuffer) return { value: value.slice(0) };
+
+    if (ArrayBuffer.isView(value)) {
+        const buffer = clone(value.buffer, memory);
+
+        if (value instanceof DataView) {
+            return { value: new DataView(buffer, value.byteOffset, value.byteLength) };
+        }
+
+        const TypedArray = TYPED_ARRAYS.find((type) => value instanceof type);
+
+        return { value: new TypedArray(buffer, value.byteOffset, value.length) };
+    }
+
+    // The bytes are immutable, so the clone shares them
+    if (value instanceof File) {
+        return {
+            value: new File([value], value.name, {
+                lastModified: value.lastModified,
+                type: value.type,
+            }),
+        };
+    }
+
+    if (value instanceof Blob) return { value: value.slice(0, value.size, value.type) };
+
+    if (value instanceof Error) {
+        const Type = ERRORS.find((type) => value instanceof type) ?? Error;
+        const error = Object.create(Type.prototype);
+
+        for (const property of ["message", "stack"]) {
+            if (Object.prototype.hasOwnProperty.call(value, property)) {
+                Object.defineProperty(error, property, {
+                    value: String(value[property]),
+                    configurable: true,
+                    writable: true,
+                });
+            }
+        }
+
+        return { value: error };
+    }
+
+    if (value instanceof Map) {
+        const map = new Map();
+
+        return {
+            value: map,
+            fill: () => {
+                for (const [key, entry] of value) {
+                    map.set(clone(key, memory), clone(entry, memory));
+                }
+            },
+        };
+    }
+
+    if (value instanceof Set) {
+        const set = new Set();
+
+        return {
+            value: set,
+            fill: () => {
+                for (const entry of value) {
+                    set.add(clone(entry, memory));
+                }
+            },
+        };
+    }
+
+    // Only the arrays and the plain objects are cloned with their own enumerable properties, the
+    // other objects, e.g. a `Promise`, a `Headers` or an instance of a class, can't be cloned
+    const prototype = Object.getPrototypeOf(value);
+
+    if (!Array.isArray(value) && prototype !== Object.prototype && prototype !== null) {
+        throw dataCloneError(value);
+    }
+
+    const object = Array.isArray(value) ? new Array(value.length) : {};
+
+    return {
+        value: object,
+        fill: () => {
+            for (const key of Object.keys(value)) {
+                object[key] = clone(value[key], memory);
+            }
+        },
+    };
+}
+
+function dataCloneError(value) {
+    const description =
+        typeof value === "object" ? `#<${value.constructor?.name ?? "Object"}>` : String(value);
+
+    return new DOMException(
+        `Failed to execute 'structuredClone': ${description} could not be cloned.`,
+        "DataCloneError",
+    );
+}
+
+globalThis.structuredClone = structuredClone;
@@ -1,27 +1,25 @@
 // @see: https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder
+// @see: https://encoding.spec.whatwg.org/#interface-textdecoder
 // @see: https://github.com/Shopify/javy/blob/ecbe8cdf302acbff28287370960a0c3e7481a505/crates/core/prelude/text-encoding.js
 (function () {
-    const ___decodeUtf8BufferToString = globalThis.___decodeUtf8BufferToString;
+    const ___textEncoding = globalThis.___textEncoding;
+    const ___decoder = Symbol();
 
     class TextDecoder {
         constructor(label = "utf-8", options = {}) {
-            label = label.trim().toLowerCase();
-            const acceptedLabels = [
-                "utf-8",
-                "utf8",
-                "unicode-1-1-utf-8",
-                "unicode11utf8",
-                "unicode20utf8",
-                "x-unicode20utf8",
-            ];
-            if (!acceptedLabels.includes(label)) {
-                // Not spec-compliant behaviour
+            label = String(label).trim().toLowerCase();
+
+            // The encodings of the Encoding Standard are decoded in Rust, with `encoding_rs`
+            const encoding = ___textEncoding.encoding(label);
+
+            if (encoding === null) {
                 throw new RangeError(
-                    "The encoding label provided must be utf-8",
+                    `Failed to construct 'TextDecoder': The encoding label provided ('${label}') is invalid.`,
                 );
             }
+
             Object.defineProperties(this, {
-                encoding: { value: "utf-8", enumerable: true, writable: false },
+                encoding: { value: encoding, enumerable: true, writable: false },
                 fatal: {
                     value: !!options.fatal,
                     enumerable: true,
@@ -32,18 +30,14 @@
                     enumerable: true,
                     writable: false,
                 },
+                // The id of the Rust decoder while a stream is decoded
+                [___decoder]: { value: null, writable: true },
             });
         }
 
-        decode(input, options = {}) {
-            if (input === undefined) {
-                return "";
-            }
-
-            if (options.stream) {
-                throw new Error("Streaming decode is not supported");
-            }
-
+        // With `{ stream: true }`, an incomplete sequence at the end of the input is decoded with
+        // the next call, and the call without it ends the stream
+        decode(input = new Uint8Array(0), options = {}) {
             // backing buffer would not have byteOffset and may have different byteLength
             let byteOffset = input.byteOffset || 0;
             let byteLength = input.byteLength;
@@ -58,17 +52,78 @@
                 );
             }
 
-            return ___decodeUtf8BufferToString(
-                input,
-                byteOffset,
-                byteLength,
-                this.fatal,
-                this.ignoreBOM,
-            );
+            const stream = !!options?.stream;
+
+            if (!stream && this[___decoder] === null) {
+                return ___textEncoding.decode(
+                    this.encoding,
+                    this.fatal,
+                    this.ignoreBOM,
+                    input,
+                    byteOffset,
+                    byteLength,
+                );
+            }
+
+            if (this[___decoder] === null) {
+                this[___decoder] = ___textEncoding.decoder(
+                    this.encoding,
+                    this.fatal,
+                    this.ignoreBOM,
+                );
+            }
+
+            const decoder = this[___decoder];
+
+            if (!stream) {
+                this[___decoder] = null;
+            }
+
+            try {
+                return ___textEncoding.decodeChunk(
+                    decoder,
+                    input,
+                    byteOffset,
+                    byteLength,
+                    !stream,
+                );
+            } catch (error) {
+                // The Rust decoder is also ended by a malformed chunk
+                this[___decoder] = null;
+
+                throw error;
+            }
         }
     }
 
-    globalThis.TextDecoder = TextDecoder;
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/TextDecoderStream
+    // @see: https://encoding.spec.whatwg.org/#interface-textdecoderstream
+    class TextDecoderStream {
+        constructor(label = "utf-8", options = {}) {
+            const decoder = new TextDecoder(label, options);
+            const transform = new TransformStream({
+                transform(chunk, controller) {
+                    const text = decoder.decode(chunk, { stream: true });
+
+                    if (text) controller.enqueue(text);
+                },
+                flush(controller) {
+                    const text = decoder.decode();
+
+                    if (text) controller.enqueue(text);
+                },
+            });
+
+            Object.defineProperties(this, {
+                encoding: { value: decoder.encoding, enumerable: true },
+                fatal: { value: decoder.fatal, enumerable: true },
+                ignoreBOM: { value: decoder.ignoreBOM, enumerable: true },
+                readable: { value: transform.readable, enumerable: true },
+                writable: { value: transform.writable, enumerable: true },
+            });
+        }
+    }
 
-    Reflect.deleteProperty(globalThis, "___decodeUtf8BufferToString");
+    globalThis.TextDecoder = TextDecoder;
+    globalThis.TextDecoderStream = TextDecoderStream;
 })();
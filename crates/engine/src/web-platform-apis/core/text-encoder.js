@@ -1,7 +1,9 @@
 // @see: https://developer.mozilla.org/en-US/docs/Web/API/TextEncoder
+// @see: https://encoding.spec.whatwg.org/#interface-textencoder
 // @see: https://github.com/Shopify/javy/blob/ecbe8cdf302acbff28287370960a0c3e7481a505/crates/core/prelude/text-encoding.js
 (function () {
     const ___encodeStringToUtf8Buffer = globalThis.___encodeStringToUtf8Buffer;
+    const ___textEncoding = globalThis.___textEncoding;
 
     class TextEncoder {
         constructor() {
@@ -15,12 +17,62 @@
             return new Uint8Array(___encodeStringToUtf8Buffer(input));
         }
 
+        // Writes into the buffer of the destination, in Rust, as many characters as fit
+        // @see: https://developer.mozilla.org/en-US/docs/Web/API/TextEncoder/encodeInto
         encodeInto(source, destination) {
-            throw new Error("encodeInto is not supported");
+            if (!(destination instanceof Uint8Array)) {
+                throw new TypeError(
+                    "Failed to execute 'encodeInto' on 'TextEncoder': parameter 2 is not of type 'Uint8Array'.",
+                );
+            }
+
+            return ___textEncoding.encodeInto(
+                String(source),
+                destination.buffer,
+                destination.byteOffset,
+                destination.byteLength,
+            );
+        }
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/TextEncoderStream
+    // @see: https://encoding.spec.whatwg.org/#interface-textencoderstream
+    class TextEncoderStream {
+        constructor() {
+            const encoder = new TextEncoder();
+            // A high surrogate ending a chunk is encoded with the low one starting the next chunk
+            let pending = "";
+
+            const transform = new TransformStream({
+                transform(chunk, controller) {
+                    let text = pending + String(chunk);
+                    const last = text.charCodeAt(text.length - 1);
+
+                    pending = "";
+
+                    if (last >= 0xd800 && last <= 0xdbff) {
+                        pending = text.slice(-1);
+                        text = text.slice(0, -1);
+                    }
+
+                    if (text) controller.enqueue(encoder.encode(text));
+                },
+                flush(controller) {
+                    // A lone surrogate is encoded as U+FFFD
+                    if (pending) controller.enqueue(encoder.encode(pending));
+                },
+            });
+
+            Object.defineProperties(this, {
+                encoding: { value: "utf-8", enumerable: true },
+                readable: { value: transform.readable, enumerable: true },
+                writable: { value: transform.writable, enumerable: true },
+            });
         }
     }
 
     globalThis.TextEncoder = TextEncoder;
+    globalThis.TextEncoderStream = TextEncoderStream;
 
     Reflect.deleteProperty(globalThis, "___encodeStringToUtf8Buffer");
 })();
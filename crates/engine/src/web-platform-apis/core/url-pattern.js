@@ -0,0 +1,122 @@
+const ___handle = Symbol();
+const ___id = Symbol();
+
+const components = [
+    "protocol",
+    "username",
+    "password",
+    "hostname",
+    "port",
+    "pathname",
+    "search",
+    "hash",
+];
+
+/**
+ * URLPattern
+ *
+ * The URLPattern interface matches URLs or parts of URLs against a pattern. The pattern can contain capturing groups that extract parts of the matched URL.
+ *
+ * The compiled pattern is kept in Rust, which also parses and matches the inputs.
+ *
+ * @see: https://developer.mozilla.org/en-US/docs/Web/API/URLPattern
+ * @see: https://urlpattern.spec.whatwg.org/
+ */
+class URLPattern {
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/URLPattern/URLPattern
+    constructor(input = {}, baseURL = undefined, options = undefined) {
+        // The options can be given instead of the base URL
+        if (typeof baseURL === "object" && baseURL !== null) {
+            baseURL = undefined;
+        }
+
+        // The compiled pattern is freed once the handle is collected with the pattern
+        [this[___id], this[___handle]] = ___urlPattern.create(toInput(input), toBase(baseURL));
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/URLPattern/test
+    test(input = {}, baseURL = undefined) {
+        return ___urlPattern.test(this[___id], toInput(input), toBase(baseURL));
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/URLPattern/exec
+    exec(input = {}, baseURL = undefined) {
+        const result = ___urlPattern.exec(
+            this[___id],
+            toInput(input),
+            toBase(baseURL),
+        );
+
+        if (result === null) {
+            return null;
+        }
+
+        return {
+            inputs: baseURL === undefined ? [input] : [input, baseURL],
+            ...result,
+        };
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/URLPattern/protocol
+    get protocol() {
+        return ___urlPattern.get(this[___id], "protocol");
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/URLPattern/username
+    get username() {
+        return ___urlPattern.get(this[___id], "username");
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/URLPattern/password
+    get password() {
+        return ___urlPattern.get(this[___id], "password");
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/URLPattern/hostname
+    get hostname() {
+        return ___urlPattern.get(this[___id], "hostname");
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/URLPattern/port
+    get port() {
+        return ___urlPattern.get(this[___id], "port");
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/URLPattern/pathname
+    get pathname() {
+        return ___urlPattern.get(this[___id], "pathname");
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/URLPattern/search
+    get search() {
+        return ___urlPattern.get(this[___id], "search");
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/URLPattern/hash
+    get hash() {
+        return ___urlPattern.get(this[___id], "hash");
+    }
+}
+
+globalThis.URLPattern = URLPattern;
+
+// A string, or a `URLPatternInit` dictionary with its members converted to strings
+function toInput(input) {
+    if (typeof input !== "object" || input === null) {
+        return String(input);
+    }
+
+    const init = {};
+
+    for (const name of [...components, "baseURL"]) {
+        if (input[name] !== undefined) {
+            init[name] = String(input[name]);
+        }
+    }
+
+    return init;
+}
+
+function toBase(baseURL) {
+    return baseURL === undefined ? undefined : String(baseURL);
+}
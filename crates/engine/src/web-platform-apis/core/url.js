@@ -1,105 +1,89 @@
-const ___state = Symbol();
-const ___update = Symbol();
+const ___handle = Symbol();
+const ___id = Symbol();
 
 /**
  * URL
  *
  * The URL interface is used to parse, construct, normalize, and encode URLs. It works by providing properties which allow you to easily read and modify the components of a URL.
  *
+ * The parsed URL is kept in Rust, and the attributes read and update it there.
+ *
  * @see: https://developer.mozilla.org/en-US/docs/Web/API/URL
  * @see: https://url.spec.whatwg.org/
  */
 class URL {
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/URL
     constructor(url, base) {
-        if (!url) {
+        if (arguments.length === 0) {
             throw new TypeError(
                 "Failed to construct 'URL': 1 argument required, but only 0 present",
             );
         }
 
-        if (typeof url !== "string") url = String(url);
-        if (base && typeof base !== "string") base = String(base);
-
-        url = url.replace(/[\t\n\r]/g, "").trim();
-
-        let properties = ___parseUrl(url, base || "about:blank");
-
-        this[___state] = {};
+        // The parsed URL is freed once the handle is collected with the URL
+        [this[___id], this[___handle]] = ___url.parse(String(url), toBase(base));
+    }
 
-        this[___update](properties);
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/canParse_static
+    static canParse(url, base) {
+        return ___url.canParse(String(url), toBase(base));
+    }
 
-        this[___state].setHash = properties.setHash;
-        this[___state].setHost = properties.setHost;
-        this[___state].setPassword = properties.setPassword;
-        this[___state].setPathname = properties.setPathname;
-        this[___state].setPort = properties.setPort;
-        this[___state].setProtocol = properties.setProtocol;
-        this[___state].setSearch = properties.setSearch;
-        this[___state].setUsername = properties.setUsername;
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/parse_static
+    static parse(url, base) {
+        return URL.canParse(url, base) ? new URL(url, base) : null;
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/toString
     toString() {
-        return this[___state].href;
+        return this.href;
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/toJSON
     toJSON() {
-        return this[___state].href;
+        return this.href;
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/hash
     get hash() {
-        return this[___state].hash;
+        return ___url.get(this[___id], "hash");
     }
 
     set hash(value) {
-        this[___update](
-            this[___state].setHash(this[___state].href, String(value)),
-        );
+        ___url.set(this[___id], "hash", String(value));
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/host
     get host() {
-        const port = this[___state].port;
-        return this[___state].host + (port ? `:${port}` : "");
+        return ___url.get(this[___id], "host");
     }
 
     set host(value) {
-        value = String(value);
-
-        this[___update](this[___state].setHost(this[___state].href, value));
-
-        if (/:/.test(value)) {
-            const parts = value.split(":");
-            this[___state].port = parts.length === 2 ? parts[1] : "";
-        }
+        ___url.set(this[___id], "host", String(value));
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/hostname
     get hostname() {
-        return this[___state].hostname || this[___state].host;
+        return ___url.get(this[___id], "hostname");
     }
 
     set hostname(value) {
-        this[___update](
-            this[___state].setHost(this[___state].href, String(value)),
-        );
+        ___url.set(this[___id], "hostname", String(value));
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/href
     get href() {
-        return this[___state].href;
+        return ___url.get(this[___id], "href");
     }
 
+    // Throws a `TypeError` for an invalid URL, unlike the other setters
     set href(value) {
-        this[___update](___parseUrl(String(value), "about:blank"));
+        ___url.set(this[___id], "href", String(value));
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/origin
     get origin() {
-        return this[___state].origin;
+        return ___url.get(this[___id], "origin");
     }
 
     // It is read only
@@ -107,69 +91,53 @@ class URL {
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/password
     get password() {
-        return this[___state].password;
+        return ___url.get(this[___id], "password");
     }
 
     set password(value) {
-        this[___update](
-            this[___state].setPassword(this[___state].href, String(value)),
-        );
+        ___url.set(this[___id], "password", String(value));
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/pathname
     get pathname() {
-        return this[___state].pathname;
+        return ___url.get(this[___id], "pathname");
     }
 
     set pathname(value) {
-        this[___update](
-            this[___state].setPathname(this[___state].href, String(value)),
-        );
+        ___url.set(this[___id], "pathname", String(value));
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/port
     get port() {
-        return this[___state].port;
+        return ___url.get(this[___id], "port");
     }
 
     set port(value) {
-        this[___update](
-            this[___state].setPort(this[___state].href, String(value)),
-        );
+        ___url.set(this[___id], "port", String(value));
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/search
     get search() {
-        let search = this[___state].search;
-        return search && !/^\?/.test(search) ? `?${search}` : search;
+        return ___url.get(this[___id], "search");
     }
 
     set search(value) {
-        const params = this[___state].setSearch(
-            this[___state].href,
-            String(value),
-        );
-
-        this[___update]({ ...params, href: params.href.replace(/\?$/, "") });
+        ___url.set(this[___id], "search", String(value));
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/protocol
     get protocol() {
-        const protocol = this[___state].protocol;
-        return protocol ? `${protocol}:` : "";
+        return ___url.get(this[___id], "protocol");
     }
 
-    // @see: https://github.com/lifaon74/url-polyfill/blob/341221207263c9214e794fc3eaf221a71c596d29/do-not-use/url.js#L250
     set protocol(value) {
-        this[___update](
-            this[___state].setProtocol(this[___state].href, String(value)),
-        );
+        ___url.set(this[___id], "protocol", String(value));
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/searchParams
     get searchParams() {
         const self = this;
-        const searchParams = self[___state].searchParams;
+        const searchParams = new URLSearchParams(self.search);
 
         return {
             append(name, value) {
@@ -183,8 +151,8 @@ class URL {
             entries() {
                 return searchParams.entries();
             },
-            forEach() {
-                return searchParams.forEach();
+            forEach(callback, thisArg) {
+                return searchParams.forEach(callback, thisArg);
             },
             get(name) {
                 return searchParams.get(name);
@@ -223,29 +191,16 @@ class URL {
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/URL/username
     get username() {
-        return this[___state].username;
+        return ___url.get(this[___id], "username");
     }
 
     set username(value) {
-        this[___update](
-            this[___state].setUsername(this[___state].href, String(value)),
-        );
-    }
-
-    [___update](properties) {
-        this[___state].hash = properties.hash;
-        this[___state].host = properties.host;
-        this[___state].hostname = properties.hostname;
-        this[___state].href = properties.href;
-        this[___state].origin = properties.origin;
-        this[___state].password = properties.password;
-        this[___state].pathname = properties.pathname;
-        this[___state].port = properties.port;
-        this[___state].protocol = properties.protocol;
-        this[___state].search = properties.search;
-        this[___state].searchParams = new URLSearchParams(properties.search);
-        this[___state].username = properties.username;
+        ___url.set(this[___id], "username", String(value));
     }
 }
 
 globalThis.URL = URL;
+
+function toBase(base) {
+    return base === undefined ? undefined : String(base);
+}
@@ -1,3 +1,5 @@
+import { ___appendParts, ___copyFormData, ___encodeFormData } from "../core/form-data.js";
+
 export const symbol = Symbol();
 
 export class ___Body {
@@ -14,21 +16,38 @@ export class ___Body {
         if (self.body instanceof ReadableStream)
             return self.body;
 
-        const stream = new TransformStream();
-        const writer = stream.writable.getWriter();
-
-        writer.write(self.body);
-        writer.close();
+        // The stream of the encoded body is kept apart, so the body keeps its type for the other
+        // methods, and the same stream is returned each time, so reading it uses the body
+        if (!self.bodyStream) {
+            self.bodyStream = new ReadableStream(
+                {
+                    async pull(controller) {
+                        if (self.bodyUsed) {
+                            controller.error(new TypeError("body stream already read"));
+                            return;
+                        }
+
+                        controller.enqueue(toBytes(await encodeBody(self, self.body)));
+                        controller.close();
+                    },
+                },
+                // Encoded once it is read
+                { highWaterMark: 0 },
+            );
+        }
 
-        return stream.readable;
+        return self.bodyStream;
     }
 
     // read-only
     set body(_) {}
 
-
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/Request/bodyUsed
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/Response/bodyUsed
     get bodyUsed() {
-        return this[this[symbol]].bodyUsed;
+        const self = this[this[symbol]];
+
+        return !!self.bodyUsed || isDisturbed(self.body) || isDisturbed(self.bodyStream);
     }
     // read-only
     set bodyUsed(_) {}
@@ -38,33 +57,21 @@ export class ___Body {
     async arrayBuffer() {
         const self = this[this[symbol]];
 
-        if (self.bodyUsed) {
+        if (this.bodyUsed) {
             throw new TypeError(
                 "Failed to execute 'arrayBuffer': body stream already read",
             );
         }
 
+        self.bodyUsed = true;
+
         let body = self.body;
 
         if (body instanceof ReadableStream) {
-            const read = await body.getReader().read();
-
-            body = read.value;
-        }
-
-        if (body instanceof Blob) {
-            return body.arrayBuffer();
+            body = await readStream(body);
         }
 
-        if (body instanceof FormData) {
-            body = multiPartToString(body, self.headers);
-        }
-
-        if (typeof body === "string") {
-            body = new TextEncoder().encode(body);
-        }
-
-        return new Promise((resolve) => resolve(body));
+        return encodeBody(self, body);
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Request/blob
@@ -72,7 +79,7 @@ export class ___Body {
     async blob() {
         const self = this[this[symbol]];
 
-        if (self.bodyUsed) {
+        if (this.bodyUsed) {
             throw new TypeError(
                 "Failed to execute 'blob': body stream already read",
             );
@@ -104,29 +111,47 @@ export class ___Body {
     async formData() {
         const self = this[this[symbol]];
 
-        if (self.bodyUsed) {
+        if (this.bodyUsed) {
             throw new TypeError(
                 "Failed to execute 'formData': body stream already read",
             );
         }
 
-        if (self.body instanceof Blob || self.body instanceof ArrayBuffer) {
-            throw new TypeError("Failed to fetch");
-        }
-
         if (self.body instanceof FormData) {
+            self.bodyUsed = true;
+
             return new Promise((resolve) => {
-                resolve(self.body);
+                resolve(___copyFormData(self.body));
             });
         }
 
+        const contentType = self.headers?.get("content-type");
+
+        if (/multipart\/form-data/i.test(contentType)) {
+            const boundary = ___getBoundary(contentType);
+
+            if (!boundary) {
+                throw new TypeError(
+                    "Failed to execute 'formData': the multipart body has no boundary",
+                );
+            }
+
+            self.bodyUsed = true;
+
+            return parseMultipart(self.body, boundary);
+        }
+
+        if (self.body instanceof Blob || self.body instanceof ArrayBuffer) {
+            throw new TypeError("Failed to fetch");
+        }
+
         return this.text().then((text) => toFormData(self.headers, text));
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Request/json
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Response/json
     async json() {
-        if (this[this[symbol]].bodyUsed) {
+        if (this.bodyUsed) {
             throw new TypeError(
                 "Failed to execute 'json': body stream already read",
             );
@@ -140,7 +165,7 @@ export class ___Body {
     async text() {
         const self = this[this[symbol]];
 
-        if (self.bodyUsed) {
+        if (this.bodyUsed) {
             throw new TypeError(
                 "Failed to execute 'text': body stream already read",
             );
@@ -155,20 +180,10 @@ export class ___Body {
         let body = self.body;
 
         if (body instanceof ReadableStream) {
-            const read = await body.getReader().read();
-
-            body = read.value;
+            body = await readStream(body);
         }
 
-        if (body instanceof Blob) {
-            body = await body.arrayBuffer();
-        }
-
-        if (body instanceof FormData) {
-            return new Promise((resolve) =>
-                resolve(multiPartToString(body, self.headers)),
-            );
-        }
+        body = await encodeBody(self, body);
 
         return new Promise((resolve) => {
             resolve(new TextDecoder().decode(body));
@@ -176,6 +191,128 @@ export class ___Body {
     }
 }
 
+// Whether the body was set as a stream, so its length isn't known before reading it
+export function ___hasStreamBody(target) {
+    return target[target[symbol]].body instanceof ReadableStream;
+}
+
+/**
+ * The body of a clone of the request or response. A stream is teed, the target keeping one branch
+ * and the clone reading the other, so each one can be read on its own. A `FormData` or
+ * `URLSearchParams` is copied, so modifying the body of one doesn't change the other.
+ *
+ * @see: https://fetch.spec.whatwg.org/#concept-body-clone
+ */
+export function ___cloneBody(target) {
+    const self = target[target[symbol]];
+
+    if (self.body instanceof FormData) {
+        return ___copyFormData(self.body);
+    }
+
+    if (self.body instanceof URLSearchParams) {
+        return new URLSearchParams(self.body);
+    }
+
+    if (!(self.body instanceof ReadableStream)) {
+        return self.body;
+    }
+
+    const [body, clone] = self.body.tee();
+
+    self.body = body;
+
+    return clone;
+}
+
+// Whether the `body` getter returned a stream which is locked to a reader
+export function ___isBodyLocked(target) {
+    const self = target[target[symbol]];
+
+    return !!(self.body?.locked || self.bodyStream?.locked);
+}
+
+/**
+ * The bytes of a body which isn't a stream, the text being encoded in UTF-8 and a `FormData` as
+ * `multipart/form-data`.
+ */
+async function encodeBody(self, body) {
+    if (body instanceof Blob) {
+        return body.arrayBuffer();
+    }
+
+    if (body instanceof FormData) {
+        return ___encodeFormData(body, formDataBoundary(self));
+    }
+
+    if (body instanceof URLSearchParams) {
+        body = body.toString();
+    }
+
+    if (typeof body === "string") {
+        return new TextEncoder().encode(body);
+    }
+
+    return body;
+}
+
+// The bytes as a `Uint8Array`, as the chunks of a stream body
+function toBytes(body) {
+    if (body instanceof ArrayBuffer) return new Uint8Array(body);
+
+    return new Uint8Array(body.buffer, body.byteOffset, body.byteLength);
+}
+
+// Whether the stream was read or canceled, `_disturbed` being set by the streams polyfill
+// @see: https://streams.spec.whatwg.org/#is-readable-stream-disturbed
+function isDisturbed(body) {
+    return body instanceof ReadableStream && body._disturbed === true;
+}
+
+/**
+ * Reads every chunk of the stream into a single `Uint8Array`. A single chunk which isn't text nor
+ * bytes, like a `Blob`, is returned as it is.
+ */
+async function readStream(stream) {
+    const reader = stream.getReader();
+    const chunks = [];
+
+    for (;;) {
+        const { done, value } = await reader.read();
+
+        if (done) break;
+
+        chunks.push(value);
+    }
+
+    if (
+        chunks.length === 1 &&
+        typeof chunks[0] !== "string" &&
+        !ArrayBuffer.isView(chunks[0]) &&
+        !(chunks[0] instanceof ArrayBuffer)
+    ) {
+        return chunks[0];
+    }
+
+    const bytes = chunks.map((chunk) => {
+        if (typeof chunk === "string") return new TextEncoder().encode(chunk);
+        if (chunk instanceof ArrayBuffer) return new Uint8Array(chunk);
+
+        return new Uint8Array(chunk.buffer, chunk.byteOffset, chunk.byteLength);
+    });
+    const body = new Uint8Array(
+        bytes.reduce((length, chunk) => length + chunk.byteLength, 0),
+    );
+    let offset = 0;
+
+    for (const chunk of bytes) {
+        body.set(chunk, offset);
+        offset += chunk.byteLength;
+    }
+
+    return body;
+}
+
 function toFormData(headers, body) {
     const formData = new FormData();
 
@@ -183,10 +320,7 @@ function toFormData(headers, body) {
 
     const contentType = headers.get("content-type");
 
-    if (/multipart\/form-data/.test(contentType)) {
-        const boundary = getBoundary(contentType);
-        return boundary ? parseMultipart(body, boundary) : formData;
-    } else if (/application\/x-www-form-urlencoded/.test(contentType)) {
+    if (/application\/x-www-form-urlencoded/.test(contentType)) {
         body.trim()
             .split("&")
             .forEach(function (bytes) {
@@ -207,62 +341,70 @@ function toFormData(headers, body) {
     }
 }
 
-function parseMultipart(body, boundary) {
-    let name = "";
-
+/**
+ * Parses a `multipart/form-data` body in Rust, chunk by chunk for a stream, so the parts are
+ * built as they are received.
+ */
+async function parseMultipart(body, boundary) {
     const formData = new FormData();
-    const chunks = body.split(boundary);
-
-    for (let i = 0, len = chunks.length; i < len; i++) {
-        const chunk = chunks[i];
-        const lines = chunk.split(/\r?\n/);
+    const parser = ___multipart.parser(boundary);
 
-        for (let l = 1, lenL = lines.length; l < lenL; l++) {
-            const line = lines[l].trim();
+    if (body instanceof ReadableStream) {
+        const reader = body.getReader();
 
-            if (!line) continue;
-            if (/content-type/i.test(line)) continue;
-            if (/content-disposition/i.test(line)) {
-                name = line.match(/\sname\=\"(.*?)\"/);
-                name = name ? name[1] : "";
-                name = name.replace("[]", "");
+        for (;;) {
+            const { done, value } = await reader.read();
 
-                continue;
-            }
+            if (done) break;
 
-            formData.append(name, line);
+            ___appendParts(formData, ___multipart.push(parser, await toArrayBuffer(value)));
         }
+    } else if (body) {
+        ___appendParts(formData, ___multipart.push(parser, await toArrayBuffer(body)));
     }
 
+    ___appendParts(formData, ___multipart.finish(parser));
+
     return formData;
 }
 
-function getBoundary(contentType) {
+async function toArrayBuffer(chunk) {
+    if (typeof chunk === "string") {
+        chunk = new TextEncoder().encode(chunk);
+    }
+
+    if (chunk instanceof Blob) {
+        return chunk.arrayBuffer();
+    }
+
+    if (chunk instanceof ArrayBuffer) {
+        return chunk;
+    }
+
+    return chunk.buffer.slice(chunk.byteOffset, chunk.byteOffset + chunk.byteLength);
+}
+
+// @see: https://datatracker.ietf.org/doc/html/rfc2046#section-5.1.1
+export function ___getBoundary(contentType) {
     if (!contentType) return "";
 
     const boundary = contentType
         .split(";")
-        .find((item) => item.includes("boundary"));
+        .map((parameter) => parameter.trim())
+        .find((parameter) => /^boundary=/i.test(parameter));
 
-    return boundary ? boundary.split("=")[1] : "";
+    return boundary ? boundary.slice("boundary=".length).replace(/^"(.*)"$/, "$1") : "";
 }
 
-function multiPartToString(formData, headers) {
-    const boundary = `----WebKitFormBoundary${Math.random()
-        .toString(36)
-        .replace(/0\./, "")}`;
-    const body = [];
-
-    for (const [key, value] of formData.entries()) {
-        body.push(`--${boundary}`);
-        body.push(`Content-Disposition: form-data; name=\"${key}\"`);
-        body.push("");
-        body.push(value);
-    }
+// The boundary of the content type set for the `FormData` body, or a new one set as content type
+function formDataBoundary(self) {
+    const boundary = ___getBoundary(self.headers?.get("content-type"));
+
+    if (boundary) return boundary;
 
-    body.push(`--${boundary}--`);
+    const created = ___multipart.boundary();
 
-    headers.set("content-type", `multipart/form-data; boundary=${boundary}`);
+    self.headers?.set("content-type", `multipart/form-data; boundary=${created}`);
 
-    return body.join("\n");
+    return created;
 }
@@ -1,3 +1,7 @@
+import { ___encodeFormData } from "../core/form-data.js";
+import { ___getBoundary } from "./body.js";
+import { ___setHeadersGuard } from "./headers.js";
+
 // @see: https://developer.mozilla.org/en-US/docs/Web/API/fetch
 // @see: https://developer.mozilla.org/en-US/docs/Web/API/fetch#resource
 // @see: https://developer.mozilla.org/en-US/docs/Web/API/fetch#options
@@ -7,11 +11,30 @@ async function fetch(resource, options) {
         resource = options.url;
     }
 
+    let headers = options?.headers || {};
+    let body = options?.body;
+
+    // The boundary of the content type is kept, or one is created for it
+    if (body instanceof FormData) {
+        headers = new Headers(headers);
+
+        let boundary = ___getBoundary(headers.get("content-type"));
+
+        if (!boundary) {
+            boundary = ___multipart.boundary();
+            headers.set("content-type", `multipart/form-data; boundary=${boundary}`);
+        }
+
+        body = await ___encodeFormData(body, boundary);
+
+        headers = headers.getAll();
+    }
+
     const strResponse = ___fetcher({
-        body: options?.body ? await getBody(options.body) : "",
+        body: body ? await getBody(body) : "",
         credentials: options?.credentials || "same-origin",
         cache: options?.cache,
-        headers: JSON.stringify(options?.headers || {}),
+        headers: JSON.stringify(headers),
         integrity: options?.integrity,
         keepalive: !!options?.keepalive,
         method: options?.method || "GET",
@@ -25,13 +48,15 @@ async function fetch(resource, options) {
 
     const response = JSON.parse(strResponse);
 
-    return Promise.resolve(
-        new Response(new Uint8Array(response.body).buffer, {
-            status: response.status,
-            url: resource,
-            headers: JSON.parse(response.headers),
-        }),
-    );
+    const fetched = new Response(new Uint8Array(response.body).buffer, {
+        status: response.status,
+        url: resource,
+        headers: JSON.parse(response.headers),
+    });
+
+    ___setHeadersGuard(fetched.headers, "immutable");
+
+    return Promise.resolve(fetched);
 }
 
 globalThis.fetch = fetch;
@@ -39,9 +64,6 @@ globalThis.fetch = fetch;
 async function getBody(body) {
     if (body instanceof Blob) {
         return new Uint8Array(await body.arrayBuffer());
-    } else if (body instanceof FormData) {
-        // TODO: Add example
-        return new TextEncoder().encode(body.toString());
     } else if (body instanceof URLSearchParams) {
         // TODO: Add example
         return new TextEncoder().encode(body.toString());
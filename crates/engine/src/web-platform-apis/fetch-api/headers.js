@@ -1,4 +1,5 @@
-const ___headers = Symbol();
+const ___handle = Symbol();
+const ___id = Symbol();
 
 /*
  * Headers
@@ -6,6 +7,8 @@ const ___headers = Symbol();
  * The Headers interface of the Fetch API allows you to perform various actions on HTTP request and response headers.
  * These actions include retrieving, setting, adding to, and removing headers from the list of the request's headers.
  *
+ * The header list is stored, validated and normalized in Rust, see `globals/headers.rs`.
+ *
  * @see: https://developer.mozilla.org/en-US/docs/Web/API/Headers
  * @see: https://developers.cloudflare.com/workers/runtime-apis/headers/
  * @see: https://github.com/github/fetch/blob/fb5b0cf42b470faf8c5448ab461d561f34380a30/fetch.js#L84
@@ -14,10 +17,13 @@ const ___headers = Symbol();
 class Headers {
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Headers/Headers
     constructor(init) {
-        this[___headers] = [];
+        // The header list is freed once the handle is collected with the headers
+        [this[___id], this[___handle]] = ___headerList.create();
 
         if (init instanceof Headers) {
-            init.forEach((value, name) => this.append(name, value));
+            for (const [name, value] of init.entries()) {
+                this.append(name, value);
+            }
         } else if (Array.isArray(init)) {
             init.forEach(function (header) {
                 if (header.length !== 2) {
@@ -41,115 +47,96 @@ class Headers {
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Headers/append
     append(name, value) {
-        name = normalizeName(name);
-        value = normalizeValue(value);
-
-        if (this.has(name)) {
-            this[___headers] = this[___headers].map((pair) => {
-                if (pair[0] === name) {
-                    return [name, `${pair[1]}, ${value}`];
-                } else {
-                    return pair;
-                }
-            });
-        } else {
-            this[___headers].push([name, value]);
-        }
+        ___headerList.append(this[___id], String(name), String(value));
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Headers/get
     get(name) {
-        const result = this[___headers].find(
-            (pair) => pair[0] === normalizeName(name),
-        );
-
-        if (result === undefined) {
-            return null;
-        }
-
-        return result[1];
+        return ___headerList.get(this[___id], String(name));
     }
 
     getAll() {
-        return this[___headers].reduce((acc, header) => {
-            acc[header[0]] = header[1];
+        return entryList(this).reduce((acc, [name, value]) => {
+            acc[name] = name in acc ? `${acc[name]}, ${value}` : value;
             return acc;
         }, {});
     }
 
-    // @see: https://developer.mozilla.org/en-US/docs/Web/API/Headers/get
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/Headers/getSetCookie
+    getSetCookie() {
+        return JSON.parse(___headerList.getSetCookie(this[___id]));
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/Headers/has
     has(name) {
-        return this[___headers].some((pair) => pair[0] === normalizeName(name));
+        return ___headerList.has(this[___id], String(name));
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Headers/set
     set(name, value) {
-        name = normalizeName(name);
-
-        if (this.has(name)) {
-            this[___headers] = this[___headers].map((pair) =>
-                pair[0] === name ? [name, normalizeValue(value)] : pair,
-            );
-        } else {
-            this[___headers].push([name, normalizeValue(value)]);
-        }
+        ___headerList.set(this[___id], String(name), String(value));
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Headers/delete
     delete(name) {
-        this[___headers] = this[___headers].filter(
-            (pair) => pair[0] !== normalizeName(name),
-        );
+        ___headerList.delete(this[___id], String(name));
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Headers/forEach
     forEach(callback, thisArg = this) {
-        for (let pair of thisArg.entries()) {
-            callback.call(thisArg, pair[1], pair[0]);
+        for (const [name, value] of entryList(this)) {
+            callback.call(thisArg, value, name, this);
         }
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Headers/keys
     keys() {
-        return this[___headers].map((pair) => pair[0])[Symbol.iterator]();
+        return entryList(this)
+            .map((pair) => pair[0])
+            [Symbol.iterator]();
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Headers/values
     values() {
-        return this[___headers].map((pair) => pair[1])[Symbol.iterator]();
+        return entryList(this)
+            .map((pair) => pair[1])
+            [Symbol.iterator]();
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Headers/entries
     entries() {
-        return this[___headers][Symbol.iterator]();
+        return entryList(this)[Symbol.iterator]();
     }
-}
-
-globalThis.Headers = Headers;
 
-function normalizeName(name) {
-    if (typeof name !== "string") {
-        name = String(name);
+    [Symbol.iterator]() {
+        return this.entries();
     }
+}
 
-    name = name.replace(/^[\n\t\r\x20]+|[\n\t\r\x20]+$/g, "");
+/**
+ * Sets the guard of the headers, `immutable` for the responses of `fetch` and `request-no-cors`
+ * for the `no-cors` requests.
+ *
+ * @see: https://fetch.spec.whatwg.org/#concept-headers-guard
+ */
+export function ___setHeadersGuard(headers, guard) {
+    ___headerList.setGuard(headers[___id], guard);
+}
 
-    // TODO: implement a test for ["\t\f\tnewLine4\n","\f\tnewLine"], result \f\tnewLine
-    // TODO: implement a test for ["newLine5\xa0","newLine5\xa0"], result newLine5\xa0
+/**
+ * Copies the headers, with their guard, for the clones of the requests and responses.
+ */
+export function ___cloneHeaders(headers) {
+    const clone = new Headers();
 
-    if (/[^a-z0-9\-#$%&'*+.^_`|~!]/i.test(name) || name === "") {
-        throw new TypeError(
-            `Invalid character in header field name: "${name}"`,
-        );
-    }
+    [clone[___id], clone[___handle]] = ___headerList.clone(headers[___id]);
 
-    return name.toLowerCase();
+    return clone;
 }
 
-function normalizeValue(value) {
-    if (typeof value !== "string") {
-        value = String(value);
-    }
+globalThis.Headers = Headers;
 
-    return value;
+// The `[name, value]` pairs, combined by name except for `set-cookie`
+function entryList(headers) {
+    return JSON.parse(___headerList.entries(headers[___id]));
 }
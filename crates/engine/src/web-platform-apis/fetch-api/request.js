@@ -1,4 +1,5 @@
-import { ___Body } from "./body.js";
+import { ___Body, ___cloneBody, ___isBodyLocked } from "./body.js";
+import { ___cloneHeaders, ___setHeadersGuard } from "./headers.js";
 
 const ___request = Symbol();
 
@@ -58,8 +59,9 @@ class Request extends ___Body {
             if (!headers.get("content-type") && self.body) {
                 const types = {
                     "[object Object]": () => {
-                        // TODO: add FormData support with boundary creation
-                        if (body instanceof Blob) {
+                        if (body instanceof FormData) {
+                            return `multipart/form-data; boundary=${___multipart.boundary()}`;
+                        } else if (body instanceof Blob) {
                             return body.type;
                         } else if (body instanceof URLSearchParams) {
                             return "application/x-www-form-urlencoded;charset=UTF-8";
@@ -75,6 +77,18 @@ class Request extends ___Body {
             }
 
             self.headers = headers;
+
+            // Only the CORS-safelisted headers are kept
+            // @see: https://fetch.spec.whatwg.org/#dom-request
+            if (self.mode === "no-cors") {
+                self.headers = new Headers();
+
+                ___setHeadersGuard(self.headers, "request-no-cors");
+
+                for (const [name, value] of headers) {
+                    self.headers.append(name, value);
+                }
+            }
         }
 
         if (self.method === "GET" || self.method === "HEAD") {
@@ -195,27 +209,28 @@ class Request extends ___Body {
     // readonly
     set url(_) {}
 
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/Request/clone
+    // @see: https://fetch.spec.whatwg.org/#dom-request-clone
     clone() {
-        if (this[___request].bodyUsed) {
+        const self = this[___request];
+
+        if (this.bodyUsed || ___isBodyLocked(this)) {
             throw new TypeError(
                 "Failed to execute 'clone' on 'Request': Request body is already used",
             );
         }
 
-        return new Request(this[___request].url, {
-            body: this[___request].body,
-            cache: this[___request].cache,
-            credentials: this[___request].credentials,
-            headers: this[___request].headers,
-            integrity: this[___request].integrity,
-            keepalive: this[___request].keepalive,
-            method: this[___request].method,
-            mode: this[___request].mode,
-            redirect: this[___request].redirect,
-            referrer: this[___request].referrer,
-            referrerPolicy: this[___request].referrerPolicy,
-            signal: this[___request].signal,
-        });
+        const request = new Request(self.url);
+
+        request[___request] = {
+            ...self,
+            body: ___cloneBody(this),
+            bodyStream: undefined,
+            bodyUsed: false,
+            headers: self.headers && ___cloneHeaders(self.headers),
+        };
+
+        return request;
     }
 }
 
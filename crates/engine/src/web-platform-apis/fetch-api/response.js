@@ -1,4 +1,5 @@
-import { ___Body } from "./body.js";
+import { ___Body, ___cloneBody, ___isBodyLocked } from "./body.js";
+import { ___cloneHeaders } from "./headers.js";
 
 const ___response = Symbol();
 
@@ -22,11 +23,21 @@ class Response extends ___Body {
         const status = init.status !== undefined ? init.status : 200;
         const location = headers.get("location");
 
+        // @see: https://developers.cloudflare.com/workers/runtime-apis/response/#parameters
+        if (init.webSocket != null) {
+            if (!(init.webSocket instanceof WebSocket) || status !== 101) {
+                throw new TypeError(
+                    "Failed to construct 'Response': The webSocket must be a WebSocket with a 101 status.",
+                );
+            }
+        }
+
         if (body && !headers.has("content-type")) {
             const types = {
                 "[object Object]": () => {
-                    // TODO: add FormData support with boundary creation
-                    if (body instanceof Blob) {
+                    if (body instanceof FormData) {
+                        return `multipart/form-data; boundary=${___multipart.boundary()}`;
+                    } else if (body instanceof Blob) {
                         return body.type;
                     } else if (body instanceof URLSearchParams) {
                         return "application/x-www-form-urlencoded;charset=UTF-8";
@@ -60,6 +71,7 @@ class Response extends ___Body {
                 : init.statusText;
         this[___response].type = "basic";
         this[___response].url = location || "";
+        this[___response].webSocket = init.webSocket ?? null;
     }
 
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Response/error
@@ -71,6 +83,26 @@ class Response extends ___Body {
         return response;
     }
 
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/Response/json_static
+    // @see: https://fetch.spec.whatwg.org/#dom-response-json
+    static json(data, init = {}) {
+        const body = JSON.stringify(data);
+
+        if (body === undefined) {
+            throw new TypeError(
+                "Failed to execute 'json' on 'Response': The data is not JSON serializable",
+            );
+        }
+
+        const headers = new Headers(init.headers || {});
+
+        if (!headers.has("content-type")) {
+            headers.set("content-type", "application/json");
+        }
+
+        return new Response(body, { ...init, headers });
+    }
+
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Response/redirect
     // @see: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Location
     // @see: https://fetch.spec.whatwg.org/#redirect-status
@@ -143,20 +175,36 @@ class Response extends ___Body {
     // read-only
     set url(_) {}
 
+    // The client end of a `WebSocketPair`, when the response upgrades the connection
+    // @see: https://developers.cloudflare.com/workers/runtime-apis/response/#properties
+    get webSocket() {
+        return this[___response].webSocket;
+    }
+    // read-only
+    set webSocket(_) {}
+
     // @see: https://developer.mozilla.org/en-US/docs/Web/API/Response/clone
+    // @see: https://fetch.spec.whatwg.org/#dom-response-clone
     clone() {
-        if (this[___response].bodyUsed) {
+        const self = this[___response];
+
+        if (this.bodyUsed || ___isBodyLocked(this)) {
             throw new TypeError(
-                "Failed to execute 'clone' on 'Response': Response body is already use",
+                "Failed to execute 'clone' on 'Response': Response body is already used",
             );
         }
 
-        return new Response(this[___response].body, {
-            headers: this[___response].headers,
-            status: this[___response].status,
-            statusText: this[___response].statusText,
-            url: this[___response].url,
-        });
+        const response = new Response(null);
+
+        response[___response] = {
+            ...self,
+            body: ___cloneBody(this),
+            bodyStream: undefined,
+            bodyUsed: false,
+            headers: ___cloneHeaders(self.headers),
+        };
+
+        return response;
     }
 }
 
@@ -1,16 +1,29 @@
 import "./core/web-streams.js";
 
+import "./core/dom-exception.js";
+import "./core/base64.js";
+
 import "./core/handle-response.js";
 
 import "./core/blob.js";
+import "./core/file.js";
 import "./core/form-data.js";
 import "./core/text-encoder.js";
 import "./core/text-decoder.js";
+import "./core/compression-stream.js";
 import "./core/url";
+import "./core/url-pattern.js";
 import "./core/url-search-params";
+import "./core/structured-clone.js";
 
 import "./fetch-api/abortcontroller.js";
 import "./fetch-api/headers.js";
 import "./fetch-api/request.js";
 import "./fetch-api/response.js";
 import "./fetch-api/fetch.js";
+
+import "./workers/env.js";
+import "./workers/execution-context.js";
+import "./workers/fetch-event.js";
+import "./workers/kv-namespace.js";
+import "./workers/websocket.js";
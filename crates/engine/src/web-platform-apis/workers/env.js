@@ -0,0 +1,17 @@
+// Creates the `env` of the handler from the bindings set by the host
+// @see: https://developers.cloudflare.com/workers/configuration/environment-variables/
+globalThis.___createEnv = function (bindings) {
+    const env = {};
+
+    for (const [name, binding] of Object.entries(JSON.parse(bindings))) {
+        switch (binding.type) {
+            case "kv":
+                env[name] = new KVNamespace(name);
+                break;
+            default:
+                env[name] = binding.value;
+        }
+    }
+
+    return Object.freeze(env);
+};
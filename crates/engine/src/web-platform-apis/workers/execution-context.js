@@ -0,0 +1,24 @@
+export const ___passThroughOnException = Symbol();
+
+// @see: https://developers.cloudflare.com/workers/runtime-apis/context/
+class ExecutionContext {
+    constructor() {
+        this[___passThroughOnException] = false;
+    }
+
+    // The invocation runs the pending jobs after responding, so it only has to report the failures
+    // @see: https://developers.cloudflare.com/workers/runtime-apis/context/#waituntil
+    waitUntil(promise) {
+        Promise.resolve(promise).catch((error) => {
+            console.error("Uncaught (in waitUntil)", error);
+        });
+    }
+
+    // When the handler throws, the request is sent to its URL and that response is returned instead
+    // @see: https://developers.cloudflare.com/workers/runtime-apis/context/#passthroughonexception
+    passThroughOnException() {
+        this[___passThroughOnException] = true;
+    }
+}
+
+globalThis.ExecutionContext = ExecutionContext;
@@ -0,0 +1,86 @@
+const ___ctx = Symbol();
+const ___response = Symbol();
+
+const fetchListeners = [];
+
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/FetchEvent
+// @see: https://developers.cloudflare.com/workers/runtime-apis/handlers/fetch/#syntax-service-worker
+class FetchEvent {
+    constructor(type, init) {
+        if (!init?.request) {
+            throw new TypeError(
+                "Failed to construct 'FetchEvent': required member request is undefined.",
+            );
+        }
+
+        this.type = type;
+        this.request = init.request;
+        this[___ctx] = init.ctx || new ExecutionContext();
+        this[___response] = null;
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/FetchEvent/respondWith
+    respondWith(response) {
+        if (this[___response]) {
+            throw new Error(
+                "Failed to execute 'respondWith' on 'FetchEvent': The event has already been responded to.",
+            );
+        }
+
+        this[___response] = Promise.resolve(response);
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/ExtendableEvent/waitUntil
+    waitUntil(promise) {
+        this[___ctx].waitUntil(promise);
+    }
+
+    passThroughOnException() {
+        this[___ctx].passThroughOnException();
+    }
+}
+
+// Dispatches the request to the listeners, the first response wins
+async function handleFetchEvent(request, _env, ctx) {
+    const event = new FetchEvent("fetch", { request, ctx });
+
+    for (const listener of fetchListeners) {
+        if (typeof listener === "function") {
+            listener.call(globalThis, event);
+        } else {
+            listener.handleEvent(event);
+        }
+    }
+
+    if (!event[___response]) {
+        throw new Error("No fetch event listener called respondWith()");
+    }
+
+    return event[___response];
+}
+
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/addEventListener
+function addEventListener(type, listener) {
+    if (type === "fetch" && listener && !fetchListeners.includes(listener)) {
+        fetchListeners.push(listener);
+        globalThis.___fetchEventHandler = handleFetchEvent;
+    }
+}
+
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/removeEventListener
+function removeEventListener(type, listener) {
+    const index = type === "fetch" ? fetchListeners.indexOf(listener) : -1;
+
+    if (index !== -1) {
+        fetchListeners.splice(index, 1);
+    }
+
+    if (fetchListeners.length === 0) {
+        globalThis.___fetchEventHandler = undefined;
+    }
+}
+
+globalThis.FetchEvent = FetchEvent;
+globalThis.addEventListener = addEventListener;
+globalThis.removeEventListener = removeEventListener;
+globalThis.self = globalThis;
@@ -0,0 +1,88 @@
+const ___binding = Symbol();
+
+// @see: https://developers.cloudflare.com/workers/runtime-apis/kv/
+class KVNamespace {
+    constructor(binding) {
+        this[___binding] = binding;
+    }
+
+    // @see: https://developers.cloudflare.com/workers/runtime-apis/kv/#reading-key-value-pairs
+    async get(key, options) {
+        const type =
+            typeof options === "string" ? options : options?.type || "text";
+        const value = kv(this[___binding], { operation: "get", key });
+
+        if (value === null) {
+            return null;
+        }
+
+        // The values are returned as an `ArrayBuffer` of their bytes
+        const bytes = new Uint8Array(value);
+
+        switch (type) {
+            case "json":
+                return JSON.parse(new TextDecoder().decode(bytes));
+            case "arrayBuffer":
+                return bytes.buffer;
+            case "stream":
+                return new Response(bytes).body;
+            default:
+                return new TextDecoder().decode(bytes);
+        }
+    }
+
+    // @see: https://developers.cloudflare.com/workers/runtime-apis/kv/#writing-key-value-pairs
+    async put(key, value) {
+        let buffer;
+
+        if (value instanceof ArrayBuffer) {
+            buffer = value;
+        } else if (ArrayBuffer.isView(value)) {
+            buffer = value.buffer.slice(value.byteOffset, value.byteOffset + value.byteLength);
+        } else if (value instanceof ReadableStream) {
+            buffer = await new Response(value).arrayBuffer();
+        } else {
+            buffer = new TextEncoder().encode(String(value)).buffer;
+        }
+
+        kv(this[___binding], { operation: "put", key }, buffer);
+    }
+
+    // @see: https://developers.cloudflare.com/workers/runtime-apis/kv/#deleting-key-value-pairs
+    async delete(key) {
+        kv(this[___binding], { operation: "delete", key });
+    }
+
+    // @see: https://developers.cloudflare.com/workers/runtime-apis/kv/#listing-keys
+    async list(options) {
+        const keys = kv(this[___binding], {
+            operation: "list",
+            prefix: options?.prefix || "",
+        });
+
+        return {
+            keys: keys.map((name) => ({ name })),
+            list_complete: true,
+        };
+    }
+}
+
+// The value to put is passed as an `ArrayBuffer`, apart from the operation
+function kv(binding, operation, value) {
+    const output = ___kv(JSON.stringify({ binding, ...operation }), value);
+
+    // The value of a key is returned as its bytes, and the other results as JSON
+    if (output instanceof ArrayBuffer) {
+        return output;
+    }
+
+    const result = JSON.parse(output);
+
+    if (result.error) {
+        throw new Error(result.error);
+    }
+
+    return result.value;
+}
+
+globalThis.KVNamespace = KVNamespace;
@@ -0,0 +1,233 @@
+const ___accepted = Symbol();
+const ___listeners = Symbol();
+const ___peer = Symbol();
+const ___readyState = Symbol();
+const ___server = Symbol();
+
+// Only the `WebSocketPair` creates the sockets
+const ___token = Symbol();
+
+// The server end of the pair returned in the response, it receives the messages of the client
+let upgraded = null;
+
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/MessageEvent
+class MessageEvent {
+    constructor(type, init) {
+        this.type = type;
+        this.data = init?.data ?? null;
+    }
+}
+
+// @see: https://developer.mozilla.org/en-US/docs/Web/API/CloseEvent
+class CloseEvent {
+    constructor(type, init) {
+        this.type = type;
+        this.code = init?.code ?? 0;
+        this.reason = init?.reason ?? "";
+        this.wasClean = !!init?.wasClean;
+    }
+}
+
+// The frames are relayed by the host, which handles the handshake and the control frames
+// @see: https://developers.cloudflare.com/workers/runtime-apis/websockets/
+class WebSocket {
+    static CONNECTING = 0;
+    static OPEN = 1;
+    static CLOSING = 2;
+    static CLOSED = 3;
+
+    constructor(token, server) {
+        if (token !== ___token) {
+            throw new TypeError(
+                "Failed to construct 'WebSocket': Outbound WebSockets aren't supported, use a WebSocketPair.",
+            );
+        }
+
+        this[___accepted] = false;
+        this[___listeners] = {};
+        this[___peer] = null;
+        this[___readyState] = WebSocket.CONNECTING;
+        this[___server] = server;
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/WebSocket/readyState
+    get readyState() {
+        return this[___readyState];
+    }
+
+    // @see: https://developers.cloudflare.com/workers/runtime-apis/websockets/#accept
+    accept() {
+        if (!this[___server]) {
+            throw new TypeError(
+                "Failed to execute 'accept' on 'WebSocket': The client end of a WebSocketPair is returned in the Response.",
+            );
+        }
+
+        if (this[___accepted]) {
+            throw new TypeError(
+                "Failed to execute 'accept' on 'WebSocket': The WebSocket has already been accepted.",
+            );
+        }
+
+        this[___accepted] = true;
+        this[___readyState] = WebSocket.OPEN;
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/WebSocket/send
+    send(message) {
+        if (!this[___accepted]) {
+            throw new TypeError(
+                "Failed to execute 'send' on 'WebSocket': accept() must be called before sending messages.",
+            );
+        }
+
+        if (this[___readyState] !== WebSocket.OPEN) {
+            throw new TypeError(
+                "Failed to execute 'send' on 'WebSocket': The WebSocket is closing or closed.",
+            );
+        }
+
+        if (message instanceof ArrayBuffer || ArrayBuffer.isView(message)) {
+            const bytes = ArrayBuffer.isView(message)
+                ? new Uint8Array(message.buffer, message.byteOffset, message.byteLength)
+                : new Uint8Array(message);
+
+            ___websocket.send(JSON.stringify({ type: "binary", data: Array.from(bytes) }));
+        } else {
+            ___websocket.send(JSON.stringify({ type: "text", data: String(message) }));
+        }
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/WebSocket/close
+    close(code, reason = "") {
+        if (!this[___accepted]) {
+            throw new TypeError(
+                "Failed to execute 'close' on 'WebSocket': accept() must be called before closing.",
+            );
+        }
+
+        if (code !== undefined && code !== 1000 && (code < 3000 || code > 4999)) {
+            throw new DOMException(
+                `Failed to execute 'close' on 'WebSocket': The close code must be either 1000, or between 3000 and 4999. ${code} is neither.`,
+                "InvalidAccessError",
+            );
+        }
+
+        reason = String(reason);
+
+        if (new TextEncoder().encode(reason).byteLength > 123) {
+            throw new DOMException(
+                "Failed to execute 'close' on 'WebSocket': The close reason must not be greater than 123 UTF-8 bytes.",
+                "SyntaxError",
+            );
+        }
+
+        if (this[___readyState] !== WebSocket.OPEN) {
+            return;
+        }
+
+        this[___readyState] = WebSocket.CLOSING;
+
+        ___websocket.send(JSON.stringify({ type: "close", code: code ?? 1000, reason }));
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/addEventListener
+    addEventListener(type, listener) {
+        const listeners = (this[___listeners][type] ??= []);
+
+        if (listener && !listeners.includes(listener)) {
+            listeners.push(listener);
+        }
+    }
+
+    // @see: https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/removeEventListener
+    removeEventListener(type, listener) {
+        const listeners = this[___listeners][type] ?? [];
+        const index = listeners.indexOf(listener);
+
+        if (index !== -1) {
+            listeners.splice(index, 1);
+        }
+    }
+}
+
+// @see: https://developers.cloudflare.com/workers/runtime-apis/websockets/#websocketpair
+class WebSocketPair {
+    constructor() {
+        const client = new WebSocket(___token, false);
+        const server = new WebSocket(___token, true);
+
+        client[___peer] = server;
+        server[___peer] = client;
+
+        this[0] = client;
+        this[1] = server;
+    }
+}
+
+/**
+ * Relays the messages of the client to the server end of the pair of the client returned in the
+ * response, which has to be accepted by then.
+ */
+export function ___upgradeWebSocket(client) {
+    if (!(client instanceof WebSocket) || client[___server]) {
+        throw new TypeError(
+            "The webSocket of the Response must be the client end of a WebSocketPair",
+        );
+    }
+
+    if (!client[___peer][___accepted]) {
+        throw new TypeError(
+            "The server end of the WebSocketPair must be accepted before returning the client end",
+        );
+    }
+
+    upgraded = client[___peer];
+    client[___readyState] = WebSocket.OPEN;
+
+    ___websocket.upgrade();
+}
+
+// Called by the engine with each message received by the host, as JSON, the last one being `close`
+globalThis.___dispatchWebSocketMessage = function (message) {
+    const { code, data, reason, type } = JSON.parse(message);
+
+    if (type === "close") {
+        upgraded[___readyState] = WebSocket.CLOSED;
+
+        // The connection was lost when there isn't a close frame
+        dispatch(upgraded, new CloseEvent("close", { code, reason, wasClean: code !== 1006 }));
+    } else {
+        dispatch(
+            upgraded,
+            new MessageEvent("message", {
+                data: type === "text" ? data : new Uint8Array(data).buffer,
+            }),
+        );
+    }
+};
+
+// A failing listener is reported, so it doesn't close the WebSocket
+function dispatch(socket, event) {
+    for (const listener of [...(socket[___listeners][event.type] ?? [])]) {
+        try {
+            const result =
+                typeof listener === "function"
+                    ? listener.call(socket, event)
+                    : listener.handleEvent(event);
+
+            Promise.resolve(result).catch(report);
+        } catch (error) {
+            report(error);
+        }
+    }
+}
+
+function report(error) {
+    console.error("Uncaught (in WebSocket listener)", error);
+}
+
+globalThis.CloseEvent = CloseEvent;
+globalThis.MessageEvent = MessageEvent;
+globalThis.WebSocket = WebSocket;
+globalThis.WebSocketPair = WebSocketPair;
@@ -0,0 +1,73 @@
+// Compares the throughput of the on-demand and the pooling allocation strategies, serving 1k concurrent requests.
+//
+// cargo bench --package js-wasm-workers-runtime --bench allocation
+use std::{sync::Arc, time::Instant};
+
+use anyhow::Result;
+use js_wasm_workers_runtime::{
+    runtime_with_config, AllocationStrategy, RuntimeConfig, WasmtimeEnvironment,
+};
+
+const CONCURRENT_REQUESTS: usize = 1000;
+
+const HANDLER: &str = r#"
+export const handleRequest = async function () {
+    return new Response("Hello World!", { status: 200 });
+};
+"#;
+
+const REQUEST: &str = r#"{
+    "body": null,
+    "headers": {},
+    "method": "GET",
+    "url": "https://test.test"
+}"#;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let strategies = [
+        ("on-demand", AllocationStrategy::OnDemand),
+        (
+            "pooling",
+            AllocationStrategy::Pooling {
+                max_instances: CONCURRENT_REQUESTS as u32,
+                // 64 MiB
+                memory_pages: 1024,
+            },
+        ),
+    ];
+
+    for (name, allocation_strategy) in strategies {
+        let config = Arc::new(RuntimeConfig {
+            environment: Some(WasmtimeEnvironment::with_allocation_strategy(
+                allocation_strategy,
+            )?),
+            ..Default::default()
+        });
+
+        // Warm up, so the compilation of the module isn't measured
+        runtime_with_config(HANDLER, REQUEST, &config).await?;
+
+        let started = Instant::now();
+        let tasks = (0..CONCURRENT_REQUESTS)
+            .map(|_| {
+                let config = config.clone();
+
+                tokio::spawn(async move { runtime_with_config(HANDLER, REQUEST, &config).await })
+            })
+            .collect::<Vec<_>>();
+
+        for task in tasks {
+            task.await??;
+        }
+
+        let elapsed = started.elapsed();
+
+        println!(
+            "{name}: {CONCURRENT_REQUESTS} concurrent requests in {elapsed:?} ({:.0} requests/s)",
+            CONCURRENT_REQUESTS as f64 / elapsed.as_secs_f64()
+        );
+    }
+
+    Ok(())
+}
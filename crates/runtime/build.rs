@@ -0,0 +1,28 @@
+use std::{env, fs, path::PathBuf};
+
+#[path = "src/engine_config.rs"]
+mod engine_config;
+
+const WASM: &str = "../../target/wasm32-wasi/release/js-wasm-workers-engine.wasm";
+
+// Compiles the engine ahead of time when the `aot` feature is enabled, so the runtime only has to deserialize it
+fn main() -> anyhow::Result<()> {
+    println!("cargo:rerun-if-changed={WASM}");
+    println!("cargo:rerun-if-changed=src/engine_config.rs");
+
+    if env::var("CARGO_FEATURE_AOT").is_err() {
+        return Ok(());
+    }
+
+    let mut config = engine_config::engine_config(engine_config::AllocationStrategy::OnDemand);
+
+    config.target(&env::var("TARGET")?)?;
+
+    let engine = wasmtime::Engine::new(&config)?;
+    let precompiled = engine.precompile_module(&fs::read(WASM)?)?;
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+
+    fs::write(out_dir.join("js-wasm-workers-engine.cwasm"), precompiled)?;
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+const REDACTED: &str = "[REDACTED]";
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+
+/// A value or a resource the handler receives in its `env` argument.
+#[derive(Clone)]
+pub enum Binding {
+    /// A plain text or JSON value
+    Var(serde_json::Value),
+    /// A value like an API token, which is a string in `env` too
+    Secret(String),
+    /// A key-value store, exposed as a `KVNamespace`
+    Kv(Arc<dyn KvNamespace>),
+}
+
+impl fmt::Debug for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Var(value) => f.debug_tuple("Var").field(value).finish(),
+            Self::Secret(_) => f.debug_tuple("Secret").field(&REDACTED).finish(),
+            Self::Kv(_) => f.debug_tuple("Kv").finish(),
+        }
+    }
+}
+
+/// The binding as sent to the engine, the KV namespaces are accessed through `import_kv`.
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub(crate) enum BindingSource<'a> {
+    Var { value: &'a serde_json::Value },
+    Secret { value: &'a str },
+    Kv,
+}
+
+impl Binding {
+    pub fn var(value: impl Into<serde_json::Value>) -> Self {
+        Self::Var(value.into())
+    }
+
+    pub fn secret(value: impl Into<String>) -> Self {
+        Self::Secret(value.into())
+    }
+
+    pub(crate) fn source(&self) -> BindingSource<'_> {
+        match self {
+            Self::Var(value) => BindingSource::Var { value },
+            Self::Secret(value) => BindingSource::Secret { value },
+            Self::Kv(_) => BindingSource::Kv,
+        }
+    }
+}
+
+/// Replaces the values of the secrets in a text written by the handler, like a log entry.
+pub(crate) fn redact(text: &str, secrets: &[&str]) -> String {
+    secrets
+        .iter()
+        .filter(|secret| !secret.is_empty())
+        .fold(text.to_string(), |text, secret| {
+            text.replace(secret, REDACTED)
+        })
+}
+
+/// A key-value store provided by the host. The values are bytes, the text ones being UTF-8.
+/// The operations are awaited by the handler, so a store can do them over the network.
+#[async_trait]
+pub trait KvNamespace: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+    async fn put(&self, key: &str, value: Vec<u8>) -> Result<()>;
+    async fn delete(&self, key: &str) -> Result<()>;
+    /// Lists the keys starting with the prefix, in lexicographic order
+    async fn list(&self, prefix: &str) -> Result<Vec<String>>;
+}
+
+/// A `KvNamespace` kept in memory, shared by the invocations using it.
+#[derive(Debug, Default)]
+pub struct MemoryKvNamespace {
+    entries: Mutex<BTreeMap<String, Vec<u8>>>,
+}
+
+impl MemoryKvNamespace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl KvNamespace for MemoryKvNamespace {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.entries.lock().unwrap().get(key).cloned())
+    }
+
+    async fn put(&self, key: &str, value: Vec<u8>) -> Result<()> {
+        self.entries.lock().unwrap().insert(key.to_string(), value);
+
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.entries.lock().unwrap().remove(key);
+
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        Ok(self
+            .entries
+            .lock()
+            .unwrap()
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,455 @@
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use serde_json::Value;
+use swc_common::{BytePos, Span};
+use swc_ecma_ast::{
+    CallExpr, Callee, EsVersion, ExportAll, Expr, ExprOrSpread, ImportDecl, Lit, MemberExpr,
+    MemberProp, Module, ModuleItem, NamedExport,
+};
+use swc_ecma_parser::{lexer::Lexer, EsConfig, Parser, StringInput, Syntax};
+use swc_ecma_visit::{Visit, VisitWith};
+
+// The conditions of the package `exports` matched by the bundler, in order of preference
+const CONDITIONS: [&str; 5] = ["worker", "browser", "import", "module", "default"];
+
+const EXTENSIONS: [&str; 2] = ["js", "mjs"];
+
+const SOURCE_MAPPING_URL: &str = "//# sourceMappingURL=";
+
+// The name the engine evaluates the handler with
+const HANDLER_MODULE: &str = "handler.js";
+
+/// A handler with the modules it imports, local or from `node_modules`, as a single artifact.
+///
+/// Its JSON is the source read by the engine, so it loads as it is, and its `modules` can be
+/// passed to `RuntimeConfig::modules`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bundle {
+    pub handler: String,
+    /// The imported modules, keyed by their path from the project root, e.g.
+    /// `/node_modules/pkg/index.js`
+    pub modules: BTreeMap<String, String>,
+    /// The handler and its modules compiled by `compile`, which the engine uses instead of the
+    /// source
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bytecode: Vec<CompiledModule>,
+    /// The source maps referenced by the modules with a `//# sourceMappingURL` comment, keyed by
+    /// the module name, `handler.js` for the handler
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub source_maps: BTreeMap<String, String>,
+}
+
+/// A module compiled to QuickJS bytecode by the engine, which only the same engine can load.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CompiledModule {
+    pub name: String,
+    pub bytecode: ByteBuf,
+}
+
+impl Bundle {
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+
+    pub fn to_vec(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_slice(&fs::read(path)?)
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        Ok(fs::write(path, self.to_vec()?)?)
+    }
+}
+
+/// Bundles the entry file with the modules it imports, following the relative specifiers and
+/// resolving the bare ones from `node_modules`, as Node.js does for ES modules.
+///
+/// The specifiers are rewritten to the keys of the modules, which QuickJS doesn't normalize, so
+/// every import resolves to a single module. The project root is the closest directory of the
+/// entry with a `package.json` or a `node_modules`. Only ES modules are supported, so a CommonJS
+/// module, one using `require` or `module.exports` without any `import` or `export`, is an error
+/// naming its package, which has to be converted first.
+pub fn bundle(entry: impl AsRef<Path>) -> Result<Bundle> {
+    let entry = entry
+        .as_ref()
+        .canonicalize()
+        .with_context(|| format!("Cannot find the entry {}", entry.as_ref().display()))?;
+    let root = project_root(&entry);
+    let mut bundler = Bundler {
+        root,
+        modules: BTreeMap::new(),
+        source_maps: BTreeMap::new(),
+    };
+    let handler = bundler.add(&entry, HANDLER_MODULE)?;
+
+    Ok(Bundle {
+        handler,
+        modules: bundler.modules,
+        bytecode: vec![],
+        source_maps: bundler.source_maps,
+    })
+}
+
+struct Bundler {
+    root: PathBuf,
+    modules: BTreeMap<String, String>,
+    source_maps: BTreeMap<String, String>,
+}
+
+impl Bundler {
+    // Adds the dependencies of the module, returning its source with their specifiers rewritten
+    fn add(&mut self, path: &Path, key: &str) -> Result<String> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Cannot read the module {}", path.display()))?;
+
+        if let Some(source_map) = read_source_map(path, &contents)? {
+            self.source_maps.insert(key.to_string(), source_map);
+        }
+
+        let mut rewritten = String::with_capacity(contents.len());
+        let mut last = 0;
+
+        let module = parse_module(&contents)
+            .with_context(|| format!("Cannot parse the module {}", path.display()))?;
+
+        if is_commonjs(path, &module) {
+            bail!(commonjs_error(path, key));
+        }
+
+        for range in specifier_ranges(&module) {
+            let specifier = &contents[range.clone()];
+            let dependency = self.resolve(path, specifier)?;
+            let key = self.key(&dependency)?;
+
+            if !self.modules.contains_key(&key) {
+                // Inserted before adding it, so an import cycle doesn't recurse forever
+                self.modules.insert(key.clone(), String::new());
+
+                let source = self.add(&dependency, &key)?;
+
+                self.modules.insert(key.clone(), source);
+            }
+
+            rewritten.push_str(&contents[last..range.start]);
+            rewritten.push_str(&key);
+            last = range.end;
+        }
+
+        rewritten.push_str(&contents[last..]);
+
+        Ok(rewritten)
+    }
+
+    fn resolve(&self, importer: &Path, specifier: &str) -> Result<PathBuf> {
+        let not_found = || {
+            anyhow!(
+                "Cannot find module '{specifier}' imported from {}",
+                importer.display()
+            )
+        };
+
+        if specifier.starts_with("./") || specifier.starts_with("../") {
+            let dir = importer.parent().ok_or_else(not_found)?;
+
+            return resolve_file(&dir.join(specifier)).ok_or_else(not_found);
+        }
+
+        if let Some(path) = specifier.strip_prefix('/') {
+            return resolve_file(&self.root.join(path)).ok_or_else(not_found);
+        }
+
+        if specifier.starts_with("node:") {
+            bail!(
+                "Node.js built-in modules aren't supported, '{specifier}' is imported from {}",
+                importer.display()
+            );
+        }
+
+        let (name, subpath) = split_package_specifier(specifier);
+
+        importer
+            .ancestors()
+            .skip(1)
+            .map(|dir| dir.join("node_modules").join(name))
+            .find(|package_dir| package_dir.is_dir())
+            .and_then(|package_dir| resolve_package(&package_dir, subpath))
+            .ok_or_else(not_found)
+    }
+
+    fn key(&self, path: &Path) -> Result<String> {
+        let relative = path.strip_prefix(&self.root).map_err(|_| {
+            anyhow!(
+                "The module {} is outside of the project root {}",
+                path.display(),
+                self.root.display()
+            )
+        })?;
+
+        Ok(relative
+            .components()
+            .map(|component| format!("/{}", component.as_os_str().to_string_lossy()))
+            .collect())
+    }
+}
+
+// Only the source maps in a file are read, the inline `data:` ones aren't supported
+fn read_source_map(path: &Path, contents: &str) -> Result<Option<String>> {
+    let Some(url) = contents
+        .lines()
+        .rev()
+        .find_map(|line| line.trim().strip_prefix(SOURCE_MAPPING_URL))
+        .map(str::trim)
+    else {
+        return Ok(None);
+    };
+
+    if url.is_empty() || url.contains(':') {
+        return Ok(None);
+    }
+
+    let source_map = path.parent().unwrap_or(path).join(url);
+
+    fs::read_to_string(&source_map)
+        .map(Some)
+        .with_context(|| format!("Cannot read the source map {}", source_map.display()))
+}
+
+fn project_root(entry: &Path) -> PathBuf {
+    let dir = entry.parent().unwrap_or(entry);
+
+    dir.ancestors()
+        .find(|dir| dir.join("package.json").is_file() || dir.join("node_modules").is_dir())
+        .unwrap_or(dir)
+        .to_path_buf()
+}
+
+// Tries the path as it is, with an extension, and as a directory with an index
+fn resolve_file(path: &Path) -> Option<PathBuf> {
+    let with_extension = |path: &Path, extension: &str| {
+        let mut path = OsString::from(path);
+
+        path.push(".");
+        path.push(extension);
+
+        PathBuf::from(path)
+    };
+
+    std::iter::once(path.to_path_buf())
+        .chain(
+            EXTENSIONS
+                .iter()
+                .map(|extension| with_extension(path, extension)),
+        )
+        .chain(
+            EXTENSIONS
+                .iter()
+                .map(|extension| with_extension(&path.join("index"), extension)),
+        )
+        .find(|path| path.is_file())
+        .and_then(|path| path.canonicalize().ok())
+}
+
+// @see: https://nodejs.org/api/packages.html#package-entry-points
+fn resolve_package(dir: &Path, subpath: &str) -> Option<PathBuf> {
+    let package = fs::read(dir.join("package.json"))
+        .ok()
+        .and_then(|package| serde_json::from_slice::<Value>(&package).ok())
+        .unwrap_or_default();
+
+    // When there are `exports`, they are the only entry points of the package
+    if let Some(exports) = package.get("exports") {
+        let subpath = if subpath.is_empty() {
+            ".".to_string()
+        } else {
+            format!("./{subpath}")
+        };
+        let is_subpath_map = exports.as_object().map_or(false, |exports| {
+            exports.keys().all(|key| key.starts_with('.'))
+        });
+        let target = match exports {
+            Value::Object(exports) if is_subpath_map => exports.get(&subpath),
+            _ if subpath == "." => Some(exports),
+            _ => None,
+        };
+
+        return target
+            .and_then(conditional_target)
+            .and_then(|target| resolve_file(&dir.join(target)));
+    }
+
+    if !subpath.is_empty() {
+        return resolve_file(&dir.join(subpath));
+    }
+
+    ["module", "main"]
+        .iter()
+        .filter_map(|field| package.get(field).and_then(Value::as_str))
+        .find_map(|main| resolve_file(&dir.join(main)))
+        .or_else(|| resolve_file(&dir.join("index")))
+}
+
+fn conditional_target(target: &Value) -> Option<&str> {
+    match target {
+        Value::String(target) => Some(target),
+        Value::Object(conditions) => CONDITIONS
+            .iter()
+            .filter_map(|condition| conditions.get(*condition))
+            .find_map(conditional_target),
+        Value::Array(targets) => targets.iter().find_map(conditional_target),
+        _ => None,
+    }
+}
+
+// Splits `@scope/name/subpath` and `name/subpath` into the package name and the subpath
+fn split_package_specifier(specifier: &str) -> (&str, &str) {
+    let name_segments = if specifier.starts_with('@') { 2 } else { 1 };
+    let end = specifier
+        .match_indices('/')
+        .nth(name_segments - 1)
+        .map_or(specifier.len(), |(index, _)| index);
+
+    (
+        &specifier[..end],
+        specifier.get(end + 1..).unwrap_or_default(),
+    )
+}
+
+// Parses the contents as an ES module, an error having the offset of the invalid token
+pub(crate) fn parse_module(contents: &str) -> Result<Module> {
+    // The positions start at 1, the spans at 0 being the ones which aren't in the source
+    let input = StringInput::new(contents, BytePos(1), BytePos(1 + contents.len() as u32));
+    let lexer = Lexer::new(
+        Syntax::Es(EsConfig::default()),
+        EsVersion::latest(),
+        input,
+        None,
+    );
+    Parser::new_from(lexer).parse_module().map_err(|error| {
+        anyhow!(
+            "{} at offset {}",
+            error.kind().msg(),
+            error.span().lo.0.saturating_sub(1)
+        )
+    })
+}
+
+// The ranges of the specifiers of the static imports and re-exports, and of the dynamic imports
+// with a string literal. As they come from the parsed module, the quotes of the comments, strings,
+// templates and regular expressions are never taken for a specifier.
+pub(crate) fn specifier_ranges(module: &Module) -> Vec<Range<usize>> {
+    let mut specifiers = Specifiers::default();
+
+    module.visit_with(&mut specifiers);
+    specifiers.spans.sort_by_key(|span| span.lo);
+
+    // The spans of the string literals include their quotes
+    specifiers
+        .spans
+        .into_iter()
+        .map(|span| span.lo.0 as usize..span.hi.0 as usize - 2)
+        .collect()
+}
+
+// A `.cjs` file, or a module without any `import` or `export` which uses `require` or `exports`.
+// The scopes aren't resolved, so a local named `require` or `exports` is taken for CommonJS too.
+fn is_commonjs(path: &Path, module: &Module) -> bool {
+    if path
+        .extension()
+        .map_or(false, |extension| extension == "cjs")
+    {
+        return true;
+    }
+
+    if module.body.iter().any(ModuleItem::is_module_decl) {
+        return false;
+    }
+
+    let mut commonjs = CommonJs::default();
+
+    module.visit_with(&mut commonjs);
+
+    commonjs.found
+}
+
+fn commonjs_error(path: &Path, key: &str) -> String {
+    // The package is the last one in the key, e.g. `b` in `/node_modules/a/node_modules/b/index.js`
+    let package = key
+        .rsplit_once("/node_modules/")
+        .map(|(_, specifier)| format!(" of the package '{}'", split_package_specifier(specifier).0))
+        .unwrap_or_default();
+
+    format!(
+        "Cannot bundle the CommonJS module {}{package}, only ES modules are supported",
+        path.display()
+    )
+}
+
+#[derive(Default)]
+struct Specifiers {
+    spans: Vec<Span>,
+}
+
+impl Visit for Specifiers {
+    fn visit_import_decl(&mut self, import: &ImportDecl) {
+        self.spans.push(import.src.span);
+    }
+
+    fn visit_export_all(&mut self, export: &ExportAll) {
+        self.spans.push(export.src.span);
+    }
+
+    fn visit_named_export(&mut self, export: &NamedExport) {
+        if let Some(src) = &export.src {
+            self.spans.push(src.span);
+        }
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let (Callee::Import(_), Some(ExprOrSpread { spread: None, expr })) =
+            (&call.callee, call.args.first())
+        {
+            if let Expr::Lit(Lit::Str(specifier)) = &**expr {
+                self.spans.push(specifier.span);
+            }
+        }
+
+        call.visit_children_with(self);
+    }
+}
+
+#[derive(Default)]
+struct CommonJs {
+    found: bool,
+}
+
+impl Visit for CommonJs {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            self.found |= matches!(&**callee, Expr::Ident(ident) if &*ident.sym == "require");
+        }
+
+        call.visit_children_with(self);
+    }
+
+    fn visit_member_expr(&mut self, member: &MemberExpr) {
+        if let Expr::Ident(object) = &*member.obj {
+            self.found |= &*object.sym == "exports"
+                || (&*object.sym == "module"
+                    && matches!(&member.prop, MemberProp::Ident(prop) if &*prop.sym == "exports"));
+        }
+
+        member.visit_children_with(self);
+    }
+}
@@ -0,0 +1,62 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+
+use anyhow::{anyhow, Result};
+use wasi_common::pipe::{ReadPipe, WritePipe};
+use wasmtime::Store;
+use wasmtime_wasi::tokio::WasiCtxBuilder;
+
+use crate::{bundle::Bundle, worker_state::WorkerState, WASMTIME_ENVIRONMENT};
+
+// Runs the engine as a compiler, with the mode as value
+const COMPILE_ENV: &str = "JS_WASM_WORKERS_COMPILE";
+
+/// Compiles the bundle to QuickJS bytecode with the engine, so the invocations skip the parsing.
+/// The compiled bundle doesn't include the source, and only runs on the same engine.
+pub async fn compile(bundle: &Bundle) -> Result<Bundle> {
+    let output = run_compiler("modules", serde_json::to_vec(bundle)?).await?;
+
+    Ok(Bundle {
+        handler: String::new(),
+        modules: BTreeMap::new(),
+        bytecode: serde_json::from_slice(&output)?,
+        // The bytecode keeps the module names, so the stack is remapped the same way
+        source_maps: bundle.source_maps.clone(),
+    })
+}
+
+/// Compiles a global script to QuickJS bytecode, as the engine embeds the web platform APIs with
+/// its `bytecode` feature.
+pub async fn compile_script(script: &str) -> Result<Vec<u8>> {
+    run_compiler("script", script.as_bytes().to_vec()).await
+}
+
+async fn run_compiler(mode: &str, input: Vec<u8>) -> Result<Vec<u8>> {
+    let stdout = Arc::new(RwLock::new(vec![]));
+    let wasi = WasiCtxBuilder::new()
+        .stdin(Box::new(ReadPipe::from(input)))
+        .stdout(Box::new(WritePipe::from_shared(stdout.clone())))
+        .inherit_stderr()
+        .env(COMPILE_ENV, mode)?
+        .build();
+    let environment = &*WASMTIME_ENVIRONMENT;
+    let mut store = Store::new(&environment.engine, WorkerState::new(wasi, None));
+
+    store.add_fuel(u64::MAX)?;
+
+    let instance = environment
+        .linker
+        .instantiate_async(&mut store, &environment.module)
+        .await?;
+
+    instance
+        .get_typed_func::<(), ()>(&mut store, "_start")?
+        .call_async(&mut store, ())
+        .await?;
+
+    let output = stdout.read().map_err(|e| anyhow!("{e:?}"))?.clone();
+
+    Ok(output)
+}
@@ -0,0 +1,133 @@
+use std::io::{self, Write};
+
+use brotli::CompressorWriter;
+use flate2::{
+    write::{GzEncoder, ZlibEncoder},
+    Compression,
+};
+
+// The responses smaller than this aren't worth compressing
+const MIN_SIZE: usize = 1024;
+
+// The quality and window of the brotli encoder, the quality 11 is too slow to compress on the fly
+const BROTLI_QUALITY: u32 = 5;
+const BROTLI_WINDOW: u32 = 22;
+const BROTLI_BUFFER_SIZE: usize = 4096;
+
+/// The content codings the server compresses the responses with, by order of preference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Brotli,
+    Gzip,
+    Deflate,
+}
+
+impl Encoding {
+    const ALL: [Encoding; 3] = [Encoding::Brotli, Encoding::Gzip, Encoding::Deflate];
+
+    /// The name of the coding, as in `Content-Encoding`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Brotli => "br",
+            Encoding::Gzip => "gzip",
+            Encoding::Deflate => "deflate",
+        }
+    }
+}
+
+/// Picks the coding with the highest weight in the `Accept-Encoding` of the request, the
+/// preferred one among those with the same weight. `*` matches the codings not listed, and a
+/// weight of `0` refuses the coding.
+///
+/// @see: https://httpwg.org/specs/rfc9110.html#field.accept-encoding
+pub fn negotiate(accept_encoding: &str) -> Option<Encoding> {
+    let codings = accept_encoding
+        .split(',')
+        .filter_map(|coding| {
+            let mut params = coding.split(';');
+            let name = params.next()?.trim().to_ascii_lowercase();
+            let weight = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|weight| weight.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            (!name.is_empty()).then_some((name, weight))
+        })
+        .collect::<Vec<_>>();
+    let weight = |name: &str| {
+        codings
+            .iter()
+            .find(|(coding, _)| coding == name)
+            .or_else(|| codings.iter().find(|(coding, _)| coding == "*"))
+            .map(|(_, weight)| *weight)
+    };
+
+    let mut best: Option<(Encoding, f32)> = None;
+
+    for encoding in Encoding::ALL {
+        match weight(encoding.name()) {
+            // The preferred coding wins the ties
+            Some(weight) if weight > 0.0 && best.map_or(true, |(_, best)| weight > best) => {
+                best = Some((encoding, weight));
+            }
+            _ => {}
+        }
+    }
+
+    best.map(|(encoding, _)| encoding)
+}
+
+/// Whether a response is worth compressing: it isn't encoded yet, it is large enough, and its
+/// content type is text, or another format that compresses well.
+pub fn is_compressible(
+    content_type: Option<&str>,
+    content_encoding: Option<&str>,
+    size: usize,
+) -> bool {
+    if content_encoding.is_some() || size < MIN_SIZE {
+        return false;
+    }
+
+    let essence = content_type
+        .and_then(|content_type| content_type.split(';').next())
+        .map(|essence| essence.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    essence.starts_with("text/")
+        || essence.ends_with("+json")
+        || essence.ends_with("+xml")
+        || matches!(
+            essence.as_str(),
+            "application/json"
+                | "application/javascript"
+                | "application/xml"
+                | "application/wasm"
+                | "image/svg+xml"
+        )
+}
+
+/// Compresses the whole body with the coding.
+pub fn compress(encoding: Encoding, body: &[u8]) -> io::Result<Vec<u8>> {
+    match encoding {
+        Encoding::Brotli => {
+            let mut encoder =
+                CompressorWriter::new(vec![], BROTLI_BUFFER_SIZE, BROTLI_QUALITY, BROTLI_WINDOW);
+
+            encoder.write_all(body)?;
+
+            Ok(encoder.into_inner())
+        }
+        Encoding::Gzip => {
+            let mut encoder = GzEncoder::new(vec![], Compression::default());
+
+            encoder.write_all(body)?;
+            encoder.finish()
+        }
+        Encoding::Deflate => {
+            let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+
+            encoder.write_all(body)?;
+            encoder.finish()
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use std::{collections::BTreeMap, env, fmt, sync::Arc};
+
+use crate::{
+    bindings::Binding,
+    error::{ErrorConfig, HandlerError},
+    logs::{LogEntry, LogSink},
+    metrics::{Metrics, MetricsRegistry},
+    wasmtime_environment::WasmtimeEnvironment,
+};
+
+/// Per worker settings used by `runtime_with_config`.
+#[derive(Clone, Default)]
+pub struct RuntimeConfig {
+    /// Identifies the worker in its log entries
+    pub worker_id: String,
+    /// Receives the log entries of each invocation, besides returning them in the output
+    pub log_sink: Option<Arc<dyn LogSink>>,
+    /// Aggregates the metrics of each invocation, besides returning them in the output
+    pub metrics_registry: Option<Arc<MetricsRegistry>>,
+    /// The engine used instead of the shared one, e.g. to use the pooling allocator
+    pub environment: Option<WasmtimeEnvironment>,
+    /// Modules the handler can import, keyed by their path relative to it, e.g. `lib/utils.js`
+    pub modules: BTreeMap<String, String>,
+    /// The source maps of the handler, as `handler.js`, and of its modules, keyed by their name, to
+    /// remap the stack of the errors to the original source, e.g. TypeScript
+    pub source_maps: BTreeMap<String, String>,
+    /// The bindings of the `env` received by the handler, keyed by their name. The vars and
+    /// secrets are in `process.env` too, and the secrets are redacted from the logs and errors.
+    pub bindings: BTreeMap<String, Binding>,
+    /// The variables of the host environment passed to the handler as vars, the bindings with the
+    /// same name take precedence. The rest of the host environment isn't visible to the handler.
+    pub inherit_env: Vec<String>,
+    /// The responses sent when the handler fails
+    pub errors: ErrorConfig,
+}
+
+impl fmt::Debug for RuntimeConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RuntimeConfig")
+            .field("worker_id", &self.worker_id)
+            .field("log_sink", &self.log_sink.is_some())
+            .field("metrics_registry", &self.metrics_registry)
+            .field("environment", &self.environment.is_some())
+            .field("modules", &self.modules.keys().collect::<Vec<_>>())
+            .field("source_maps", &self.source_maps.keys().collect::<Vec<_>>())
+            .field("bindings", &self.bindings)
+            .field("inherit_env", &self.inherit_env)
+            .field("errors", &self.errors)
+            .finish()
+    }
+}
+
+impl RuntimeConfig {
+    // The bindings with the allow-listed variables of the host environment
+    pub(crate) fn env(&self) -> BTreeMap<String, Binding> {
+        self.inherit_env
+            .iter()
+            .filter_map(|name| {
+                env::var(name)
+                    .ok()
+                    .map(|value| (name.clone(), Binding::var(value)))
+            })
+            .chain(
+                self.bindings
+                    .iter()
+                    .map(|(name, binding)| (name.clone(), binding.clone())),
+            )
+            .collect()
+    }
+
+    pub(crate) fn secrets(&self) -> Vec<&str> {
+        self.bindings
+            .values()
+            .filter_map(|binding| match binding {
+                Binding::Secret(secret) => Some(secret.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// The result of running a handler.
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeOutput {
+    /// The serialized response returned by the handler
+    pub response: Vec<u8>,
+    /// The `console` entries written by the handler
+    pub logs: Vec<LogEntry>,
+    /// The resources used by the invocation
+    pub metrics: Metrics,
+    /// The error of the handler, when the response is an error response or it failed after
+    /// responding
+    pub error: Option<HandlerError>,
+}
@@ -0,0 +1,39 @@
+use wasmtime::{Config, InstanceAllocationStrategy, PoolingAllocationConfig};
+
+/// How the instances of the engine are allocated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AllocationStrategy {
+    /// Maps the memory of each instance when it is created
+    #[default]
+    OnDemand,
+    /// Reserves the memory of the instances up front and reuses it, for high-concurrency serving
+    Pooling {
+        /// Maximum number of instances alive at the same time, the next instantiations fail
+        max_instances: u32,
+        /// Maximum linear memory of each instance, in WebAssembly pages of 64 KiB
+        memory_pages: u64,
+    },
+}
+
+// It is shared with the build script, as a precompiled module only loads into an engine with the same configuration
+pub fn engine_config(allocation_strategy: AllocationStrategy) -> Config {
+    let mut config = Config::new();
+
+    config.async_support(true).consume_fuel(true);
+
+    if let AllocationStrategy::Pooling {
+        max_instances,
+        memory_pages,
+    } = allocation_strategy
+    {
+        let mut pooling = PoolingAllocationConfig::default();
+
+        pooling
+            .instance_count(max_instances)
+            .instance_memory_pages(memory_pages);
+
+        config.allocation_strategy(InstanceAllocationStrategy::Pooling(pooling));
+    }
+
+    config
+}
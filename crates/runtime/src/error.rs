@@ -0,0 +1,209 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::Arc,
+};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use serde_json::json;
+use sourcemap::SourceMap;
+
+use crate::{bindings::redact, http::HandlerResponse};
+
+const DEFAULT_STATUS: u16 = 500;
+
+/// What failed in the handler.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// An uncaught exception, or a rejection, of the handler or its evaluation
+    #[default]
+    Exception,
+    /// The handler doesn't export nor register a request handler
+    MissingHandler,
+    /// The handler returned something else than a `Response`
+    InvalidResponse,
+}
+
+/// An error thrown by the handler, set in `RuntimeOutput::error` along with the error response.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HandlerError {
+    pub name: String,
+    pub message: String,
+    /// The QuickJS stack, with the frames of the modules with a source map remapped to their source
+    pub stack: Option<String>,
+    #[serde(default)]
+    pub kind: ErrorKind,
+}
+
+impl HandlerError {
+    /// Remaps the frames of the stack, e.g. `at fetch (handler.js:12)`, to their original location.
+    pub(crate) fn remap(mut self, source_maps: &BTreeMap<String, String>) -> Self {
+        if source_maps.is_empty() {
+            return self;
+        }
+
+        let source_maps = source_maps
+            .iter()
+            .filter_map(|(name, source_map)| {
+                SourceMap::from_slice(source_map.as_bytes())
+                    .ok()
+                    .map(|source_map| (name.as_str(), source_map))
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        self.stack = self.stack.map(|stack| {
+            stack
+                .split('\n')
+                .map(|frame| remap_frame(frame, &source_maps).unwrap_or_else(|| frame.to_string()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+
+        self
+    }
+
+    /// Replaces the values of the secrets in the message and the stack.
+    pub(crate) fn redact(self, secrets: &[&str]) -> Self {
+        Self {
+            message: redact(&self.message, secrets),
+            stack: self.stack.map(|stack| redact(&stack, secrets)),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for HandlerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.message)
+    }
+}
+
+impl std::error::Error for HandlerError {}
+
+/// The response sent when the handler fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorResponse {
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl ErrorResponse {
+    // Serialized as the responses of the engine
+    pub(crate) fn to_vec(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&HandlerResponse {
+            body: Some(ByteBuf::from(self.body.clone())),
+            headers: Some(self.headers.clone()),
+            status: self.status,
+            status_text: None,
+            web_socket: None,
+        })?)
+    }
+}
+
+/// Customizes the response of the handler errors, e.g. to render an error page.
+pub trait ErrorHook: Send + Sync {
+    /// Receives the response built from the `ErrorConfig`, and returns the one to send.
+    fn on_error(&self, error: &HandlerError, response: ErrorResponse) -> ErrorResponse;
+}
+
+/// How the handler errors are turned into responses, `500 Internal Server Error` by default.
+#[derive(Clone, Default)]
+pub struct ErrorConfig {
+    /// The status of the responses by kind of error, the missing ones are `500`
+    pub statuses: HashMap<ErrorKind, u16>,
+    /// Responds with the error and its stack as JSON, which must only be enabled in development
+    pub debug: bool,
+    /// Called with each error response before it is sent
+    pub hook: Option<Arc<dyn ErrorHook>>,
+}
+
+impl ErrorConfig {
+    pub fn response(&self, error: &HandlerError) -> ErrorResponse {
+        let status = self
+            .statuses
+            .get(&error.kind)
+            .copied()
+            .unwrap_or(DEFAULT_STATUS);
+        let (content_type, body) = if self.debug {
+            (
+                "application/json",
+                json!({ "error": error }).to_string().into_bytes(),
+            )
+        } else {
+            (
+                "text/plain;charset=UTF-8",
+                reason(status).as_bytes().to_vec(),
+            )
+        };
+        let response = ErrorResponse {
+            status,
+            headers: HashMap::from([("content-type".to_string(), content_type.to_string())]),
+            body,
+        };
+
+        match &self.hook {
+            Some(hook) => hook.on_error(error, response),
+            None => response,
+        }
+    }
+}
+
+impl fmt::Debug for ErrorConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErrorConfig")
+            .field("statuses", &self.statuses)
+            .field("debug", &self.debug)
+            .field("hook", &self.hook.is_some())
+            .finish()
+    }
+}
+
+// The body of the error responses outside of the debug mode, which doesn't expose the error
+fn reason(status: u16) -> &'static str {
+    match status {
+        400 => "Bad Request",
+        404 => "Not Found",
+        501 => "Not Implemented",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Internal Server Error",
+    }
+}
+
+// The location of a frame is `module:line` or `module:line:column`, with one based numbers
+fn remap_frame(frame: &str, source_maps: &BTreeMap<&str, SourceMap>) -> Option<String> {
+    let start = frame.rfind('(')? + 1;
+    let end = start + frame[start..].find(')')?;
+    let location = &frame[start..end];
+    let (rest, last) = location.rsplit_once(':')?;
+    let last = last.parse::<u32>().ok()?;
+    let (module, line, column) = match rest.rsplit_once(':') {
+        Some((module, line)) if line.parse::<u32>().is_ok() => {
+            (module, line.parse::<u32>().ok()?, Some(last))
+        }
+        _ => (rest, last, None),
+    };
+    let source_map = source_maps.get(module)?;
+    let line = line.checked_sub(1)?;
+    let token = match column {
+        Some(column) => source_map.lookup_token(line, column.saturating_sub(1)),
+        // Without a column, the first mapping of the line
+        None => source_map
+            .tokens()
+            .find(|token| token.get_dst_line() == line),
+    }?;
+
+    Some(format!(
+        "{}{}:{}:{}{}",
+        &frame[..start],
+        token.get_source()?,
+        token.get_src_line() + 1,
+        token.get_src_col() + 1,
+        &frame[end..]
+    ))
+}
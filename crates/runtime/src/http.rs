@@ -18,6 +18,18 @@ pub struct Response {
     pub status: usize,
 }
 
+/// The response returned by the handler, as serialized by the engine
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HandlerResponse {
+    pub body: Option<ByteBuf>,
+    pub headers: Option<HashMap<String, String>>,
+    pub status: u16,
+    pub status_text: Option<String>,
+    /// Set when the handler responds with the client end of a `WebSocketPair`
+    pub web_socket: Option<bool>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct BoxedRequestError(Box<RequestError>);
 
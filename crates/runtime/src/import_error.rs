@@ -0,0 +1,18 @@
+use std::future::Future;
+
+use wasmtime::*;
+
+use crate::{error::HandlerError, mem::read_string, worker_state::WorkerState};
+
+// Receives the error of the handler as `{name, message, stack}`, before the guest exits
+pub(crate) fn import_error(
+    mut caller: Caller<'_, WorkerState>,
+    ptr: i32,
+) -> Box<dyn Future<Output = ()> + Send + '_> {
+    Box::new(async move {
+        let memory = caller.get_export("memory").unwrap().into_memory().unwrap();
+        let error = read_string(&mut caller, &memory, ptr).await;
+
+        caller.data_mut().error = serde_json::from_str::<HandlerError>(error).ok();
+    })
+}
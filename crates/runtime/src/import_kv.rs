@@ -0,0 +1,100 @@
+use std::{collections::HashMap, future::Future, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use wasmtime::*;
+
+use crate::{
+    bindings::KvNamespace,
+    mem::{read_bytes, read_string, stack_push, write_bytes, write_string},
+    worker_state::WorkerState,
+};
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct KvRequest {
+    pub binding: String,
+    #[serde(flatten)]
+    pub operation: KvOperation,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "operation", rename_all = "lowercase")]
+pub(crate) enum KvOperation {
+    Get { key: String },
+    // The value is passed apart from the operation, as its bytes
+    Put { key: String },
+    Delete { key: String },
+    List { prefix: String },
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum KvResult {
+    Value(serde_json::Value),
+    /// The value of a key, which is written to the memory as it is rather than as JSON
+    #[serde(skip)]
+    Bytes(Vec<u8>),
+    Error(String),
+}
+
+/// Runs the operation at `ptr` with the value at `value_ptr`, which is empty except to `put`.
+/// The result is written to the memory followed by a flag on the stack, which is 1 when it's the
+/// bytes of a value and 0 when it's the JSON of a `KvResult`.
+pub(crate) fn import_kv(
+    mut caller: Caller<'_, WorkerState>,
+    ptr: i32,
+    value_ptr: i32,
+) -> Box<dyn Future<Output = i32> + Send + '_> {
+    Box::new(async move {
+        let memory = caller.get_export("memory").unwrap().into_memory().unwrap();
+        // The length of the value is the last one pushed to the stack
+        let value = read_bytes(&mut caller, &memory, value_ptr).await.to_vec();
+        let request = read_string(&mut caller, &memory, ptr).await;
+        // The namespaces are cloned, so the caller isn't borrowed while the operation is awaited
+        let namespaces = caller.data().kv_namespaces.clone();
+        let result = run_kv_request(&namespaces, request, value).await;
+        let (ptr, is_bytes) = match result {
+            KvResult::Bytes(bytes) => (write_bytes(&mut caller, &memory, &bytes).await, 1),
+            result => {
+                let json = serde_json::to_string(&result).unwrap();
+
+                (write_string(&mut caller, &memory, json.as_str()).await, 0)
+            }
+        };
+
+        stack_push(&mut caller, is_bytes).await;
+
+        ptr
+    })
+}
+
+pub(crate) async fn run_kv_request(
+    namespaces: &HashMap<String, Arc<dyn KvNamespace>>,
+    request: &str,
+    value: Vec<u8>,
+) -> KvResult {
+    let request = match serde_json::from_str::<KvRequest>(request) {
+        Ok(request) => request,
+        Err(error) => return KvResult::Error(format!("Invalid KV operation: {error}")),
+    };
+    let Some(namespace) = namespaces.get(&request.binding) else {
+        return KvResult::Error(format!("Unknown KV namespace: {}", request.binding));
+    };
+
+    let result = match request.operation {
+        KvOperation::Get { key } => match namespace.get(&key).await {
+            Ok(Some(bytes)) => return KvResult::Bytes(bytes),
+            result => result.map(|_| serde_json::Value::Null),
+        },
+        KvOperation::Put { key } => namespace
+            .put(&key, value)
+            .await
+            .map(|_| serde_json::Value::Null),
+        KvOperation::Delete { key } => namespace
+            .delete(&key)
+            .await
+            .map(|_| serde_json::Value::Null),
+        KvOperation::List { prefix } => namespace.list(&prefix).await.map(serde_json::Value::from),
+    };
+
+    result.map_or_else(|error| KvResult::Error(error.to_string()), KvResult::Value)
+}
@@ -0,0 +1,27 @@
+use std::future::Future;
+
+use wasmtime::*;
+
+use crate::{mem::read_string, worker_state::WorkerState};
+
+// Receives the response before the guest finishes, as it may keep running the work registered with
+// `ctx.waitUntil`
+pub(crate) fn import_respond(
+    mut caller: Caller<'_, WorkerState>,
+    ptr: i32,
+) -> Box<dyn Future<Output = ()> + Send + '_> {
+    Box::new(async move {
+        let memory = caller.get_export("memory").unwrap().into_memory().unwrap();
+        let response = read_string(&mut caller, &memory, ptr)
+            .await
+            .as_bytes()
+            .to_vec();
+        let state = caller.data_mut();
+
+        if let Some(sender) = state.response_sender.take() {
+            let _ = sender.send(response.clone());
+        }
+
+        state.response = Some(response);
+    })
+}
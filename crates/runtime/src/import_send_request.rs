@@ -1,9 +1,4 @@
-use std::{
-    collections::HashMap,
-    future::Future,
-    slice,
-    str::{self, FromStr},
-};
+use std::{collections::HashMap, future::Future, str::FromStr};
 
 use anyhow::Result;
 use reqwest::{
@@ -12,27 +7,53 @@ use reqwest::{
     Body,
 };
 use serde_bytes::ByteBuf;
+use tracing::Instrument;
 use wasmtime::*;
-use wasmtime_wasi::WasiCtx;
 
 use super::http::{Request, RequestError, RequestErrorKind, Response};
+use crate::{
+    mem::{read_string, write_string},
+    trace::{outbound_traceparent, TRACEPARENT},
+    worker_state::WorkerState,
+};
 
 pub(crate) fn import_send_request(
-    mut caller: Caller<'_, WasiCtx>,
+    mut caller: Caller<'_, WorkerState>,
     ptr: i32,
 ) -> Box<dyn Future<Output = i32> + Send + '_> {
     Box::new(async move {
         let memory = caller.get_export("memory").unwrap().into_memory().unwrap();
         let request = read_string(&mut caller, &memory, ptr).await;
         let request = serde_json::from_str::<Request>(request).unwrap();
+        let span = tracing::info_span!(
+            parent: &caller.data().current_span(),
+            "import_send_request",
+            http.method = %request.method,
+            http.url = %request.url,
+            http.status_code = tracing::field::Empty,
+        );
         let client = reqwest::Client::new();
         let method = reqwest::Method::from_str(&request.method).unwrap();
         let url = reqwest::Url::from_str(&request.url).unwrap();
+        let request_bytes = request.body.as_ref().map_or(0, |body| body.len());
         let body = match request.body {
             Some(body) => Body::from(body.into_vec()),
             None => Body::from(""),
         };
-        let headers = request_headers(request.headers.unwrap()).unwrap();
+        let mut headers = request.headers.unwrap();
+
+        if !headers
+            .keys()
+            .any(|name| name.eq_ignore_ascii_case(TRACEPARENT))
+        {
+            if let Some(traceparent) =
+                outbound_traceparent(&span, caller.data().trace_parent.as_ref())
+            {
+                headers.insert(TRACEPARENT.to_string(), traceparent);
+            }
+        }
+
+        let headers = request_headers(headers).unwrap();
 
         // TODO: trace errors
         // @see: https://github.com/fermyon/spin/blob/13f7916523f1fd4ab4b6c46d098c28e50baf2843/crates/outbound-http/src/lib.rs#L56
@@ -41,9 +62,20 @@ pub(crate) fn import_send_request(
             .headers(headers)
             .body(body)
             .send()
+            .instrument(span.clone())
             .await;
 
-        let response = parse_response(response).await.unwrap();
+        if let Ok(response) = &response {
+            span.record("http.status_code", response.status().as_u16());
+        }
+
+        let response = parse_response(response).instrument(span).await.unwrap();
+        let state = caller.data_mut();
+
+        state.outbound_requests += 1;
+        state.outbound_request_bytes += request_bytes as u64;
+        state.outbound_response_bytes += response.body.as_ref().map_or(0, |body| body.len()) as u64;
+
         let json = serde_json::to_string(&response).unwrap();
 
         write_string(&mut caller, &memory, json.as_str()).await
@@ -58,71 +90,6 @@ fn request_headers(headers: HashMap<String, String>) -> anyhow::Result<HeaderMap
     Ok(header_map)
 }
 
-async fn read_string<'c, 'm>(
-    caller: &'c mut Caller<'_, WasiCtx>,
-    memory: &'m Memory,
-    ptr: i32,
-) -> &'m str {
-    let len = stack_pop(caller).await as usize;
-
-    unsafe {
-        let ptr = memory.data_ptr(&caller).offset(ptr as isize);
-        let bytes = slice::from_raw_parts(ptr, len);
-        std::str::from_utf8(bytes).unwrap()
-    }
-}
-
-async fn write_string<'c, 'm>(
-    caller: &'c mut Caller<'_, WasiCtx>,
-    memory: &'m Memory,
-    value: &str,
-) -> i32 {
-    let alloc_func = caller.get_export("alloc").unwrap().into_func().unwrap();
-
-    let ptr = alloc_func
-        .typed::<i32, i32>(caller.as_context())
-        .unwrap()
-        .call_async(caller.as_context_mut(), value.len() as i32)
-        .await
-        .unwrap();
-
-    stack_push(caller, value.len() as i32).await;
-
-    memory
-        .write(caller.as_context_mut(), ptr as usize, value.as_bytes())
-        .unwrap();
-
-    ptr
-}
-
-async fn stack_push<'c, 'm>(caller: &'c mut Caller<'_, WasiCtx>, value: i32) {
-    let push_fn = caller
-        .get_export("stack_push")
-        .unwrap()
-        .into_func()
-        .unwrap();
-
-    push_fn
-        .typed::<i32, ()>(&caller)
-        .unwrap()
-        .call_async(caller, value)
-        .await
-        .unwrap();
-}
-
-async fn stack_pop<'c, 'm>(caller: &'c mut Caller<'_, WasiCtx>) -> i32 {
-    let pop_fn = caller.get_export("stack_pop").unwrap().into_func().unwrap();
-
-    let value = pop_fn
-        .typed::<(), i32>(&caller)
-        .unwrap()
-        .call_async(caller, ())
-        .await
-        .unwrap();
-
-    value
-}
-
 async fn parse_response(
     response: reqwest::Result<reqwest::Response>,
 ) -> Result<Response, RequestError> {
@@ -0,0 +1,49 @@
+use std::future::Future;
+
+use wasmtime::*;
+
+use crate::{
+    mem::{read_string, write_string},
+    websocket::WebSocketMessage,
+    worker_state::WorkerState,
+};
+
+// Sends a message of the handler to the client, it is dropped when the connection isn't upgraded
+pub(crate) fn import_websocket_send(
+    mut caller: Caller<'_, WorkerState>,
+    ptr: i32,
+) -> Box<dyn Future<Output = ()> + Send + '_> {
+    Box::new(async move {
+        let memory = caller.get_export("memory").unwrap().into_memory().unwrap();
+        let message = read_string(&mut caller, &memory, ptr).await;
+
+        let Ok(message) = serde_json::from_str::<WebSocketMessage>(message) else {
+            return;
+        };
+
+        if let Some(channel) = &caller.data().websocket {
+            let _ = channel.outgoing.send(message);
+        }
+    })
+}
+
+// Waits for the next message of the client, the connection is considered lost when the host stops
+// relaying them
+pub(crate) fn import_websocket_receive(
+    mut caller: Caller<'_, WorkerState>,
+) -> Box<dyn Future<Output = i32> + Send + '_> {
+    Box::new(async move {
+        let memory = caller.get_export("memory").unwrap().into_memory().unwrap();
+        let message = match caller.data_mut().websocket.as_mut() {
+            Some(channel) => channel.incoming.recv().await,
+            None => None,
+        }
+        .unwrap_or(WebSocketMessage::Close {
+            code: 1006,
+            reason: String::new(),
+        });
+        let json = serde_json::to_string(&message).unwrap();
+
+        write_string(&mut caller, &memory, json.as_str()).await
+    })
+}
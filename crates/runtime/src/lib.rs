@@ -1,64 +1,319 @@
 use std::{
-    str,
+    io, str,
     sync::{Arc, RwLock},
+    time::Instant,
 };
 
 use wasmtime_environment::WasmtimeEnvironment;
 
+use anyhow::anyhow;
 use lazy_static::lazy_static;
+use tokio::{sync::oneshot, task::JoinHandle};
+use tracing::Instrument;
 use wasi_common::pipe::{ReadPipe, WritePipe};
 use wasmtime::*;
 use wasmtime_wasi::tokio::WasiCtxBuilder;
 
+mod bindings;
+mod bundle;
+mod compile;
+#[cfg(feature = "server")]
+mod compression;
+mod config;
+mod engine_config;
+mod error;
 mod http;
+mod import_error;
+mod import_kv;
+mod import_respond;
 mod import_send_request;
+mod import_websocket;
+mod logs;
+mod mem;
+mod metrics;
+mod module_cache;
+#[cfg(feature = "server")]
+mod server;
+mod source;
+mod tests;
+mod trace;
 mod wasmtime_environment;
+mod websocket;
+mod worker_state;
+
+pub use bindings::{Binding, KvNamespace, MemoryKvNamespace};
+pub use bundle::{bundle, Bundle, CompiledModule};
+pub use compile::{compile, compile_script};
+pub use config::{RuntimeConfig, RuntimeOutput};
+pub use engine_config::AllocationStrategy;
+pub use error::{ErrorConfig, ErrorHook, ErrorKind, ErrorResponse, HandlerError};
+pub use logs::{Level, LogEntry, LogSink, TracingSink};
+pub use metrics::{Metrics, MetricsRegistry};
+#[cfg(feature = "server")]
+pub use server::{serve, ServerConfig};
+pub use source::read_modules;
+pub use trace::TraceParent;
+#[cfg(feature = "otlp")]
+pub use trace::{init_otlp_tracing, shutdown_otlp_tracing};
+pub use wasmtime_environment::WasmtimeEnvironment;
+pub use websocket::{WebSocketMessage, WebSocketRelay};
+
+use source::{Code, Source};
+use websocket::WebSocketChannel;
+use worker_state::WorkerState;
 
 lazy_static! {
     static ref WASMTIME_ENVIRONMENT: WasmtimeEnvironment = WasmtimeEnvironment::default();
 }
 
 pub async fn runtime(handler: &str, request: &str) -> anyhow::Result<Vec<u8>> {
-    let handler = handler
-        .trim()
-        .replace(
-            "export const handleRequest = ",
-            "globalThis.handleRequest = ",
-        )
-        .replace(
-            "export async function handleRequest",
-            "async function handleRequest",
-        );
-    let stdout_buf: Vec<u8> = vec![];
-    let stdout_mutex = Arc::new(RwLock::new(stdout_buf));
-    let stdout = WritePipe::from_shared(stdout_mutex.clone());
-    let stdin = ReadPipe::from(handler);
+    let output = runtime_with_config(handler, request, &RuntimeConfig::default()).await?;
+
+    Ok(output.response)
+}
+
+pub async fn runtime_with_config(
+    handler: &str,
+    request: &str,
+    config: &RuntimeConfig,
+) -> anyhow::Result<RuntimeOutput> {
+    let code = Code {
+        handler,
+        modules: &config.modules,
+        bytecode: &[],
+        source_maps: &config.source_maps,
+    };
+
+    run(code, request, config, None, None).await
+}
+
+/// Runs a bundled handler, which may be compiled to bytecode. Its modules are used instead of
+/// `RuntimeConfig::modules`.
+pub async fn runtime_with_bundle(
+    bundle: &Bundle,
+    request: &str,
+    config: &RuntimeConfig,
+) -> anyhow::Result<RuntimeOutput> {
+    let code = Code {
+        handler: &bundle.handler,
+        modules: &bundle.modules,
+        bytecode: &bundle.bytecode,
+        source_maps: &bundle.source_maps,
+    };
+
+    run(code, request, config, None, None).await
+}
+
+/// An invocation that has responded, which may still be running the work registered by the
+/// handler with `ctx.waitUntil`.
+#[derive(Debug)]
+pub struct Invocation {
+    /// The serialized response returned by the handler
+    pub response: Vec<u8>,
+    /// Resolves once the invocation finishes, with its logs and metrics
+    pub completion: JoinHandle<anyhow::Result<RuntimeOutput>>,
+    /// Relays the messages of the connection when the handler responds with a WebSocket, the
+    /// invocation then finishes once it is closed
+    pub websocket: WebSocketRelay,
+}
+
+/// Runs the handler in a task, returning as soon as it responds.
+pub async fn spawn_with_config(
+    handler: &str,
+    request: &str,
+    config: &RuntimeConfig,
+) -> anyhow::Result<Invocation> {
+    let (sender, receiver) = oneshot::channel();
+    let (websocket, channel) = websocket::channel();
+    let handler = handler.to_string();
+    let request = request.to_string();
+    let config = config.clone();
+    let completion = tokio::spawn(async move {
+        let code = Code {
+            handler: &handler,
+            modules: &config.modules,
+            bytecode: &[],
+            source_maps: &config.source_maps,
+        };
+
+        run(code, &request, &config, Some(sender), Some(channel)).await
+    });
+
+    match receiver.await {
+        Ok(response) => Ok(Invocation {
+            response,
+            completion,
+            websocket,
+        }),
+        // The sender is dropped without responding when the invocation fails
+        Err(_) => {
+            completion.await??;
+
+            Err(anyhow!("The handler didn't return a response"))
+        }
+    }
+}
+
+async fn run(
+    code: Code<'_>,
+    request: &str,
+    config: &RuntimeConfig,
+    response_sender: Option<oneshot::Sender<Vec<u8>>>,
+    websocket: Option<WebSocketChannel>,
+) -> anyhow::Result<RuntimeOutput> {
+    let headers = serde_json::from_str::<http::Request>(request)
+        .ok()
+        .and_then(|request| request.headers)
+        .unwrap_or_default();
+    let span = tracing::info_span!("invocation", worker_id = %config.worker_id);
+
+    trace::set_parent(&span, &headers);
+
+    invoke(
+        code,
+        request,
+        config,
+        trace::find_traceparent(&headers),
+        response_sender,
+        websocket,
+    )
+    .instrument(span)
+    .await
+}
+
+async fn invoke(
+    code: Code<'_>,
+    request: &str,
+    config: &RuntimeConfig,
+    trace_parent: Option<TraceParent>,
+    response_sender: Option<oneshot::Sender<Vec<u8>>>,
+    websocket: Option<WebSocketChannel>,
+) -> anyhow::Result<RuntimeOutput> {
+    let bindings = config.env();
+    let secrets = config.secrets();
+    let source = serde_json::to_string(&Source {
+        code,
+        env: bindings
+            .iter()
+            .map(|(name, binding)| (name.as_str(), binding.source()))
+            .collect(),
+    })?;
+    // The response is sent through `import_respond`
+    let stdout = WritePipe::new(io::sink());
+    let stderr_buf: Vec<u8> = vec![];
+    let stderr_mutex = Arc::new(RwLock::new(stderr_buf));
+    let stderr = WritePipe::from_shared(stderr_mutex.clone());
+    let stdin = ReadPipe::from(source);
 
     let wasi = WasiCtxBuilder::new()
         .stdin(Box::new(stdin))
         .stdout(Box::new(stdout))
+        .stderr(Box::new(stderr))
+        .env("WORKER_ID", &config.worker_id)?
         .args(&[request.to_string()])?
         .build();
 
-    let environment = WASMTIME_ENVIRONMENT.clone();
+    let environment = config
+        .environment
+        .clone()
+        .unwrap_or_else(|| WASMTIME_ENVIRONMENT.clone());
     let engine = environment.engine;
     let module = environment.module;
     let linker = environment.linker;
 
-    let mut store = Store::new(&engine, wasi);
-    let instance = linker.instantiate_async(&mut store, &module).await?;
-    instance
+    let started = Instant::now();
+    let mut state = WorkerState::new(wasi, trace_parent);
+
+    state.kv_namespaces = bindings
+        .iter()
+        .filter_map(|(name, binding)| match binding {
+            Binding::Kv(namespace) => Some((name.clone(), namespace.clone())),
+            _ => None,
+        })
+        .collect();
+    state.response_sender = response_sender;
+    state.websocket = websocket;
+
+    let mut store = Store::new(&engine, state);
+
+    store.add_fuel(u64::MAX)?;
+
+    let instance = linker
+        .instantiate_async(&mut store, &module)
+        .instrument(tracing::info_span!("instantiate"))
+        .await?;
+    let result = instance
         .get_typed_func::<(), ()>(&mut store, "_start")?
         .call_async(&mut store, ())
-        .await?;
+        .instrument(tracing::info_span!("_start"))
+        .await;
 
-    let mut buffer = Vec::new();
+    // The logs are collected even when the handler fails, as they may explain the failure
+    let mut logs = logs::parse_logs(
+        &stderr_mutex
+            .read()
+            .map_err(|e| anyhow::Error::msg(format!("{e:?}")))?,
+        &config.worker_id,
+    );
 
-    stdout_mutex
-        .read()
-        .map_err(|e| anyhow::Error::msg(format!("{e:?}")))?
-        .iter()
-        .for_each(|i| buffer.push(*i));
+    logs::redact_logs(&mut logs, &secrets);
+
+    if let Some(log_sink) = &config.log_sink {
+        logs.iter().for_each(|entry| log_sink.log(entry));
+    }
+
+    let error = store
+        .data_mut()
+        .error
+        .take()
+        .map(|error| error.remap(code.source_maps).redact(&secrets));
+
+    // The handler error explains the failure better than the exit code
+    if error.is_none() {
+        result?;
+    }
+
+    let buffer = match (store.data_mut().response.take(), &error) {
+        // It failed after responding, e.g. in the work registered with `ctx.waitUntil`
+        (Some(buffer), _) => buffer,
+        (None, Some(error)) => {
+            let buffer = config.errors.response(error).to_vec()?;
+
+            if let Some(sender) = store.data_mut().response_sender.take() {
+                let _ = sender.send(buffer.clone());
+            }
+
+            buffer
+        }
+        (None, None) => return Err(anyhow!("The handler didn't return a response")),
+    };
+
+    let memory = instance
+        .get_memory(&mut store, "memory")
+        .map_or(0, |memory| memory.data_size(&store));
+    let response_bytes = serde_json::from_slice::<http::HandlerResponse>(&buffer)
+        .ok()
+        .and_then(|response| response.body)
+        .map_or(0, |body| body.len());
+    let state = store.data();
+    let metrics = Metrics {
+        wall_time: started.elapsed(),
+        fuel_consumed: store.fuel_consumed().unwrap_or_default(),
+        memory_peak_bytes: memory as u64,
+        outbound_requests: state.outbound_requests,
+        outbound_request_bytes: state.outbound_request_bytes,
+        outbound_response_bytes: state.outbound_response_bytes,
+        response_bytes: response_bytes as u64,
+    };
+
+    if let Some(metrics_registry) = &config.metrics_registry {
+        metrics_registry.record(&config.worker_id, &metrics);
+    }
 
-    Ok(buffer)
+    Ok(RuntimeOutput {
+        response: buffer,
+        logs,
+        metrics,
+        error,
+    })
 }
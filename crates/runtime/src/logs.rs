@@ -0,0 +1,115 @@
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::bindings::redact;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self {
+            Level::Trace => "trace",
+            Level::Debug => "debug",
+            Level::Info => "info",
+            Level::Warn => "warn",
+            Level::Error => "error",
+        };
+
+        write!(f, "{level}")
+    }
+}
+
+/// A `console` entry written by the handler.
+///
+/// The engine writes each entry to stderr as a JSON line.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    pub level: Level,
+    pub message: String,
+    /// Milliseconds since the UNIX epoch
+    pub timestamp: u64,
+    pub worker_id: String,
+}
+
+/// Receives the log entries of each invocation, as soon as the handler finishes.
+pub trait LogSink: Send + Sync {
+    fn log(&self, entry: &LogEntry);
+}
+
+/// Forwards the log entries to the current `tracing` subscriber.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TracingSink;
+
+impl LogSink for TracingSink {
+    fn log(&self, entry: &LogEntry) {
+        let message = entry.message.as_str();
+        let timestamp = entry.timestamp;
+        let worker_id = entry.worker_id.as_str();
+
+        match entry.level {
+            Level::Trace => {
+                tracing::trace!(target: "js_wasm_workers", worker_id, timestamp, "{message}")
+            }
+            Level::Debug => {
+                tracing::debug!(target: "js_wasm_workers", worker_id, timestamp, "{message}")
+            }
+            Level::Info => {
+                tracing::info!(target: "js_wasm_workers", worker_id, timestamp, "{message}")
+            }
+            Level::Warn => {
+                tracing::warn!(target: "js_wasm_workers", worker_id, timestamp, "{message}")
+            }
+            Level::Error => {
+                tracing::error!(target: "js_wasm_workers", worker_id, timestamp, "{message}")
+            }
+        }
+    }
+}
+
+/// Parses the stderr stream of the engine into log entries.
+///
+/// Lines that aren't a log record, like a panic message, are kept as error entries.
+pub(crate) fn parse_logs(stderr: &[u8], worker_id: &str) -> Vec<LogEntry> {
+    String::from_utf8_lossy(stderr)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str::<LogEntry>(line).unwrap_or_else(|_| LogEntry {
+                level: Level::Error,
+                message: line.to_string(),
+                timestamp: timestamp(),
+                worker_id: worker_id.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn redact_logs(logs: &mut [LogEntry], secrets: &[&str]) {
+    if secrets.is_empty() {
+        return;
+    }
+
+    for entry in logs {
+        entry.message = redact(&entry.message, secrets);
+    }
+}
+
+fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,85 @@
+use std::slice;
+
+use wasmtime::*;
+
+use crate::worker_state::WorkerState;
+
+pub(crate) async fn read_string<'c, 'm>(
+    caller: &'c mut Caller<'_, WorkerState>,
+    memory: &'m Memory,
+    ptr: i32,
+) -> &'m str {
+    std::str::from_utf8(read_bytes(caller, memory, ptr).await).unwrap()
+}
+
+pub(crate) async fn read_bytes<'c, 'm>(
+    caller: &'c mut Caller<'_, WorkerState>,
+    memory: &'m Memory,
+    ptr: i32,
+) -> &'m [u8] {
+    let len = stack_pop(caller).await as usize;
+
+    unsafe {
+        let ptr = memory.data_ptr(&caller).offset(ptr as isize);
+        slice::from_raw_parts(ptr, len)
+    }
+}
+
+pub(crate) async fn write_string<'c, 'm>(
+    caller: &'c mut Caller<'_, WorkerState>,
+    memory: &'m Memory,
+    value: &str,
+) -> i32 {
+    write_bytes(caller, memory, value.as_bytes()).await
+}
+
+pub(crate) async fn write_bytes<'c, 'm>(
+    caller: &'c mut Caller<'_, WorkerState>,
+    memory: &'m Memory,
+    value: &[u8],
+) -> i32 {
+    let alloc_func = caller.get_export("alloc").unwrap().into_func().unwrap();
+
+    let ptr = alloc_func
+        .typed::<i32, i32>(caller.as_context())
+        .unwrap()
+        .call_async(caller.as_context_mut(), value.len() as i32)
+        .await
+        .unwrap();
+
+    stack_push(caller, value.len() as i32).await;
+
+    memory
+        .write(caller.as_context_mut(), ptr as usize, value)
+        .unwrap();
+
+    ptr
+}
+
+pub(crate) async fn stack_push<'c, 'm>(caller: &'c mut Caller<'_, WorkerState>, value: i32) {
+    let push_fn = caller
+        .get_export("stack_push")
+        .unwrap()
+        .into_func()
+        .unwrap();
+
+    push_fn
+        .typed::<i32, ()>(&caller)
+        .unwrap()
+        .call_async(caller, value)
+        .await
+        .unwrap();
+}
+
+async fn stack_pop<'c, 'm>(caller: &'c mut Caller<'_, WorkerState>) -> i32 {
+    let pop_fn = caller.get_export("stack_pop").unwrap().into_func().unwrap();
+
+    let value = pop_fn
+        .typed::<(), i32>(&caller)
+        .unwrap()
+        .call_async(caller, ())
+        .await
+        .unwrap();
+
+    value
+}
@@ -0,0 +1,139 @@
+use std::{collections::BTreeMap, fmt::Write, sync::Mutex, time::Duration};
+
+/// The resources used by one invocation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// Time from the instantiation until the response is read
+    pub wall_time: Duration,
+    /// Wasmtime fuel consumed by the guest, roughly one unit per WebAssembly instruction
+    pub fuel_consumed: u64,
+    /// The size of the linear memory at the end of the invocation, as it never shrinks
+    pub memory_peak_bytes: u64,
+    /// Number of `fetch` calls sent by the handler
+    pub outbound_requests: u64,
+    /// Bytes of the bodies sent by `fetch`
+    pub outbound_request_bytes: u64,
+    /// Bytes of the bodies received by `fetch`
+    pub outbound_response_bytes: u64,
+    /// Bytes of the response body returned by the handler
+    pub response_bytes: u64,
+}
+
+#[derive(Debug, Default, Clone)]
+struct WorkerMetrics {
+    invocations: u64,
+    wall_time_seconds: f64,
+    fuel_consumed: u64,
+    memory_peak_bytes: u64,
+    outbound_requests: u64,
+    outbound_request_bytes: u64,
+    outbound_response_bytes: u64,
+    response_bytes: u64,
+}
+
+/// Aggregates the metrics of the invocations by worker, to export them in the Prometheus text format.
+///
+/// @see: https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    workers: Mutex<BTreeMap<String, WorkerMetrics>>,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, worker_id: &str, metrics: &Metrics) {
+        let mut workers = self.workers.lock().unwrap();
+        let worker = workers.entry(worker_id.to_string()).or_default();
+
+        worker.invocations += 1;
+        worker.wall_time_seconds += metrics.wall_time.as_secs_f64();
+        worker.fuel_consumed += metrics.fuel_consumed;
+        worker.memory_peak_bytes = worker.memory_peak_bytes.max(metrics.memory_peak_bytes);
+        worker.outbound_requests += metrics.outbound_requests;
+        worker.outbound_request_bytes += metrics.outbound_request_bytes;
+        worker.outbound_response_bytes += metrics.outbound_response_bytes;
+        worker.response_bytes += metrics.response_bytes;
+    }
+
+    pub fn render(&self) -> String {
+        let workers = self.workers.lock().unwrap();
+        let mut output = String::new();
+
+        let families: [(&str, &str, &str, fn(&WorkerMetrics) -> String); 8] = [
+            (
+                "invocations_total",
+                "counter",
+                "Number of invocations",
+                |worker| worker.invocations.to_string(),
+            ),
+            (
+                "wall_time_seconds_total",
+                "counter",
+                "Wall time spent by the invocations",
+                |worker| worker.wall_time_seconds.to_string(),
+            ),
+            (
+                "fuel_consumed_total",
+                "counter",
+                "Fuel consumed by the invocations",
+                |worker| worker.fuel_consumed.to_string(),
+            ),
+            (
+                "memory_peak_bytes",
+                "gauge",
+                "Highest linear memory size of an invocation",
+                |worker| worker.memory_peak_bytes.to_string(),
+            ),
+            (
+                "outbound_requests_total",
+                "counter",
+                "Number of outbound fetch requests",
+                |worker| worker.outbound_requests.to_string(),
+            ),
+            (
+                "outbound_request_bytes_total",
+                "counter",
+                "Bytes sent in outbound fetch request bodies",
+                |worker| worker.outbound_request_bytes.to_string(),
+            ),
+            (
+                "outbound_response_bytes_total",
+                "counter",
+                "Bytes received in outbound fetch response bodies",
+                |worker| worker.outbound_response_bytes.to_string(),
+            ),
+            (
+                "response_bytes_total",
+                "counter",
+                "Bytes of the response bodies",
+                |worker| worker.response_bytes.to_string(),
+            ),
+        ];
+
+        for (name, kind, help, value) in families {
+            let _ = writeln!(output, "# HELP js_wasm_workers_{name} {help}");
+            let _ = writeln!(output, "# TYPE js_wasm_workers_{name} {kind}");
+
+            for (worker_id, worker) in workers.iter() {
+                let _ = writeln!(
+                    output,
+                    "js_wasm_workers_{name}{{worker_id=\"{}\"}} {}",
+                    escape_label(worker_id),
+                    value(worker)
+                );
+            }
+        }
+
+        output
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,163 @@
+use std::{
+    fs::{self, File, Metadata, OpenOptions},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+#[cfg(unix)]
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, OpenOptionsExt};
+
+const KEY_FILE: &str = "key";
+const KEY_LEN: usize = blake3::KEY_LEN;
+const MAC_LEN: usize = blake3::OUT_LEN;
+
+/// The compiled modules cached on disk. They are native code, so they are only read from a
+/// directory private to the current user, and each file starts with a MAC of the module, keyed
+/// with a random key stored in that directory, so a file the runtime didn't write is never loaded.
+#[derive(Debug)]
+pub(crate) struct ModuleCache {
+    dir: PathBuf,
+    key: [u8; KEY_LEN],
+}
+
+impl ModuleCache {
+    /// Opens the cache, creating its directory and its key. It is `None` when the directory isn't
+    /// owned by the current user or is accessible to the others, or isn't supported on the platform.
+    pub(crate) fn open(dir: &Path) -> Option<Self> {
+        if !dir.exists() {
+            create_private_dir(dir).ok()?;
+        }
+
+        let metadata = fs::symlink_metadata(dir).ok()?;
+
+        if !metadata.is_dir() || !is_private(&metadata, current_uid()?) {
+            return None;
+        }
+
+        let key = read_key(dir).or_else(|| create_key(dir))?;
+
+        Some(Self {
+            dir: dir.to_path_buf(),
+            key,
+        })
+    }
+
+    /// The serialized module, when it was written by the cache and hasn't been modified since.
+    pub(crate) fn read(&self, name: &str) -> Option<Vec<u8>> {
+        let contents = read_private_file(&self.dir.join(name))?;
+
+        if contents.len() < MAC_LEN {
+            return None;
+        }
+
+        let (mac, module) = contents.split_at(MAC_LEN);
+        let mac = blake3::Hash::from(<[u8; MAC_LEN]>::try_from(mac).ok()?);
+
+        // The comparison of `blake3::Hash` is constant time
+        (mac == blake3::keyed_hash(&self.key, module)).then(|| module.to_vec())
+    }
+
+    /// Writes the serialized module, a concurrent process never reads a partial file as the file
+    /// is renamed once written.
+    pub(crate) fn write(&self, name: &str, module: &[u8]) -> io::Result<()> {
+        let path = self.dir.join(name);
+        let temp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        let mut file = create_private_file(&temp_path)?;
+
+        file.write_all(blake3::keyed_hash(&self.key, module).as_bytes())
+            .and_then(|_| file.write_all(module))
+            .and_then(|_| fs::rename(&temp_path, &path))
+            .map_err(|error| {
+                let _ = fs::remove_file(&temp_path);
+
+                error
+            })
+    }
+}
+
+/// Whether the file or directory is owned by the user, and only accessible to them.
+#[cfg(unix)]
+pub(crate) fn is_private(metadata: &Metadata, uid: u32) -> bool {
+    metadata.uid() == uid && metadata.mode() & 0o077 == 0
+}
+
+#[cfg(not(unix))]
+pub(crate) fn is_private(_metadata: &Metadata, _uid: u32) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn current_uid() -> Option<u32> {
+    // SAFETY: `geteuid` has no preconditions and always succeeds
+    Some(unsafe { libc::geteuid() })
+}
+
+// The permissions can't be checked, so the cache is disabled
+#[cfg(not(unix))]
+fn current_uid() -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(dir)
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)
+}
+
+#[cfg(unix)]
+fn create_private_file(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+}
+
+#[cfg(not(unix))]
+fn create_private_file(path: &Path) -> io::Result<File> {
+    OpenOptions::new().write(true).create_new(true).open(path)
+}
+
+// The metadata is read from the open file, so the checked file is the one read
+fn read_private_file(path: &Path) -> Option<Vec<u8>> {
+    let mut file = File::open(path).ok()?;
+    let metadata = file.metadata().ok()?;
+
+    if !metadata.is_file() || !is_private(&metadata, current_uid()?) {
+        return None;
+    }
+
+    let mut contents = vec![];
+
+    file.read_to_end(&mut contents).ok()?;
+
+    Some(contents)
+}
+
+fn read_key(dir: &Path) -> Option<[u8; KEY_LEN]> {
+    read_private_file(&dir.join(KEY_FILE))?.try_into().ok()
+}
+
+fn create_key(dir: &Path) -> Option<[u8; KEY_LEN]> {
+    let mut key = [0; KEY_LEN];
+
+    getrandom::getrandom(&mut key).ok()?;
+
+    match create_private_file(&dir.join(KEY_FILE)) {
+        Ok(mut file) => {
+            file.write_all(&key).ok()?;
+
+            Some(key)
+        }
+        // Created by a concurrent process
+        Err(error) if error.kind() == io::ErrorKind::AlreadyExists => read_key(dir),
+        Err(_) => None,
+    }
+}
@@ -0,0 +1,434 @@
+use std::{collections::HashMap, convert::Infallible, net::SocketAddr, sync::Arc, time::Duration};
+
+use futures_util::{SinkExt, StreamExt};
+use hyper::{
+    body::to_bytes,
+    header::{
+        ACCEPT_ENCODING, CONNECTION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, HOST,
+        SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, UPGRADE, VARY,
+    },
+    service::{make_service_fn, service_fn},
+    upgrade::{OnUpgrade, Upgraded},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use serde_bytes::ByteBuf;
+use tokio::time::{sleep_until, Instant};
+use tokio_tungstenite::{
+    tungstenite::{
+        handshake::derive_accept_key,
+        protocol::{frame::coding::CloseCode, CloseFrame, Role},
+        Message,
+    },
+    WebSocketStream,
+};
+
+use crate::{
+    compression::{compress, is_compressible, negotiate},
+    config::RuntimeConfig,
+    http,
+    metrics::MetricsRegistry,
+    spawn_with_config,
+    websocket::{WebSocketMessage, WebSocketRelay},
+};
+
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Settings of the HTTP server mode.
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub addr: SocketAddr,
+    /// The source of the handler run for each request
+    pub handler: String,
+    pub runtime: RuntimeConfig,
+    /// Path of the Prometheus metrics endpoint, it isn't exposed when it is `None`
+    pub metrics_path: Option<String>,
+    /// Compresses the text responses of the handler with brotli, gzip or deflate, as accepted by
+    /// the client. The responses the handler already encoded are sent as they are.
+    pub compression: bool,
+    /// A WebSocket without any message in either direction for this long is closed with the 1001
+    /// code
+    pub websocket_idle_timeout: Duration,
+    /// How long the other end has to answer a close frame before the connection is dropped
+    pub websocket_close_timeout: Duration,
+}
+
+struct ServerState {
+    handler: String,
+    runtime: RuntimeConfig,
+    metrics_path: Option<String>,
+    metrics_registry: Arc<MetricsRegistry>,
+    compression: bool,
+    relay_timeouts: RelayTimeouts,
+}
+
+#[derive(Clone, Copy)]
+struct RelayTimeouts {
+    idle: Duration,
+    close: Duration,
+}
+
+/// Serves the handler over HTTP, running it once per request.
+pub async fn serve(config: ServerConfig) -> anyhow::Result<()> {
+    let mut runtime = config.runtime;
+    let metrics_registry = runtime
+        .metrics_registry
+        .get_or_insert_with(|| Arc::new(MetricsRegistry::new()))
+        .clone();
+    let state = Arc::new(ServerState {
+        handler: config.handler,
+        runtime,
+        metrics_path: config.metrics_path,
+        metrics_registry,
+        compression: config.compression,
+        relay_timeouts: RelayTimeouts {
+            idle: config.websocket_idle_timeout,
+            close: config.websocket_close_timeout,
+        },
+    });
+
+    let make_service = make_service_fn(move |_| {
+        let state = state.clone();
+
+        async move { Ok::<_, Infallible>(service_fn(move |request| handle(state.clone(), request))) }
+    });
+
+    Server::bind(&config.addr).serve(make_service).await?;
+
+    Ok(())
+}
+
+async fn handle(
+    state: Arc<ServerState>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    if request.method() == Method::GET
+        && state.metrics_path.as_deref() == Some(request.uri().path())
+    {
+        return Ok(Response::builder()
+            .header(CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)
+            .body(Body::from(state.metrics_registry.render()))
+            .unwrap());
+    }
+
+    Ok(invoke(&state, request).await.unwrap_or_else(|error| {
+        tracing::error!(%error, "the handler failed");
+
+        Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from(StatusCode::INTERNAL_SERVER_ERROR.to_string()))
+            .unwrap()
+    }))
+}
+
+async fn invoke(state: &ServerState, mut request: Request<Body>) -> anyhow::Result<Response<Body>> {
+    // The connection is upgraded once the response is sent, so it is taken before the request is
+    // consumed
+    let upgrade = websocket_key(&request).map(|key| (key, hyper::upgrade::on(&mut request)));
+    let (parts, body) = request.into_parts();
+    let host = parts
+        .headers
+        .get(HOST)
+        .and_then(|host| host.to_str().ok())
+        .unwrap_or("localhost");
+    let accept_encoding = parts
+        .headers
+        .get(ACCEPT_ENCODING)
+        .and_then(|accept_encoding| accept_encoding.to_str().ok())
+        .map(String::from);
+    let path = parts
+        .uri
+        .path_and_query()
+        .map_or("/", |path_and_query| path_and_query.as_str());
+    let headers = parts
+        .headers
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.to_string(),
+                value.to_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+    let body = to_bytes(body).await?;
+
+    let request = http::Request {
+        body: (!body.is_empty()).then(|| ByteBuf::from(body.to_vec())),
+        headers: Some(headers),
+        method: parts.method.to_string(),
+        url: format!("http://{host}{path}"),
+    };
+
+    let invocation = spawn_with_config(
+        &state.handler,
+        &serde_json::to_string(&request)?,
+        &state.runtime,
+    )
+    .await?;
+
+    let completion = invocation.completion;
+
+    // The work registered with `ctx.waitUntil` keeps running after responding
+    tokio::spawn(async move {
+        match completion.await {
+            Ok(Ok(output)) => {
+                if let Some(error) = output.error {
+                    tracing::error!(%error, stack = ?error.stack, "the handler failed");
+                }
+            }
+            Ok(Err(error)) => tracing::error!(%error, "the handler failed after responding"),
+            Err(_) => {}
+        }
+    });
+
+    let response = serde_json::from_slice::<http::HandlerResponse>(&invocation.response)?;
+
+    if response.web_socket == Some(true) {
+        return upgrade_response(
+            response,
+            upgrade,
+            invocation.websocket,
+            state.relay_timeouts,
+        );
+    }
+
+    let mut headers = response.headers.unwrap_or_default();
+    let mut body = response
+        .body
+        .map(|body| body.into_vec())
+        .unwrap_or_default();
+
+    if state.compression {
+        body = compress_body(&mut headers, body, accept_encoding.as_deref())?;
+    }
+
+    let mut builder = Response::builder().status(response.status);
+
+    for (name, value) in headers {
+        builder = builder.header(name, value);
+    }
+
+    Ok(builder.body(Body::from(body))?)
+}
+
+// The key of the WebSocket handshake, when the request asks to upgrade the connection
+// @see: https://datatracker.ietf.org/doc/html/rfc6455#section-4.2.1
+fn websocket_key(request: &Request<Body>) -> Option<String> {
+    let header = |name| {
+        request
+            .headers()
+            .get(name)
+            .and_then(|value: &hyper::header::HeaderValue| value.to_str().ok())
+    };
+    let is_upgrade = request.method() == Method::GET
+        && header(CONNECTION).map_or(false, |connection| {
+            connection
+                .split(',')
+                .any(|token| token.trim().eq_ignore_ascii_case("upgrade"))
+        })
+        && header(UPGRADE).map_or(false, |upgrade| upgrade.eq_ignore_ascii_case("websocket"));
+
+    is_upgrade
+        .then(|| header(SEC_WEBSOCKET_KEY).map(String::from))
+        .flatten()
+}
+
+// Completes the handshake, then relays the messages once the connection is upgraded. A WebSocket
+// returned to a request which isn't an upgrade is refused, the handler then receives a close.
+fn upgrade_response(
+    response: http::HandlerResponse,
+    upgrade: Option<(String, OnUpgrade)>,
+    relay: WebSocketRelay,
+    timeouts: RelayTimeouts,
+) -> anyhow::Result<Response<Body>> {
+    let Some((key, on_upgrade)) = upgrade else {
+        return Ok(Response::builder()
+            .status(StatusCode::UPGRADE_REQUIRED)
+            .header(UPGRADE, "websocket")
+            .body(Body::from(StatusCode::UPGRADE_REQUIRED.to_string()))?);
+    };
+
+    tokio::spawn(async move {
+        match on_upgrade.await {
+            Ok(upgraded) => {
+                let stream = WebSocketStream::from_raw_socket(upgraded, Role::Server, None).await;
+
+                relay_messages(stream, relay, timeouts).await;
+            }
+            Err(error) => tracing::error!(%error, "the WebSocket upgrade failed"),
+        }
+    });
+
+    let mut builder = Response::builder().status(StatusCode::SWITCHING_PROTOCOLS);
+
+    // E.g. the `Sec-WebSocket-Protocol` chosen by the handler
+    for (name, value) in response.headers.unwrap_or_default() {
+        builder = builder.header(name, value);
+    }
+
+    Ok(builder
+        .header(CONNECTION, "upgrade")
+        .header(UPGRADE, "websocket")
+        .header(SEC_WEBSOCKET_ACCEPT, derive_accept_key(key.as_bytes()))
+        .body(Body::empty())?)
+}
+
+// Relays the messages until the connection is closed, tungstenite answers the pings and the close
+// handshake. The handler receives a close with the 1006 code when the connection is lost, and the
+// client one with the 1011 code when the invocation finishes without closing it. An idle
+// connection is closed with the 1001 code for both, and one whose close frame isn't answered in
+// time is dropped.
+async fn relay_messages(
+    mut stream: WebSocketStream<Upgraded>,
+    mut relay: WebSocketRelay,
+    timeouts: RelayTimeouts,
+) {
+    // A close frame has been sent or received
+    let mut closing = false;
+    // The invocation is running, once it finished the close handshake is still awaited
+    let mut relay_open = true;
+    // The end of the idle timeout, then the one of the close handshake once closing
+    let mut deadline = Instant::now() + timeouts.idle;
+
+    loop {
+        let was_closing = closing;
+
+        tokio::select! {
+            message = stream.next() => {
+                let message = match message {
+                    Some(Ok(Message::Text(data))) => WebSocketMessage::Text { data },
+                    Some(Ok(Message::Binary(data))) => WebSocketMessage::Binary {
+                        data: ByteBuf::from(data),
+                    },
+                    Some(Ok(Message::Close(frame))) => {
+                        closing = true;
+
+                        frame.map_or(
+                            WebSocketMessage::Close {
+                                code: 1005,
+                                reason: String::new(),
+                            },
+                            |frame| WebSocketMessage::Close {
+                                code: frame.code.into(),
+                                reason: frame.reason.into_owned(),
+                            },
+                        )
+                    }
+                    Some(Ok(_)) => continue,
+                    Some(Err(_)) | None => {
+                        if !closing {
+                            let _ = relay.sender.send(WebSocketMessage::Close {
+                                code: 1006,
+                                reason: String::new(),
+                            });
+                        }
+
+                        return;
+                    }
+                };
+
+                let _ = relay.sender.send(message);
+            }
+            message = relay.receiver.recv(), if relay_open => {
+                let message = match message {
+                    Some(WebSocketMessage::Text { data }) => Message::Text(data),
+                    Some(WebSocketMessage::Binary { data }) => Message::Binary(data.into_vec()),
+                    Some(WebSocketMessage::Close { code, reason }) => {
+                        closing = true;
+
+                        Message::Close(Some(CloseFrame {
+                            code: CloseCode::from(code),
+                            reason: reason.into(),
+                        }))
+                    }
+                    None => {
+                        relay_open = false;
+
+                        if closing {
+                            continue;
+                        }
+
+                        closing = true;
+
+                        Message::Close(Some(CloseFrame {
+                            code: CloseCode::Error,
+                            reason: "".into(),
+                        }))
+                    }
+                };
+
+                // A failure is seen by the next read, as the connection is then closed
+                let _ = stream.send(message).await;
+            }
+            _ = sleep_until(deadline) => {
+                if closing {
+                    return;
+                }
+
+                closing = true;
+
+                let _ = relay.sender.send(WebSocketMessage::Close {
+                    code: 1001,
+                    reason: String::new(),
+                });
+                let _ = stream
+                    .send(Message::Close(Some(CloseFrame {
+                        code: CloseCode::Away,
+                        reason: "".into(),
+                    })))
+                    .await;
+            }
+        }
+
+        if closing && !was_closing {
+            deadline = Instant::now() + timeouts.close;
+        } else if !closing {
+            deadline = Instant::now() + timeouts.idle;
+        }
+    }
+}
+
+// Compresses the body with the coding negotiated with the client, updating the headers. The
+// compressible responses vary on `Accept-Encoding`, even when the client doesn't accept any coding.
+fn compress_body(
+    headers: &mut HashMap<String, String>,
+    body: Vec<u8>,
+    accept_encoding: Option<&str>,
+) -> anyhow::Result<Vec<u8>> {
+    let header = |headers: &HashMap<String, String>, name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(key, value)| (key.clone(), value.clone()))
+    };
+    let content_type = header(headers, CONTENT_TYPE.as_str());
+    let content_encoding = header(headers, CONTENT_ENCODING.as_str());
+
+    if !is_compressible(
+        content_type.as_ref().map(|(_, value)| value.as_str()),
+        content_encoding.as_ref().map(|(_, value)| value.as_str()),
+        body.len(),
+    ) {
+        return Ok(body);
+    }
+
+    match header(headers, VARY.as_str()) {
+        Some((name, vary)) => {
+            headers.insert(name, format!("{vary}, {ACCEPT_ENCODING}"));
+        }
+        None => {
+            headers.insert(VARY.to_string(), ACCEPT_ENCODING.to_string());
+        }
+    }
+
+    let encoding = match accept_encoding.and_then(negotiate) {
+        Some(encoding) => encoding,
+        None => return Ok(body),
+    };
+    let body = compress(encoding, &body)?;
+
+    headers.retain(|name, _| !name.eq_ignore_ascii_case(CONTENT_LENGTH.as_str()));
+    headers.insert(CONTENT_ENCODING.to_string(), encoding.name().to_string());
+    headers.insert(CONTENT_LENGTH.to_string(), body.len().to_string());
+
+    Ok(body)
+}
@@ -0,0 +1,59 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::{bindings::BindingSource, bundle::CompiledModule};
+
+/// The handler and the modules it can import, as source or as bytecode.
+#[derive(Serialize, Debug, Clone, Copy)]
+pub(crate) struct Code<'a> {
+    pub handler: &'a str,
+    pub modules: &'a BTreeMap<String, String>,
+    pub bytecode: &'a [CompiledModule],
+    /// Only used by the runtime, to remap the stack of the errors
+    #[serde(skip)]
+    pub source_maps: &'a BTreeMap<String, String>,
+}
+
+/// The code and the `env` bindings, as read by the engine from stdin.
+#[derive(Serialize, Debug)]
+pub(crate) struct Source<'a> {
+    #[serde(flatten)]
+    pub code: Code<'a>,
+    pub env: BTreeMap<&'a str, BindingSource<'a>>,
+}
+
+/// Reads the JavaScript modules of a directory, keyed by their path relative to it, so a handler
+/// in the directory can import them with relative specifiers.
+pub fn read_modules(dir: impl AsRef<Path>) -> Result<BTreeMap<String, String>> {
+    let mut modules = BTreeMap::new();
+
+    read_dir(dir.as_ref(), dir.as_ref(), &mut modules)?;
+
+    Ok(modules)
+}
+
+fn read_dir(root: &Path, dir: &Path, modules: &mut BTreeMap<String, String>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            read_dir(root, &path, modules)?;
+        } else if path
+            .extension()
+            .map_or(false, |extension| extension == "js" || extension == "mjs")
+        {
+            let name = path
+                .strip_prefix(root)?
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            modules.insert(name, fs::read_to_string(&path)?);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,12 @@
+mod bindings;
+mod bundle;
+#[cfg(feature = "server")]
+mod compression;
+mod error;
+mod logs;
+mod metrics;
+mod source;
+mod trace;
+mod wasmtime_environment;
+#[cfg(feature = "server")]
+mod websocket;
@@ -0,0 +1,184 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeMap, HashMap},
+        env,
+        sync::Arc,
+    };
+
+    use serde_json::json;
+
+    use crate::{
+        bindings::{redact, Binding, KvNamespace, MemoryKvNamespace},
+        config::RuntimeConfig,
+        import_kv::{run_kv_request, KvResult},
+    };
+
+    #[tokio::test]
+    async fn test_run_kv_request() {
+        let namespace: Arc<dyn KvNamespace> = Arc::new(MemoryKvNamespace::new());
+        let namespaces = HashMap::from([("CACHE".to_string(), namespace.clone())]);
+
+        assert_eq!(
+            KvResult::Value(json!(null)),
+            run_kv_request(
+                &namespaces,
+                r#"{"binding":"CACHE","operation":"put","key":"a"}"#,
+                b"1".to_vec()
+            )
+            .await
+        );
+        assert_eq!(
+            KvResult::Bytes(b"1".to_vec()),
+            run_kv_request(
+                &namespaces,
+                r#"{"binding":"CACHE","operation":"get","key":"a"}"#,
+                vec![]
+            )
+            .await
+        );
+        assert_eq!(
+            KvResult::Value(json!(["a"])),
+            run_kv_request(
+                &namespaces,
+                r#"{"binding":"CACHE","operation":"list","prefix":""}"#,
+                vec![]
+            )
+            .await
+        );
+        assert_eq!(
+            KvResult::Value(json!(null)),
+            run_kv_request(
+                &namespaces,
+                r#"{"binding":"CACHE","operation":"delete","key":"a"}"#,
+                vec![]
+            )
+            .await
+        );
+        assert_eq!(None, namespace.get("a").await.unwrap());
+        assert_eq!(
+            KvResult::Value(json!(null)),
+            run_kv_request(
+                &namespaces,
+                r#"{"binding":"CACHE","operation":"get","key":"a"}"#,
+                vec![]
+            )
+            .await
+        );
+
+        // Binary values, which aren't valid UTF-8, are stored as they are
+        assert_eq!(
+            KvResult::Value(json!(null)),
+            run_kv_request(
+                &namespaces,
+                r#"{"binding":"CACHE","operation":"put","key":"b"}"#,
+                vec![0xff, 0x00, 0x80]
+            )
+            .await
+        );
+        assert_eq!(
+            Some(vec![0xff, 0x00, 0x80]),
+            namespace.get("b").await.unwrap()
+        );
+        assert_eq!(
+            KvResult::Bytes(vec![0xff, 0x00, 0x80]),
+            run_kv_request(
+                &namespaces,
+                r#"{"binding":"CACHE","operation":"get","key":"b"}"#,
+                vec![]
+            )
+            .await
+        );
+        assert_eq!(
+            KvResult::Error("Unknown KV namespace: MISSING".to_string()),
+            run_kv_request(
+                &namespaces,
+                r#"{"binding":"MISSING","operation":"get","key":"a"}"#,
+                vec![]
+            )
+            .await
+        );
+        assert!(matches!(
+            run_kv_request(
+                &namespaces,
+                r#"{"binding":"CACHE","operation":"drop"}"#,
+                vec![]
+            )
+            .await,
+            KvResult::Error(_)
+        ));
+    }
+
+    #[test]
+    fn test_binding_source() {
+        let bindings = [
+            Binding::Var(json!({ "retries": 3 })),
+            Binding::Secret("s3cr3t".to_string()),
+            Binding::Kv(Arc::new(MemoryKvNamespace::new())),
+        ];
+
+        assert_eq!(
+            json!([
+                { "type": "var", "value": { "retries": 3 } },
+                { "type": "secret", "value": "s3cr3t" },
+                { "type": "kv" },
+            ]),
+            serde_json::to_value(
+                bindings
+                    .iter()
+                    .map(|binding| binding.source())
+                    .collect::<Vec<_>>()
+            )
+            .unwrap()
+        );
+        assert_eq!(r#"Secret("[REDACTED]")"#, format!("{:?}", bindings[1]));
+    }
+
+    #[test]
+    fn test_redact() {
+        assert_eq!(
+            "token: [REDACTED], again [REDACTED]",
+            redact("token: s3cr3t, again s3cr3t", &["s3cr3t", ""])
+        );
+    }
+
+    #[test]
+    fn test_config_env() {
+        env::set_var("JS_WASM_WORKERS_TEST_REGION", "eu");
+        env::set_var("JS_WASM_WORKERS_TEST_NAME", "host");
+        env::set_var("JS_WASM_WORKERS_TEST_HIDDEN", "hidden");
+
+        let config = RuntimeConfig {
+            bindings: BTreeMap::from([
+                (
+                    "JS_WASM_WORKERS_TEST_NAME".to_string(),
+                    Binding::var("worker"),
+                ),
+                ("TOKEN".to_string(), Binding::secret("s3cr3t")),
+            ]),
+            inherit_env: vec![
+                "JS_WASM_WORKERS_TEST_REGION".to_string(),
+                "JS_WASM_WORKERS_TEST_NAME".to_string(),
+                "JS_WASM_WORKERS_TEST_MISSING".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            json!({
+                "JS_WASM_WORKERS_TEST_NAME": { "type": "var", "value": "worker" },
+                "JS_WASM_WORKERS_TEST_REGION": { "type": "var", "value": "eu" },
+                "TOKEN": { "type": "secret", "value": "s3cr3t" },
+            }),
+            serde_json::to_value(
+                config
+                    .env()
+                    .iter()
+                    .map(|(name, binding)| (name.as_str(), binding.source()))
+                    .collect::<BTreeMap<_, _>>()
+            )
+            .unwrap()
+        );
+        assert_eq!(vec!["s3cr3t"], config.secrets());
+    }
+}
@@ -0,0 +1,221 @@
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::Path};
+
+    use anyhow::Result;
+
+    use crate::bundle::{bundle, parse_module, specifier_ranges, Bundle};
+
+    fn write(path: &Path, contents: &str) -> Result<()> {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, contents)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_specifier_ranges() -> Result<()> {
+        let contents = r#"
+            // Here's the handler, it doesn't import from "pkg"
+            import a from "./a.js";
+            /* It's re-exported from 'pkg' */
+            export * from './b';
+            const quote = /'/;
+            const divided = a / 2 / "from";
+            const template = `It's ${"from"} "c"`;
+            const text = "import('d')";
+            import("c"); Array.from("d");
+            export { e } from "./e.js";
+        "#;
+
+        assert_eq!(
+            vec!["./a.js", "./b", "c", "./e.js"],
+            specifier_ranges(&parse_module(contents)?)
+                .into_iter()
+                .map(|range| &contents[range])
+                .collect::<Vec<_>>()
+        );
+        assert!(parse_module("import { a } from").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bundle() -> Result<()> {
+        let dir = env::temp_dir().join(format!("js-wasm-workers-bundle-{}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+
+        write(&dir.join("package.json"), "{}")?;
+        write(
+            &dir.join("src/handler.js"),
+            r#"
+                import { greeting } from "./lib/greeting";
+                import { upperCase } from "text-case";
+                import { slugify } from "@scope/slug/slugify.js";
+
+                export default {
+                    fetch: () => new Response(upperCase(greeting(slugify("World")))),
+                };
+            "#,
+        )?;
+        write(
+            &dir.join("src/lib/greeting/index.js"),
+            r#"export { greeting } from "../../utils.mjs";"#,
+        )?;
+        write(
+            &dir.join("utils.mjs"),
+            r#"export const greeting = (name) => `Hello ${name}`;"#,
+        )?;
+        write(
+            &dir.join("node_modules/text-case/package.json"),
+            r#"{ "main": "./main.cjs", "exports": { ".": { "require": "./main.cjs", "import": "./esm/index.js" } } }"#,
+        )?;
+        write(
+            &dir.join("node_modules/text-case/esm/index.js"),
+            r#"export const upperCase = (text) => text.toUpperCase();"#,
+        )?;
+        write(
+            &dir.join("node_modules/@scope/slug/slugify.js"),
+            r#"export const slugify = (text) => text.toLowerCase();"#,
+        )?;
+
+        let bundle = bundle(dir.join("src/handler.js"))?;
+
+        assert!(bundle
+            .handler
+            .contains(r#"import { greeting } from "/src/lib/greeting/index.js";"#));
+        assert!(bundle
+            .handler
+            .contains(r#"import { upperCase } from "/node_modules/text-case/esm/index.js";"#));
+        assert!(bundle
+            .handler
+            .contains(r#"import { slugify } from "/node_modules/@scope/slug/slugify.js";"#));
+        assert_eq!(
+            vec![
+                "/node_modules/@scope/slug/slugify.js",
+                "/node_modules/text-case/esm/index.js",
+                "/src/lib/greeting/index.js",
+                "/utils.mjs",
+            ],
+            bundle.modules.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            r#"export { greeting } from "/utils.mjs";"#,
+            bundle.modules["/src/lib/greeting/index.js"]
+        );
+
+        let path = dir.join("handler.bundle.json");
+
+        bundle.write(&path)?;
+
+        assert_eq!(bundle, Bundle::read(&path)?);
+
+        // The quotes of a comment aren't taken for a specifier
+        write(
+            &dir.join("src/commented.js"),
+            r#"
+                // It's imported from "pkg" once bundled
+                import { greeting } from "../utils.mjs";
+
+                export default { fetch: () => new Response(greeting("World")) };
+            "#,
+        )?;
+
+        assert_eq!(
+            vec!["/utils.mjs"],
+            crate::bundle::bundle(dir.join("src/commented.js"))?
+                .modules
+                .keys()
+                .collect::<Vec<_>>()
+        );
+
+        write(&dir.join("src/missing.js"), r#"import "./nowhere.js";"#)?;
+        write(&dir.join("src/builtin.js"), r#"import fs from "node:fs";"#)?;
+
+        assert!(crate::bundle::bundle(dir.join("src/missing.js"))
+            .unwrap_err()
+            .to_string()
+            .starts_with("Cannot find module './nowhere.js'"));
+        assert!(crate::bundle::bundle(dir.join("src/builtin.js")).is_err());
+
+        fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+    #[test]
+    fn test_bundle_commonjs() -> Result<()> {
+        let dir = env::temp_dir().join(format!(
+            "js-wasm-workers-bundle-commonjs-{}",
+            std::process::id()
+        ));
+
+        let _ = fs::remove_dir_all(&dir);
+
+        write(&dir.join("package.json"), "{}")?;
+        write(
+            &dir.join("handler.js"),
+            r#"
+                import { upperCase } from "text-case";
+
+                export default { fetch: () => new Response(upperCase("Hello")) };
+            "#,
+        )?;
+        write(
+            &dir.join("node_modules/text-case/package.json"),
+            r#"{ "main": "./index.js" }"#,
+        )?;
+        write(
+            &dir.join("node_modules/text-case/index.js"),
+            r#"
+                const { toUpper } = require("./utils");
+
+                module.exports = { upperCase: toUpper };
+            "#,
+        )?;
+
+        assert_eq!(
+            format!(
+                "Cannot bundle the CommonJS module {} of the package 'text-case', only ES modules \
+                 are supported",
+                dir.join("node_modules/text-case/index.js")
+                    .canonicalize()?
+                    .display()
+            ),
+            bundle(dir.join("handler.js")).unwrap_err().to_string()
+        );
+
+        // A `.cjs` file is CommonJS, even without `require` or `exports`
+        write(&dir.join("local.js"), r#"import "./lib/config.cjs";"#)?;
+        write(&dir.join("lib/config.cjs"), r#"globalThis.config = {};"#)?;
+
+        assert!(bundle(dir.join("local.js"))
+            .unwrap_err()
+            .to_string()
+            .starts_with("Cannot bundle the CommonJS module"));
+
+        // An ES module may use the names, as may a script without any of them
+        write(
+            &dir.join("esm.js"),
+            r#"
+                import "./script.js";
+
+                const module = { exports: {} };
+                export default module.exports;
+            "#,
+        )?;
+        write(&dir.join("script.js"), r#"globalThis.ready = true;"#)?;
+
+        assert_eq!(
+            vec!["/script.js"],
+            bundle(dir.join("esm.js"))?
+                .modules
+                .keys()
+                .collect::<Vec<_>>()
+        );
+
+        fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use anyhow::Result;
+    use flate2::read::{GzDecoder, ZlibDecoder};
+
+    use crate::compression::{compress, is_compressible, negotiate, Encoding};
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(Some(Encoding::Brotli), negotiate("gzip, deflate, br"));
+        assert_eq!(Some(Encoding::Gzip), negotiate("gzip;q=1.0, br;q=0.5"));
+        assert_eq!(Some(Encoding::Deflate), negotiate("Deflate"));
+        assert_eq!(Some(Encoding::Gzip), negotiate("br;q=0, *"));
+        assert_eq!(Some(Encoding::Brotli), negotiate("*;q=0.1"));
+        assert_eq!(None, negotiate("identity"));
+        assert_eq!(None, negotiate("gzip;q=0, *;q=0"));
+        assert_eq!(None, negotiate(""));
+    }
+
+    #[test]
+    fn test_is_compressible() {
+        assert!(is_compressible(
+            Some("text/html; charset=utf-8"),
+            None,
+            2048
+        ));
+        assert!(is_compressible(Some("application/json"), None, 2048));
+        assert!(is_compressible(
+            Some("application/vnd.api+json"),
+            None,
+            2048
+        ));
+        assert!(!is_compressible(Some("image/png"), None, 2048));
+        assert!(!is_compressible(Some("text/plain"), None, 100));
+        assert!(!is_compressible(Some("text/plain"), Some("gzip"), 2048));
+        assert!(!is_compressible(None, None, 2048));
+    }
+
+    #[test]
+    fn test_compress() -> Result<()> {
+        let body = "Hello World! ".repeat(200);
+
+        let mut decoded = String::new();
+        GzDecoder::new(compress(Encoding::Gzip, body.as_bytes())?.as_slice())
+            .read_to_string(&mut decoded)?;
+        assert_eq!(body, decoded);
+
+        let mut decoded = String::new();
+        ZlibDecoder::new(compress(Encoding::Deflate, body.as_bytes())?.as_slice())
+            .read_to_string(&mut decoded)?;
+        assert_eq!(body, decoded);
+
+        let brotli = compress(Encoding::Brotli, body.as_bytes())?;
+        let mut decoded = String::new();
+        brotli::Decompressor::new(brotli.as_slice(), 4096).read_to_string(&mut decoded)?;
+        assert!(brotli.len() < body.len());
+        assert_eq!(body, decoded);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeMap, HashMap},
+        sync::Arc,
+    };
+
+    use crate::error::{ErrorConfig, ErrorHook, ErrorKind, ErrorResponse, HandlerError};
+
+    const SOURCE_MAP: &str =
+        r#"{"version":3,"sources":["src/handler.ts"],"names":[],"mappings":"AAAA;AACA"}"#;
+
+    fn error(stack: &str) -> HandlerError {
+        HandlerError {
+            name: "TypeError".to_string(),
+            message: "not a function".to_string(),
+            stack: Some(stack.to_string()),
+            kind: ErrorKind::Exception,
+        }
+    }
+
+    #[test]
+    fn test_remap() {
+        let source_maps = BTreeMap::from([("handler.js".to_string(), SOURCE_MAP.to_string())]);
+        let error = error("    at fetch (handler.js:2)\n    at <anonymous> (lib/greeting.js:4)\n");
+
+        assert_eq!(
+            Some("    at fetch (src/handler.ts:2:1)\n    at <anonymous> (lib/greeting.js:4)\n"),
+            error.remap(&source_maps).stack.as_deref()
+        );
+    }
+
+    #[test]
+    fn test_remap_with_column() {
+        let source_maps = BTreeMap::from([("handler.js".to_string(), SOURCE_MAP.to_string())]);
+
+        assert_eq!(
+            Some("    at fetch (src/handler.ts:1:1)"),
+            error("    at fetch (handler.js:1:1)")
+                .remap(&source_maps)
+                .stack
+                .as_deref()
+        );
+    }
+
+    #[test]
+    fn test_remap_without_source_maps() {
+        let error = error("    at fetch (handler.js:2)");
+
+        assert_eq!(error.clone(), error.remap(&BTreeMap::new()));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("TypeError: not a function", error("").to_string());
+    }
+
+    #[test]
+    fn test_error_response() {
+        let response = ErrorConfig::default().response(&error("    at fetch (handler.js:2)"));
+
+        assert_eq!(500, response.status);
+        assert_eq!(b"Internal Server Error".to_vec(), response.body);
+    }
+
+    #[test]
+    fn test_error_response_status() {
+        let config = ErrorConfig {
+            statuses: HashMap::from([(ErrorKind::InvalidResponse, 502)]),
+            ..Default::default()
+        };
+        let mut invalid_response = error("");
+
+        invalid_response.kind = ErrorKind::InvalidResponse;
+
+        assert_eq!(502, config.response(&invalid_response).status);
+        assert_eq!(
+            b"Bad Gateway".to_vec(),
+            config.response(&invalid_response).body
+        );
+        assert_eq!(500, config.response(&error("")).status);
+    }
+
+    #[test]
+    fn test_error_response_debug() {
+        let config = ErrorConfig {
+            debug: true,
+            ..Default::default()
+        };
+        let response = config.response(&error("    at fetch (handler.js:2)"));
+
+        assert_eq!(
+            Some(&"application/json".to_string()),
+            response.headers.get("content-type")
+        );
+        assert_eq!(
+            serde_json::json!({
+                "error": {
+                    "name": "TypeError",
+                    "message": "not a function",
+                    "stack": "    at fetch (handler.js:2)",
+                    "kind": "exception",
+                }
+            }),
+            serde_json::from_slice::<serde_json::Value>(&response.body).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_error_response_hook() {
+        struct ErrorPage;
+
+        impl ErrorHook for ErrorPage {
+            fn on_error(&self, error: &HandlerError, response: ErrorResponse) -> ErrorResponse {
+                ErrorResponse {
+                    headers: HashMap::from([("content-type".to_string(), "text/html".to_string())]),
+                    body: format!("<h1>{}</h1>", error.name).into_bytes(),
+                    ..response
+                }
+            }
+        }
+
+        let config = ErrorConfig {
+            hook: Some(Arc::new(ErrorPage)),
+            ..Default::default()
+        };
+        let response = config.response(&error(""));
+
+        assert_eq!(500, response.status);
+        assert_eq!(b"<h1>TypeError</h1>".to_vec(), response.body);
+    }
+}
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod tests {
+    use crate::logs::{parse_logs, redact_logs, Level, LogEntry};
+
+    #[test]
+    fn test_parse_logs() {
+        let stderr = concat!(
+            r#"{"level":"info","message":"hello","timestamp":1,"workerId":"worker"}"#,
+            "\n",
+            r#"{"level":"warn","message":"multi\nline","timestamp":2,"workerId":"worker"}"#,
+            "\n",
+            "\n",
+            "thread 'main' panicked\n",
+        );
+
+        let logs = parse_logs(stderr.as_bytes(), "worker");
+
+        assert_eq!(3, logs.len());
+        assert_eq!(
+            LogEntry {
+                level: Level::Info,
+                message: "hello".to_string(),
+                timestamp: 1,
+                worker_id: "worker".to_string(),
+            },
+            logs[0]
+        );
+        assert_eq!(Level::Warn, logs[1].level);
+        assert_eq!("multi\nline", logs[1].message);
+        assert_eq!(Level::Error, logs[2].level);
+        assert_eq!("thread 'main' panicked", logs[2].message);
+        assert_eq!("worker", logs[2].worker_id);
+    }
+
+    #[test]
+    fn test_redact_logs() {
+        let mut logs = parse_logs(
+            r#"{"level":"info","message":"token s3cr3t","timestamp":1,"workerId":"worker"}"#
+                .as_bytes(),
+            "worker",
+        );
+
+        redact_logs(&mut logs, &["s3cr3t"]);
+
+        assert_eq!("token [REDACTED]", logs[0].message);
+    }
+}
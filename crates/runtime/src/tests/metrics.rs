@@ -0,0 +1,130 @@
+// @see: https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    use anyhow::Result;
+
+    use crate::{
+        metrics::{Metrics, MetricsRegistry},
+        runtime_with_config, RuntimeConfig,
+    };
+
+    #[test]
+    fn test_metrics_registry_render() {
+        let registry = MetricsRegistry::new();
+
+        assert!(!registry.render().contains("worker_id"));
+
+        registry.record(
+            "worker-a",
+            &Metrics {
+                wall_time: Duration::from_millis(1500),
+                fuel_consumed: 100,
+                memory_peak_bytes: 2048,
+                outbound_requests: 1,
+                outbound_request_bytes: 10,
+                outbound_response_bytes: 20,
+                response_bytes: 30,
+            },
+        );
+        registry.record(
+            "worker-a",
+            &Metrics {
+                wall_time: Duration::from_millis(500),
+                fuel_consumed: 50,
+                memory_peak_bytes: 1024,
+                outbound_requests: 2,
+                outbound_request_bytes: 5,
+                outbound_response_bytes: 5,
+                response_bytes: 5,
+            },
+        );
+        registry.record("worker-\"b\"", &Metrics::default());
+
+        let output = registry.render();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines.contains(&"# TYPE js_wasm_workers_invocations_total counter"));
+        assert!(lines.contains(&"# TYPE js_wasm_workers_memory_peak_bytes gauge"));
+        assert!(lines.contains(&r#"js_wasm_workers_invocations_total{worker_id="worker-a"} 2"#));
+        assert!(
+            lines.contains(&r#"js_wasm_workers_wall_time_seconds_total{worker_id="worker-a"} 2"#)
+        );
+        assert!(lines.contains(&r#"js_wasm_workers_fuel_consumed_total{worker_id="worker-a"} 150"#));
+        assert!(lines.contains(&r#"js_wasm_workers_memory_peak_bytes{worker_id="worker-a"} 2048"#));
+        assert!(
+            lines.contains(&r#"js_wasm_workers_outbound_requests_total{worker_id="worker-a"} 3"#)
+        );
+        assert!(lines
+            .contains(&r#"js_wasm_workers_outbound_request_bytes_total{worker_id="worker-a"} 15"#));
+        assert!(lines.contains(
+            &r#"js_wasm_workers_outbound_response_bytes_total{worker_id="worker-a"} 25"#
+        ));
+        assert!(lines.contains(&r#"js_wasm_workers_response_bytes_total{worker_id="worker-a"} 35"#));
+        assert!(lines.contains(&r#"js_wasm_workers_invocations_total{worker_id="worker-\"b\""} 1"#));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_outbound_request_metrics() -> Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}/", listener.local_addr()?);
+
+        // Answers each request with "Hello World" once its body is read
+        thread::spawn(move || {
+            for stream in listener.incoming().take(2) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+
+                    if line.trim_end().is_empty() {
+                        break;
+                    }
+
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                stream
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\ncontent-length: 11\r\nconnection: close\r\n\r\nHello World",
+                    )
+                    .unwrap();
+            }
+        });
+
+        let handler = format!(
+            r#"
+            export const handleRequest = async function (request) {{
+                await (await fetch("{url}", {{ method: "POST", body: "ping" }})).text();
+                await (await fetch("{url}")).text();
+
+                return new Response("Done");
+            }};
+            "#
+        );
+        let request = r#"{"body":null,"headers":{},"method":"GET","url":"https://test.test/"}"#;
+
+        let output = runtime_with_config(&handler, request, &RuntimeConfig::default()).await?;
+
+        assert_eq!(2, output.metrics.outbound_requests);
+        assert_eq!(4, output.metrics.outbound_request_bytes);
+        assert_eq!(22, output.metrics.outbound_response_bytes);
+        assert_eq!(4, output.metrics.response_bytes);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use anyhow::Result;
+
+    use crate::source::read_modules;
+
+    #[test]
+    fn test_read_modules() -> Result<()> {
+        let dir = env::temp_dir().join(format!("js-wasm-workers-modules-{}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+
+        fs::create_dir_all(dir.join("lib"))?;
+        fs::write(dir.join("handler.js"), "export default {};")?;
+        fs::write(dir.join("lib/utils.mjs"), "export const answer = 42;")?;
+        fs::write(dir.join("README.md"), "# Ignored")?;
+
+        let modules = read_modules(&dir)?;
+
+        assert_eq!(
+            vec!["handler.js", "lib/utils.mjs"],
+            modules.keys().collect::<Vec<_>>()
+        );
+        assert_eq!("export const answer = 42;", modules["lib/utils.mjs"]);
+
+        fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+}
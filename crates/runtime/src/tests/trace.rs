@@ -0,0 +1,148 @@
+// @see: https://www.w3.org/TR/trace-context/#traceparent-header
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::trace::{find_traceparent, TraceParent};
+
+    #[test]
+    fn test_traceparent_parse() {
+        let trace_parent =
+            TraceParent::parse("00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01").unwrap();
+
+        assert_eq!("4bf92f3577b34da6a3ce929d0e0e4736", trace_parent.trace_id);
+        assert_eq!("00f067aa0ba902b7", trace_parent.parent_id);
+        assert_eq!("01", trace_parent.flags);
+        assert_eq!(
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            trace_parent.to_string()
+        );
+
+        // Unknown version
+        assert!(
+            TraceParent::parse("01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").is_none()
+        );
+        // All zeros trace id
+        assert!(
+            TraceParent::parse("00-00000000000000000000000000000000-00f067aa0ba902b7-01").is_none()
+        );
+        // All zeros parent id
+        assert!(
+            TraceParent::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01").is_none()
+        );
+        // Invalid length
+        assert!(
+            TraceParent::parse("00-4bf92f3577b34da6a3ce929d0e0e47-00f067aa0ba902b7-01").is_none()
+        );
+        // Invalid characters
+        assert!(
+            TraceParent::parse("00-4bf92f3577b34da6a3ce929d0e0e473z-00f067aa0ba902b7-01").is_none()
+        );
+        assert!(TraceParent::parse("").is_none());
+    }
+
+    #[test]
+    fn test_traceparent_child() {
+        let trace_parent =
+            TraceParent::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").unwrap();
+        let child = trace_parent.child();
+        let other_child = trace_parent.child();
+
+        assert_eq!(trace_parent.trace_id, child.trace_id);
+        assert_eq!(trace_parent.flags, child.flags);
+        assert_ne!(trace_parent.parent_id, child.parent_id);
+        assert_ne!(child.parent_id, other_child.parent_id);
+        assert_eq!(Some(child.clone()), TraceParent::parse(&child.to_string()));
+    }
+
+    #[test]
+    fn test_find_traceparent() {
+        let mut headers = HashMap::new();
+
+        assert!(find_traceparent(&headers).is_none());
+
+        headers.insert(
+            "TraceParent".to_string(),
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string(),
+        );
+
+        assert_eq!(
+            "4bf92f3577b34da6a3ce929d0e0e4736",
+            find_traceparent(&headers).unwrap().trace_id
+        );
+    }
+
+    // A collector stand-in receives the spans exported over OTLP/HTTP
+    #[cfg(feature = "otlp")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_otlp_exporter() -> anyhow::Result<()> {
+        use std::{
+            io::{BufRead, BufReader, Read, Write},
+            net::TcpListener,
+            sync::mpsc,
+            thread,
+            time::Duration,
+        };
+
+        use crate::trace::{init_otlp_tracing, shutdown_otlp_tracing};
+
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let endpoint = format!("http://{}", listener.local_addr()?);
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut head = vec![];
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+
+                let line = line.trim_end().to_string();
+
+                if line.is_empty() {
+                    break;
+                }
+
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+
+                head.push(line);
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                .unwrap();
+
+            sender.send((head, body)).unwrap();
+        });
+
+        init_otlp_tracing(&endpoint, "js-wasm-workers-test")?;
+
+        tracing::info_span!("invocation", worker_id = "test").in_scope(|| {
+            tracing::info_span!("instantiate").in_scope(|| {});
+        });
+
+        // The shutdown blocks until the batch is exported
+        tokio::task::spawn_blocking(shutdown_otlp_tracing).await?;
+
+        let (head, body) = receiver.recv_timeout(Duration::from_secs(10))?;
+
+        assert!(head[0].starts_with("POST "));
+        assert!(head
+            .iter()
+            .any(|line| line.to_lowercase() == "content-type: application/x-protobuf"));
+        assert!(!body.is_empty());
+        assert!(body
+            .windows(b"instantiate".len())
+            .any(|w| w == b"instantiate"));
+
+        Ok(())
+    }
+}
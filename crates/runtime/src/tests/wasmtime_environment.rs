@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use std::{env, ffi::OsStr, fs};
+
+    use anyhow::Result;
+    use wasmtime::{Engine, Instance, Store};
+
+    use crate::{
+        engine_config::{engine_config, AllocationStrategy},
+        module_cache::ModuleCache,
+        wasmtime_environment::load_module,
+    };
+
+    static WAT: &str = r#"(module (func (export "answer") (result i32) i32.const 42))"#;
+
+    #[test]
+    fn test_load_module_cache() -> Result<()> {
+        let engine = Engine::new(&engine_config(AllocationStrategy::OnDemand))?;
+        let wasm = wat::parse_str(WAT)?;
+        let cache_dir =
+            env::temp_dir().join(format!("js-wasm-workers-test-{}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&cache_dir);
+
+        // Compiled and written to the cache
+        let module = load_module(&engine, &wasm, Some(&cache_dir))?;
+        let entries = fs::read_dir(&cache_dir)?.collect::<Result<Vec<_>, _>>()?;
+
+        assert!(module.get_export("answer").is_some());
+        assert_eq!(1, entries.len());
+
+        let path = entries[0].path();
+
+        assert_eq!(Some(OsStr::new("cwasm")), path.extension());
+
+        // Deserialized from the cache
+        let module = load_module(&engine, &wasm, Some(&cache_dir))?;
+
+        assert!(module.get_export("answer").is_some());
+
+        // An invalid cache file is replaced
+        fs::write(&path, b"invalid")?;
+
+        let module = load_module(&engine, &wasm, Some(&cache_dir))?;
+
+        assert!(module.get_export("answer").is_some());
+        assert_ne!(b"invalid".to_vec(), fs::read(&path)?);
+
+        // Without cache
+        let module = load_module(&engine, &wasm, None)?;
+
+        assert!(module.get_export("answer").is_some());
+
+        fs::remove_dir_all(&cache_dir)?;
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_module_cache_integrity() -> Result<()> {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+        use crate::module_cache::is_private;
+
+        let cache_dir =
+            env::temp_dir().join(format!("js-wasm-workers-test-mac-{}", std::process::id()));
+        let other_dir =
+            env::temp_dir().join(format!("js-wasm-workers-test-other-{}", std::process::id()));
+        let path = cache_dir.join("module.cwasm");
+
+        let _ = fs::remove_dir_all(&cache_dir);
+        let _ = fs::remove_dir_all(&other_dir);
+
+        let cache = ModuleCache::open(&cache_dir).unwrap();
+
+        assert_eq!(0o700, fs::metadata(&cache_dir)?.mode() & 0o777);
+
+        cache.write("module.cwasm", b"module")?;
+
+        assert_eq!(0o600, fs::metadata(&path)?.mode() & 0o777);
+        assert_eq!(Some(b"module".to_vec()), cache.read("module.cwasm"));
+
+        // A tampered file is ignored
+        let mut contents = fs::read(&path)?;
+
+        *contents.last_mut().unwrap() ^= 1;
+        fs::write(&path, &contents)?;
+
+        assert_eq!(None, cache.read("module.cwasm"));
+
+        // A file written with the key of another cache is ignored
+        ModuleCache::open(&other_dir)
+            .unwrap()
+            .write("module.cwasm", b"planted")?;
+        fs::copy(other_dir.join("module.cwasm"), &path)?;
+
+        assert_eq!(None, cache.read("module.cwasm"));
+
+        // A file accessible to the others is ignored
+        cache.write("module.cwasm", b"module")?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o666))?;
+
+        assert_eq!(None, cache.read("module.cwasm"));
+
+        // A file owned by another user is ignored
+        let metadata = fs::metadata(&path)?;
+
+        assert!(!is_private(&metadata, metadata.uid() + 1));
+
+        // A directory accessible to the others isn't used
+        fs::set_permissions(&cache_dir, fs::Permissions::from_mode(0o777))?;
+
+        assert!(ModuleCache::open(&cache_dir).is_none());
+
+        fs::remove_dir_all(&cache_dir)?;
+        fs::remove_dir_all(&other_dir)?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pooling_max_instances() -> Result<()> {
+        let engine = Engine::new(&engine_config(AllocationStrategy::Pooling {
+            max_instances: 2,
+            memory_pages: 16,
+        }))?;
+        let module = load_module(&engine, &wat::parse_str(WAT)?, None)?;
+        let mut stores = vec![];
+
+        for _ in 0..2 {
+            let mut store = Store::new(&engine, ());
+            Instance::new_async(&mut store, &module, &[]).await?;
+            stores.push(store);
+        }
+
+        let mut store = Store::new(&engine, ());
+
+        assert!(Instance::new_async(&mut store, &module, &[]).await.is_err());
+
+        // Dropping a store releases its slot
+        stores.pop();
+
+        assert!(Instance::new_async(&mut store, &module, &[]).await.is_ok());
+
+        Ok(())
+    }
+}
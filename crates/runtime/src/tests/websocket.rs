@@ -0,0 +1,184 @@
+// @see: https://developers.cloudflare.com/workers/runtime-apis/websockets/
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{SocketAddr, TcpListener},
+        time::{Duration, Instant},
+    };
+
+    use anyhow::Result;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+    use tokio_tungstenite::{
+        connect_async,
+        tungstenite::{protocol::frame::coding::CloseCode, Message},
+        MaybeTlsStream, WebSocketStream,
+    };
+
+    use crate::{serve, RuntimeConfig, ServerConfig};
+
+    static HANDLER: &str = r#"
+        export default {
+            fetch() {
+                const [client, server] = Object.values(new WebSocketPair());
+
+                server.accept();
+                server.addEventListener("message", (event) => {
+                    if (event.data === "close") {
+                        server.close(3000, "Done");
+                    } else if (typeof event.data === "string") {
+                        server.send(`echo: ${event.data}`);
+                    } else {
+                        server.send(new Uint8Array(event.data).reverse());
+                    }
+                });
+
+                return new Response(null, { status: 101, webSocket: client });
+            },
+        };
+    "#;
+
+    fn spawn_server(idle_timeout: Duration, close_timeout: Duration) -> Result<SocketAddr> {
+        let addr = TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+
+        tokio::spawn(serve(ServerConfig {
+            addr,
+            handler: HANDLER.to_string(),
+            runtime: RuntimeConfig::default(),
+            metrics_path: None,
+            compression: false,
+            websocket_idle_timeout: idle_timeout,
+            websocket_close_timeout: close_timeout,
+        }));
+
+        Ok(addr)
+    }
+
+    // Connects once the server is listening
+    async fn connect(addr: SocketAddr) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>> {
+        let url = format!("ws://{addr}/chat");
+        let mut attempts = 0;
+
+        loop {
+            match connect_async(&url).await {
+                Ok((stream, response)) => {
+                    assert_eq!(101, response.status().as_u16());
+
+                    return Ok(stream);
+                }
+                Err(_) if attempts < 50 => {
+                    attempts += 1;
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+                Err(error) => return Err(error.into()),
+            }
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_websocket_server() -> Result<()> {
+        let addr = spawn_server(Duration::from_secs(60), Duration::from_secs(5))?;
+        let mut stream = connect(addr).await?;
+
+        stream.send(Message::Text("Hello".to_string())).await?;
+        assert_eq!(
+            Some(Message::Text("echo: Hello".to_string())),
+            stream.next().await.transpose()?
+        );
+
+        stream.send(Message::Binary(vec![1, 2, 3])).await?;
+        assert_eq!(
+            Some(Message::Binary(vec![3, 2, 1])),
+            stream.next().await.transpose()?
+        );
+
+        stream.send(Message::Text("close".to_string())).await?;
+
+        match stream.next().await.transpose()? {
+            Some(Message::Close(Some(frame))) => {
+                assert_eq!(CloseCode::from(3000), frame.code);
+                assert_eq!("Done", frame.reason);
+            }
+            message => panic!("expected a close frame, got {message:?}"),
+        }
+
+        assert!(stream.next().await.is_none());
+
+        // The handler responds with a WebSocket to a request which isn't an upgrade
+        let response = reqwest::get(format!("http://{addr}/chat")).await?;
+
+        assert_eq!(426, response.status().as_u16());
+
+        Ok(())
+    }
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_websocket_idle_timeout() -> Result<()> {
+        let addr = spawn_server(Duration::from_millis(300), Duration::from_secs(5))?;
+        let mut stream = connect(addr).await?;
+        let start = Instant::now();
+
+        match stream.next().await.transpose()? {
+            Some(Message::Close(Some(frame))) => assert_eq!(CloseCode::Away, frame.code),
+            message => panic!("expected a close frame, got {message:?}"),
+        }
+
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert!(stream.next().await.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_websocket_close_timeout() -> Result<()> {
+        let addr = spawn_server(Duration::from_secs(60), Duration::from_millis(300))?;
+        let mut attempts = 0;
+
+        // A raw connection, so the close frame of the handler isn't answered
+        let mut socket = loop {
+            match TcpStream::connect(addr).await {
+                Ok(socket) => break socket,
+                Err(_) if attempts < 50 => {
+                    attempts += 1;
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+                Err(error) => return Err(error.into()),
+            }
+        };
+
+        socket
+            .write_all(
+                format!(
+                    "GET /chat HTTP/1.1\r\nHost: {addr}\r\nConnection: Upgrade\r\nUpgrade: websocket\r\nSec-WebSocket-Version: 13\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n"
+                )
+                .as_bytes(),
+            )
+            .await?;
+
+        let mut response = vec![];
+
+        while !response.ends_with(b"\r\n\r\n") {
+            response.push(socket.read_u8().await?);
+        }
+
+        assert!(response.starts_with(b"HTTP/1.1 101"));
+
+        // A "close" text frame, masked with a zero key, to which the handler answers with a close
+        socket
+            .write_all(&[0x81, 0x85, 0, 0, 0, 0, b'c', b'l', b'o', b's', b'e'])
+            .await?;
+
+        let start = Instant::now();
+        let mut frames = vec![];
+
+        tokio::time::timeout(Duration::from_secs(5), socket.read_to_end(&mut frames)).await??;
+
+        // The close frame, then the connection is dropped
+        assert_eq!(Some(&0x88), frames.first());
+        assert!(start.elapsed() >= Duration::from_millis(300));
+
+        Ok(())
+    }
+}
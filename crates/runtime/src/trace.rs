@@ -0,0 +1,192 @@
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    fmt,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use tracing::Span;
+use wasmtime::*;
+
+use crate::{mem::read_string, worker_state::WorkerState};
+
+pub(crate) const TRACEPARENT: &str = "traceparent";
+
+/// The W3C `traceparent` header.
+///
+/// @see: https://www.w3.org/TR/trace-context/#traceparent-header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceParent {
+    pub trace_id: String,
+    pub parent_id: String,
+    pub flags: String,
+}
+
+impl TraceParent {
+    pub fn parse(value: &str) -> Option<Self> {
+        let parts: Vec<&str> = value.trim().split('-').collect();
+
+        match parts.as_slice() {
+            [version, trace_id, parent_id, flags]
+                if *version == "00"
+                    && is_hex_id(trace_id, 32)
+                    && is_hex_id(parent_id, 16)
+                    && flags.len() == 2
+                    && flags.chars().all(|c| c.is_ascii_hexdigit()) =>
+            {
+                Some(Self {
+                    trace_id: trace_id.to_lowercase(),
+                    parent_id: parent_id.to_lowercase(),
+                    flags: flags.to_lowercase(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// The same trace with a new parent id, used by the outbound requests
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id.clone(),
+            parent_id: format!("{:016x}", span_id()),
+            flags: self.flags.clone(),
+        }
+    }
+}
+
+impl fmt::Display for TraceParent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "00-{}-{}-{}", self.trace_id, self.parent_id, self.flags)
+    }
+}
+
+fn is_hex_id(value: &str, len: usize) -> bool {
+    value.len() == len
+        && value.chars().all(|c| c.is_ascii_hexdigit())
+        && value.chars().any(|c| c != '0')
+}
+
+fn span_id() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(1);
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+
+    // Zero is an invalid span id
+    hasher.finish().max(1)
+}
+
+pub(crate) fn find_traceparent(headers: &HashMap<String, String>) -> Option<TraceParent> {
+    headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(TRACEPARENT))
+        .and_then(|(_, value)| TraceParent::parse(value))
+}
+
+/// Links the invocation span to the trace of the incoming request
+#[cfg(feature = "otlp")]
+pub(crate) fn set_parent(span: &Span, headers: &HashMap<String, String>) {
+    use opentelemetry::propagation::TextMapPropagator;
+    use opentelemetry::sdk::propagation::TraceContextPropagator;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let headers = headers
+        .iter()
+        .map(|(name, value)| (name.to_lowercase(), value.clone()))
+        .collect::<HashMap<_, _>>();
+
+    span.set_parent(TraceContextPropagator::new().extract(&headers));
+}
+
+#[cfg(not(feature = "otlp"))]
+pub(crate) fn set_parent(_span: &Span, _headers: &HashMap<String, String>) {}
+
+/// The `traceparent` header of an outbound request sent inside of the span
+#[cfg(feature = "otlp")]
+pub(crate) fn outbound_traceparent(
+    span: &Span,
+    trace_parent: Option<&TraceParent>,
+) -> Option<String> {
+    use opentelemetry::propagation::TextMapPropagator;
+    use opentelemetry::sdk::propagation::TraceContextPropagator;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let mut headers = HashMap::new();
+
+    TraceContextPropagator::new().inject_context(&span.context(), &mut headers);
+
+    // Without an OpenTelemetry subscriber the span context is invalid, so nothing is injected
+    headers
+        .remove(TRACEPARENT)
+        .or_else(|| trace_parent.map(|trace_parent| trace_parent.child().to_string()))
+}
+
+/// The `traceparent` header of an outbound request sent inside of the span
+#[cfg(not(feature = "otlp"))]
+pub(crate) fn outbound_traceparent(
+    _span: &Span,
+    trace_parent: Option<&TraceParent>,
+) -> Option<String> {
+    trace_parent.map(|trace_parent| trace_parent.child().to_string())
+}
+
+pub(crate) fn import_span_enter(
+    mut caller: Caller<'_, WorkerState>,
+    ptr: i32,
+) -> Box<dyn Future<Output = ()> + Send + '_> {
+    Box::new(async move {
+        let memory = caller.get_export("memory").unwrap().into_memory().unwrap();
+        let name = read_string(&mut caller, &memory, ptr).await.to_string();
+        let parent = caller.data().current_span();
+        let span = tracing::info_span!(parent: &parent, "guest", otel.name = %name);
+
+        caller.data_mut().spans.push(span);
+    })
+}
+
+pub(crate) fn import_span_exit(mut caller: Caller<'_, WorkerState>) {
+    // Dropping the span closes it
+    caller.data_mut().spans.pop();
+}
+
+/// Installs a global `tracing` subscriber that exports the spans to an OTLP collector over HTTP.
+///
+/// It has to be called inside of a Tokio runtime.
+#[cfg(feature = "otlp")]
+pub fn init_otlp_tracing(endpoint: &str, service_name: &str) -> anyhow::Result<()> {
+    use opentelemetry::{
+        sdk::{trace, Resource},
+        KeyValue,
+    };
+    use opentelemetry_otlp::WithExportConfig;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .http()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(
+            trace::config().with_resource(Resource::new(vec![KeyValue::new(
+                "service.name",
+                service_name.to_string(),
+            )])),
+        )
+        .install_batch(opentelemetry::runtime::Tokio)?;
+
+    let subscriber =
+        tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    Ok(())
+}
+
+/// Exports the pending spans, before the process exits.
+#[cfg(feature = "otlp")]
+pub fn shutdown_otlp_tracing() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
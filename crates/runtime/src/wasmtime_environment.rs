@@ -1,32 +1,69 @@
-use std::sync::Arc;
+use std::{
+    collections::hash_map::DefaultHasher,
+    env,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use anyhow::Error;
-use wasi_common::WasiCtx;
-use wasmtime::{Config, Engine, Linker, Module};
+use wasmtime::{Engine, Linker, Module};
 
-use crate::import_send_request::import_send_request;
+use crate::{
+    engine_config::{engine_config, AllocationStrategy},
+    import_error::import_error,
+    import_kv::import_kv,
+    import_respond::import_respond,
+    import_send_request::import_send_request,
+    import_websocket::{import_websocket_receive, import_websocket_send},
+    module_cache::ModuleCache,
+    trace::{import_span_enter, import_span_exit},
+    worker_state::WorkerState,
+};
 
 static WASM: &[u8] =
     include_bytes!("../../../target/wasm32-wasi/release/js-wasm-workers-engine.wasm");
 
+#[cfg(feature = "aot")]
+static PRECOMPILED: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/js-wasm-workers-engine.cwasm"));
+
+// Directory of the compiled module cache, an empty value disables it
+const CACHE_DIR_ENV: &str = "JS_WASM_WORKERS_CACHE_DIR";
+
+/// The compiled engine, shared by the invocations.
 #[derive(Clone)]
 pub struct WasmtimeEnvironment {
     pub engine: Engine,
     pub module: Module,
-    pub linker: Arc<Linker<WasiCtx>>,
+    pub linker: Arc<Linker<WorkerState>>,
 }
 
 impl WasmtimeEnvironment {
     pub fn new() -> Result<Self, Error> {
-        let mut config = Config::new();
-        let engine = Engine::new(config.async_support(true))?;
-        let module = Module::from_binary(&engine, WASM)?;
+        Self::with_allocation_strategy(AllocationStrategy::default())
+    }
+
+    pub fn with_allocation_strategy(
+        allocation_strategy: AllocationStrategy,
+    ) -> Result<Self, Error> {
+        let engine = Engine::new(&engine_config(allocation_strategy))?;
+        let module = load_module(&engine, WASM, cache_dir().as_deref())?;
 
         let mut linker = Linker::new(&engine);
 
-        wasmtime_wasi::tokio::add_to_linker(&mut linker, |cx| cx)?;
+        wasmtime_wasi::tokio::add_to_linker(&mut linker, |state: &mut WorkerState| {
+            &mut state.wasi
+        })?;
 
         linker.func_wrap1_async("env", "import_send_request", import_send_request)?;
+        linker.func_wrap2_async("env", "import_kv", import_kv)?;
+        linker.func_wrap1_async("env", "import_respond", import_respond)?;
+        linker.func_wrap1_async("env", "import_error", import_error)?;
+        linker.func_wrap1_async("env", "import_websocket_send", import_websocket_send)?;
+        linker.func_wrap0_async("env", "import_websocket_receive", import_websocket_receive)?;
+        linker.func_wrap1_async("env", "import_span_enter", import_span_enter)?;
+        linker.func_wrap("env", "import_span_exit", import_span_exit)?;
 
         Ok(Self {
             engine,
@@ -41,3 +78,67 @@ impl Default for WasmtimeEnvironment {
         Self::new().unwrap()
     }
 }
+
+// A per-user directory by default, as the others could write native code in a shared one
+fn cache_dir() -> Option<PathBuf> {
+    let non_empty = |name| env::var_os(name).filter(|dir| !dir.is_empty());
+
+    match env::var_os(CACHE_DIR_ENV) {
+        Some(dir) if dir.is_empty() => None,
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => non_empty("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".cache")))
+            .map(|dir| dir.join("js-wasm-workers")),
+    }
+}
+
+// Loads the module avoiding the Cranelift compilation when possible, first from the module precompiled at
+// build time, then from the cache. A module that doesn't match the engine fails to deserialize, so it
+// falls back to compile it, as it does when the cache directory isn't private to the user.
+pub(crate) fn load_module(
+    engine: &Engine,
+    wasm: &[u8],
+    cache_dir: Option<&Path>,
+) -> Result<Module, Error> {
+    #[cfg(feature = "aot")]
+    if wasm == WASM {
+        // SAFETY: the bytes are created by `Engine::precompile_module` in the build script
+        if let Ok(module) = unsafe { Module::deserialize(engine, PRECOMPILED) } {
+            return Ok(module);
+        }
+    }
+
+    let Some(cache) = cache_dir.and_then(ModuleCache::open) else {
+        return Module::from_binary(engine, wasm);
+    };
+
+    let name = format!("{:016x}.cwasm", cache_key(engine, wasm));
+
+    if let Some(serialized) = cache.read(&name) {
+        // SAFETY: the bytes are authenticated as written by `ModuleCache::write`, from
+        // `Module::serialize`, in a directory only the user can write to
+        if let Ok(module) = unsafe { Module::deserialize(engine, serialized) } {
+            return Ok(module);
+        }
+    }
+
+    let module = Module::from_binary(engine, wasm)?;
+
+    // The cache is an optimization, so failing to write it isn't an error
+    if let Ok(serialized) = module.serialize() {
+        let _ = cache.write(&name, &serialized);
+    }
+
+    Ok(module)
+}
+
+// The key changes with the wasmtime version, the engine configuration and the module
+fn cache_key(engine: &Engine, wasm: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    engine.precompile_compatibility_hash().hash(&mut hasher);
+    wasm.hash(&mut hasher);
+
+    hasher.finish()
+}
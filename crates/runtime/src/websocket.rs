@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+/// A message of an upgraded connection, as relayed between the host and the handler.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum WebSocketMessage {
+    Text {
+        data: String,
+    },
+    Binary {
+        data: ByteBuf,
+    },
+    /// The last message, the code is 1006 when the connection is lost without a close frame
+    Close {
+        code: u16,
+        reason: String,
+    },
+}
+
+/// The host end of the WebSocket of an invocation, it only carries messages once the handler
+/// responded with the client end of a `WebSocketPair`.
+#[derive(Debug)]
+pub struct WebSocketRelay {
+    /// Sends the messages of the client to the handler
+    pub sender: UnboundedSender<WebSocketMessage>,
+    /// Receives the messages of the handler, it ends when the invocation finishes
+    pub receiver: UnboundedReceiver<WebSocketMessage>,
+}
+
+/// The handler end of the WebSocket, owned by the state of the invocation.
+#[derive(Debug)]
+pub(crate) struct WebSocketChannel {
+    pub incoming: UnboundedReceiver<WebSocketMessage>,
+    pub outgoing: UnboundedSender<WebSocketMessage>,
+}
+
+pub(crate) fn channel() -> (WebSocketRelay, WebSocketChannel) {
+    let (incoming_sender, incoming) = unbounded_channel();
+    let (outgoing, outgoing_receiver) = unbounded_channel();
+
+    (
+        WebSocketRelay {
+            sender: incoming_sender,
+            receiver: outgoing_receiver,
+        },
+        WebSocketChannel { incoming, outgoing },
+    )
+}
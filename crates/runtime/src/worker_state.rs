@@ -0,0 +1,54 @@
+use std::{collections::HashMap, sync::Arc};
+
+use tokio::sync::oneshot;
+use tracing::Span;
+use wasi_common::WasiCtx;
+
+use crate::{
+    bindings::KvNamespace, error::HandlerError, trace::TraceParent, websocket::WebSocketChannel,
+};
+
+/// The data owned by the store of each invocation.
+pub struct WorkerState {
+    pub wasi: WasiCtx,
+    /// The spans opened by the guest, closed in reverse order
+    pub spans: Vec<Span>,
+    /// The `traceparent` header of the incoming request
+    pub trace_parent: Option<TraceParent>,
+    pub outbound_requests: u64,
+    pub outbound_request_bytes: u64,
+    pub outbound_response_bytes: u64,
+    /// The KV namespace bindings, keyed by their name
+    pub kv_namespaces: HashMap<String, Arc<dyn KvNamespace>>,
+    /// The serialized response, once the handler has responded
+    pub response: Option<Vec<u8>>,
+    /// Notified when the handler responds, before the invocation finishes
+    pub response_sender: Option<oneshot::Sender<Vec<u8>>>,
+    /// Relays the messages of the connection, once the handler upgraded it
+    pub(crate) websocket: Option<WebSocketChannel>,
+    /// The error of the handler, when it fails
+    pub error: Option<HandlerError>,
+}
+
+impl WorkerState {
+    pub fn new(wasi: WasiCtx, trace_parent: Option<TraceParent>) -> Self {
+        Self {
+            wasi,
+            spans: vec![],
+            trace_parent,
+            outbound_requests: 0,
+            outbound_request_bytes: 0,
+            outbound_response_bytes: 0,
+            kv_namespaces: HashMap::new(),
+            response: None,
+            response_sender: None,
+            websocket: None,
+            error: None,
+        }
+    }
+
+    /// The span the next span has to be child of. Outside of a guest span, it is the `_start` span.
+    pub fn current_span(&self) -> Span {
+        self.spans.last().cloned().unwrap_or_else(Span::current)
+    }
+}
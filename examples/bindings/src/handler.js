@@ -0,0 +1,15 @@
+export default {
+    async fetch(request, env, ctx) {
+        const visits = Number((await env.VISITS.get("count")) || 0) + 1;
+
+        // Runs after the response is sent
+        ctx.waitUntil(env.VISITS.put("count", String(visits)));
+
+        return new Response(`${env.GREETING} visitor #${visits}`, {
+            status: 200,
+            headers: {
+                "content-type": "text/plain;charset=UTF-8",
+            },
+        });
+    },
+};
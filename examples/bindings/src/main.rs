@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use js_wasm_workers_runtime::{spawn_with_config, Binding, MemoryKvNamespace, RuntimeConfig};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let handler: &str = include_str!("./handler.js");
+
+    let request = r#"{
+        "body": null,
+        "headers": {},
+        "method": "GET",
+        "url": "https://test.test"
+    }"#
+    .to_string();
+
+    let config = RuntimeConfig {
+        bindings: [
+            (
+                "GREETING".to_string(),
+                Binding::Var(serde_json::json!("Hello")),
+            ),
+            (
+                "VISITS".to_string(),
+                Binding::Kv(Arc::new(MemoryKvNamespace::new())),
+            ),
+        ]
+        .into(),
+        ..Default::default()
+    };
+
+    for _ in 0..2 {
+        let invocation = spawn_with_config(handler, &request, &config).await?;
+
+        println!("returned: {:?}", String::from_utf8(invocation.response)?);
+
+        // Waits for `ctx.waitUntil`, so the next request sees the new count
+        invocation.completion.await??;
+    }
+
+    Ok(())
+}
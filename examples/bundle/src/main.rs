@@ -0,0 +1,37 @@
+use std::env;
+
+use anyhow::Result;
+use js_wasm_workers_runtime::{bundle, runtime_with_bundle, Bundle, RuntimeConfig};
+
+// cargo run --example bundle -- <entry> [output]
+#[tokio::main]
+async fn main() -> Result<()> {
+    let mut args = env::args().skip(1);
+    let entry = args.next().unwrap_or_else(|| {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/modules/src/handler.js").to_string()
+    });
+    let output = args
+        .next()
+        .unwrap_or_else(|| "handler.bundle.json".to_string());
+
+    bundle(&entry)?.write(&output)?;
+
+    println!("bundled {entry} into {output}");
+
+    // The artifact is all the runtime needs to run the handler
+    let bundle = Bundle::read(&output)?;
+
+    let request = r#"{
+        "body": null,
+        "headers": {},
+        "method": "GET",
+        "url": "https://test.test/bundle"
+    }"#
+    .to_string();
+
+    let output = runtime_with_bundle(&bundle, &request, &RuntimeConfig::default()).await?;
+
+    println!("returned: {:?}", String::from_utf8(output.response)?);
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use std::{env, fs};
+
+use anyhow::{bail, Result};
+use js_wasm_workers_runtime::{bundle, compile, compile_script};
+
+// cargo run --example compile -- bundle <entry> <output>
+// cargo run --example compile -- script <input> <output>
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["bundle", entry, output] => compile(&bundle(entry)?).await?.write(output)?,
+        ["script", input, output] => {
+            fs::write(output, compile_script(&fs::read_to_string(input)?).await?)?
+        }
+        _ => bail!("Usage: compile <bundle|script> <input> <output>"),
+    }
+
+    println!("compiled {} into {}", args[1], args[2]);
+
+    Ok(())
+}
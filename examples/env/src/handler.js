@@ -1,5 +1,8 @@
-export const handleRequest = function (request) {
-    ___logger("process.env:", JSON.stringify(process.env));
+export default {
+    fetch(request, env) {
+        // The secret is redacted from the logs
+        console.log("process.env:", JSON.stringify(process.env));
 
-    return "";
+        return new Response(`FOO=${env.FOO}`);
+    },
 };
@@ -0,0 +1,13 @@
+export const handleRequest = async function (request) {
+    console.info("method:", request.method);
+    console.debug("headers: %o", request.headers.getAll());
+    console.warn("url: %s", request.url);
+    console.table([{ name: "a", value: 1 }, { name: "b", value: 2 }]);
+
+    return new Response("", {
+        status: 200,
+        headers: {
+            "content-type": "text/html;charset=UTF-8",
+        },
+    });
+};
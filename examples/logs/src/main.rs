@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use js_wasm_workers_runtime::{runtime_with_config, LogEntry, LogSink, RuntimeConfig};
+
+struct StdoutSink;
+
+impl LogSink for StdoutSink {
+    fn log(&self, entry: &LogEntry) {
+        println!("[{}] {}: {}", entry.worker_id, entry.level, entry.message);
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let handler: &str = include_str!("./handler.js");
+
+    let request = r#"{
+        "body": null,
+        "headers": {
+            "content-type": "application/json"
+        },
+        "method": "GET",
+        "url": "https://test.test"
+    }"#
+    .to_string();
+
+    let config = RuntimeConfig {
+        worker_id: "logs-example".to_string(),
+        log_sink: Some(Arc::new(StdoutSink)),
+        ..Default::default()
+    };
+
+    let output = runtime_with_config(handler, &request, &config).await?;
+
+    println!("returned: {:?}", String::from_utf8(output.response)?);
+    println!("logs: {:#?}", output.logs);
+    println!("metrics: {:#?}", output.metrics);
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+import { greeting } from "./lib/greeting.js";
+
+export default {
+    async fetch(request) {
+        const { pathname } = new URL(request.url);
+
+        return new Response(greeting(pathname), {
+            status: 200,
+            headers: {
+                "content-type": "text/plain;charset=UTF-8",
+            },
+        });
+    },
+};
@@ -0,0 +1,26 @@
+use anyhow::Result;
+use js_wasm_workers_runtime::{read_modules, runtime_with_config, RuntimeConfig};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let handler: &str = include_str!("./handler.js");
+
+    let request = r#"{
+        "body": null,
+        "headers": {},
+        "method": "GET",
+        "url": "https://test.test/world"
+    }"#
+    .to_string();
+
+    let config = RuntimeConfig {
+        modules: read_modules(concat!(env!("CARGO_MANIFEST_DIR"), "/modules/src"))?,
+        ..Default::default()
+    };
+
+    let output = runtime_with_config(handler, &request, &config).await?;
+
+    println!("returned: {:?}", String::from_utf8(output.response)?);
+
+    Ok(())
+}
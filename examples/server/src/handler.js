@@ -0,0 +1,14 @@
+const html = `<!DOCTYPE html>
+<body>
+  <h1>__TITLE__</h1>
+  <p>This markup was generated by a JS WASM Worker.</p>
+</body>`;
+
+export const handleRequest = async function (_) {
+    return new Response(html.replace("__TITLE__", "Hello World!"), {
+        status: 200,
+        headers: {
+            "content-type": "text/html;charset=UTF-8",
+        },
+    });
+};
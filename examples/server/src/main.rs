@@ -0,0 +1,27 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use js_wasm_workers_runtime::{serve, RuntimeConfig, ServerConfig};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let handler: &str = include_str!("./handler.js");
+
+    let config = ServerConfig {
+        addr: ([127, 0, 0, 1], 8080).into(),
+        handler: handler.to_string(),
+        runtime: RuntimeConfig {
+            worker_id: "server-example".to_string(),
+            ..Default::default()
+        },
+        metrics_path: Some("/metrics".to_string()),
+        compression: true,
+        websocket_idle_timeout: Duration::from_secs(300),
+        websocket_close_timeout: Duration::from_secs(5),
+    };
+
+    println!("Listening on http://{}", config.addr);
+    println!("Metrics on http://{}/metrics", config.addr);
+
+    serve(config).await
+}